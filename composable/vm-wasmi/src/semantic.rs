@@ -25,8 +25,8 @@ use cosmwasm_std::IbcTimeout;
 use cosmwasm_std::Order;
 use cosmwasm_std::{
 	Addr, Attribute, Binary, BlockInfo, CanonicalAddr, CodeInfoResponse, Coin, ContractInfo,
-	ContractInfoResponse, ContractResult, Empty, Env, Event, MessageInfo, Reply, SystemResult,
-	Timestamp,
+	ContractInfoResponse, ContractResult, DenomMetadata, Empty, Env, Event, MessageInfo, Reply,
+	SystemResult, Timestamp,
 };
 use cosmwasm_vm::{
 	executor::{
@@ -485,6 +485,11 @@ impl<'a> VMBase for SimpleWasmiVM<'a> {
 		Err(SimpleVMError::Unsupported)
 	}
 
+	fn denom_metadata(&mut self, _: String) -> Result<DenomMetadata, Self::Error> {
+		log::debug!("Denom metadata.");
+		Err(SimpleVMError::Unsupported)
+	}
+
 	fn all_balance(&mut self, _: &Self::Address) -> Result<Vec<Coin>, Self::Error> {
 		log::debug!("Query all balance.");
 		Ok(vec![])
@@ -10,8 +10,8 @@ use core::{
 #[cfg(feature = "iterator")]
 use cosmwasm_std::Order;
 use cosmwasm_std::{
-	Addr, Binary, CanonicalAddr, CodeInfoResponse, Coin, ContractInfoResponse, Env, Event,
-	MessageInfo, Reply, SystemResult,
+	Addr, Binary, CanonicalAddr, CodeInfoResponse, Coin, ContractInfoResponse, DenomMetadata, Env,
+	Event, MessageInfo, Reply, SystemResult,
 };
 use cosmwasm_vm::{
 	executor::{CosmwasmQueryResult, ExecutorError, QueryResult},
@@ -283,6 +283,11 @@ where
 		self.0.as_context_mut().data_mut().supply(denom)
 	}
 
+	fn denom_metadata(&mut self, denom: String) -> Result<DenomMetadata, Self::Error> {
+		self.charge(VmGas::DenomMetadata)?;
+		self.0.as_context_mut().data_mut().denom_metadata(denom)
+	}
+
 	fn query_contract_info(
 		&mut self,
 		address: Self::Address,
@@ -32,8 +32,9 @@ use alloc::{
 use core::fmt::Debug;
 use cosmwasm_std::{
 	Addr, AllBalanceResponse, Attribute, BalanceResponse, BankMsg, BankQuery, Binary,
-	ContractResult, CosmosMsg, Env, Event, MessageInfo, QueryRequest, Reply, ReplyOn, Response,
-	SubMsgResponse, SubMsgResult, SupplyResponse, SystemResult, WasmMsg, WasmQuery,
+	ContractResult, CosmosMsg, DenomMetadataResponse, Env, Event, MessageInfo, QueryRequest, Reply,
+	ReplyOn, Response, SubMsgResponse, SubMsgResult, SupplyResponse, SystemResult, WasmMsg,
+	WasmQuery,
 };
 #[cfg(feature = "stargate")]
 use cosmwasm_std::{Empty, IbcMsg};
@@ -861,6 +862,12 @@ where
 					serde_json::to_vec(&supply).map_err(|_| SystemError::FailedToSerialize)?;
 				Ok(SystemResult::Ok(ContractResult::Ok(Binary::new(serialized_info))))
 			},
+			BankQuery::DenomMetadata { denom } => {
+				let metadata = DenomMetadataResponse::new(vm.denom_metadata(denom)?);
+				let serialized_info =
+					serde_json::to_vec(&metadata).map_err(|_| SystemError::FailedToSerialize)?;
+				Ok(SystemResult::Ok(ContractResult::Ok(Binary::new(serialized_info))))
+			},
 			_ => Err(SystemError::UnsupportedMessage.into()),
 		},
 		QueryRequest::Wasm(wasm_query) => match wasm_query {
@@ -10,7 +10,7 @@ use cosmwasm_std::IbcTimeout;
 #[cfg(feature = "iterator")]
 use cosmwasm_std::Order;
 use cosmwasm_std::{
-	Binary, CodeInfoResponse, Coin, ContractInfoResponse, Event, Reply, SystemResult,
+	Binary, CodeInfoResponse, Coin, ContractInfoResponse, DenomMetadata, Event, Reply, SystemResult,
 };
 
 use serde::de::DeserializeOwned;
@@ -63,6 +63,8 @@ pub enum VmGas {
 	AllBalance,
 	/// Cost of `supply`
 	Supply,
+	/// Cost of `denom_metadata`.
+	DenomMetadata,
 	/// Cost of `query_contract_info`.
 	QueryContractInfo,
 	/// Cost of `query_code_info`.
@@ -292,6 +294,9 @@ pub trait VMBase {
 	/// Query for the supply of a `denom`.
 	fn supply(&mut self, denom: String) -> Result<Coin, Self::Error>;
 
+	/// Query for the metadata of a `denom`.
+	fn denom_metadata(&mut self, denom: String) -> Result<DenomMetadata, Self::Error>;
+
 	/// Query the contract info.
 	fn query_contract_info(
 		&mut self,
@@ -21,23 +21,36 @@
 
 extern crate alloc;
 
+pub mod migrations;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
 pub mod weights;
 
 use crate::weights::WeightInfo;
-use alloc::vec::Vec;
+use alloc::{collections::BTreeSet, vec::Vec};
 #[cfg(feature = "std")]
 use frame_support::traits::BuildGenesisConfig;
+use frame_support::{traits::OnNewAccount, BoundedVec};
 use hp_crypto::EcdsaExt;
 pub use pallet::*;
 use sp_core::H160;
 
+/// The maximum length of an encoded public key, enough for a multisig of many keys.
+pub const MAX_PUB_KEY_LENGTH: u32 = 2048;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::config]
@@ -63,14 +76,45 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type Connections<T: Config> = StorageMap<_, Blake2_128Concat, H160, T::AccountId>;
 
+	/// Cosmos account numbers, keyed by address.
+	#[pallet::storage]
+	pub type AccountNumbers<T: Config> = StorageMap<_, Blake2_128Concat, H160, u64>;
+
+	/// The account number assigned to the next address seen.
+	#[pallet::storage]
+	pub type NextAccountNumber<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// Protobuf-encoded public keys of the addresses that have signed a transaction.
+	#[pallet::storage]
+	pub type PubKeys<T: Config> =
+		StorageMap<_, Blake2_128Concat, H160, BoundedVec<u8, ConstU32<MAX_PUB_KEY_LENGTH>>>;
+
+	/// Progress of the account numbering started by [`migrations::v1::MigrateToV1`].
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub type MigrationCursor<T: Config> = StorageValue<_, migrations::v1::Cursor>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T>
+	where
+		T::AccountId: EcdsaExt,
+	{
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			migrations::v1::step::<T>(remaining_weight)
+		}
+	}
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub accounts: Vec<T::AccountId>,
+		/// Account numbers assigned to addresses beforehand, such as those exported by another
+		/// chain.
+		pub account_numbers: Vec<(H160, u64)>,
 	}
 
 	impl<T: Config> Default for GenesisConfig<T> {
 		fn default() -> Self {
-			Self { accounts: Default::default() }
+			Self { accounts: Default::default(), account_numbers: Default::default() }
 		}
 	}
 
@@ -80,6 +124,19 @@ pub mod pallet {
 		T::AccountId: EcdsaExt,
 	{
 		fn build(&self) {
+			let mut numbers = BTreeSet::new();
+			for (address, number) in self.account_numbers.iter() {
+				assert!(
+					!AccountNumbers::<T>::contains_key(address),
+					"Address {:?} is numbered twice",
+					address
+				);
+				assert!(numbers.insert(*number), "Account number {} is assigned twice", number);
+				AccountNumbers::<T>::insert(address, number);
+				if *number >= NextAccountNumber::<T>::get() {
+					NextAccountNumber::<T>::put(number.saturating_add(1));
+				}
+			}
 			for account in self.accounts.iter() {
 				let _ = Pallet::<T>::connect_account(account);
 			}
@@ -108,8 +165,58 @@ pub mod pallet {
 		pub fn connect_account(who: &T::AccountId) -> Result<(), DispatchError> {
 			let address = who.to_cosmos_address().ok_or(Error::<T>::DeriveFailed)?;
 			Connections::<T>::insert(address, who);
+			Self::ensure_account_number(&address);
 			Self::deposit_event(Event::<T>::Connected { address, who: who.clone() });
 			Ok(())
 		}
 	}
 }
+
+impl<T: Config> Pallet<T> {
+	/// The account number of `address`, if one has been assigned.
+	pub fn account_number(address: &H160) -> Option<u64> {
+		AccountNumbers::<T>::get(address)
+	}
+
+	/// The account number the next address seen will be assigned.
+	pub fn next_account_number() -> u64 {
+		NextAccountNumber::<T>::get()
+	}
+
+	/// The encoded public key stored for `address`, if any.
+	pub fn pub_key(address: &H160) -> Option<Vec<u8>> {
+		PubKeys::<T>::get(address).map(Into::into)
+	}
+
+	/// Stores the encoded public key of `address`. Keys over [`MAX_PUB_KEY_LENGTH`] are not
+	/// stored.
+	pub fn set_pub_key(address: &H160, pub_key: Vec<u8>) {
+		if let Ok(pub_key) = BoundedVec::try_from(pub_key) {
+			PubKeys::<T>::insert(address, pub_key);
+		}
+	}
+
+	/// Assigns the next account number to `address` unless it already has one.
+	pub fn ensure_account_number(address: &H160) -> u64 {
+		AccountNumbers::<T>::get(address).unwrap_or_else(|| {
+			let number = NextAccountNumber::<T>::get();
+			AccountNumbers::<T>::insert(address, number);
+			NextAccountNumber::<T>::put(number.saturating_add(1));
+
+			number
+		})
+	}
+}
+
+/// Numbers accounts with a Cosmos address as they are created. Interim accounts have no
+/// address to derive and are numbered when their address is first used instead.
+impl<T: Config> OnNewAccount<T::AccountId> for Pallet<T>
+where
+	T::AccountId: EcdsaExt,
+{
+	fn on_new_account(who: &T::AccountId) {
+		if let Some(address) = who.to_cosmos_address() {
+			Self::ensure_account_number(&address);
+		}
+	}
+}
@@ -0,0 +1,154 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+pub mod v1 {
+	use crate::{Config, Connections, MigrationCursor, Pallet};
+	use alloc::vec::Vec;
+	use core::marker::PhantomData;
+	use frame_support::{
+		storage::KeyPrefixIterator,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		weights::{RuntimeDbWeight, Weight},
+	};
+	use hp_crypto::EcdsaExt;
+	use parity_scale_codec::{Decode, Encode};
+	use scale_info::TypeInfo;
+	use sp_core::{Get, H160};
+
+	/// Where the account numbering left off, as the last raw storage key visited.
+	#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode, TypeInfo)]
+	pub enum Cursor {
+		/// Numbering connected addresses.
+		Connections(Option<Vec<u8>>),
+		/// Numbering the addresses of existing accounts.
+		Accounts(Option<Vec<u8>>),
+	}
+
+	/// Assigns account numbers to the addresses of existing accounts.
+	///
+	/// The upgrade only starts the migration; the accounts are numbered in `on_idle` with the
+	/// weight left in each block until none remain. Interim accounts cannot be mapped back to
+	/// their addresses, so they are numbered when their address is first used after the upgrade.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= 1 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			MigrationCursor::<T>::put(Cursor::Connections(None));
+			StorageVersion::new(1).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(1, 2)
+		}
+	}
+
+	struct Meter {
+		db: RuntimeDbWeight,
+		limit: Weight,
+		reads: u64,
+		writes: u64,
+	}
+
+	impl Meter {
+		/// Whether one more address can be numbered and the cursor still saved.
+		fn can_take(&self) -> bool {
+			let next = self
+				.db
+				.reads_writes(self.reads.saturating_add(3), self.writes.saturating_add(3));
+			self.limit.all_gte(next)
+		}
+
+		fn consumed(&self) -> Weight {
+			self.db.reads_writes(self.reads, self.writes)
+		}
+	}
+
+	/// Numbers the addresses of `keys` while the weight allows, returning whether all were done.
+	fn number_addresses<T: Config, K>(
+		keys: &mut KeyPrefixIterator<K>,
+		address_of: impl Fn(K) -> Option<H160>,
+		meter: &mut Meter,
+	) -> bool {
+		while meter.can_take() {
+			meter.reads = meter.reads.saturating_add(1);
+			let Some(key) = keys.next() else { return true };
+
+			if let Some(address) = address_of(key) {
+				meter.reads = meter.reads.saturating_add(1);
+				if Pallet::<T>::account_number(&address).is_none() {
+					Pallet::<T>::ensure_account_number(&address);
+					meter.reads = meter.reads.saturating_add(1);
+					meter.writes = meter.writes.saturating_add(2);
+				}
+			}
+		}
+
+		false
+	}
+
+	/// Continues the account numbering started by [`MigrateToV1`] within `limit`, returning the
+	/// weight consumed.
+	pub fn step<T: Config>(limit: Weight) -> Weight
+	where
+		T::AccountId: EcdsaExt,
+	{
+		let mut meter = Meter { db: T::DbWeight::get(), limit, reads: 1, writes: 0 };
+		if !limit.all_gte(meter.consumed()) {
+			return Weight::zero();
+		}
+		let Some(mut cursor) = MigrationCursor::<T>::get() else {
+			return meter.consumed();
+		};
+
+		while meter.can_take() {
+			cursor = match cursor {
+				Cursor::Connections(last) => {
+					let mut keys = match last {
+						Some(key) => Connections::<T>::iter_keys_from(key),
+						None => Connections::<T>::iter_keys(),
+					};
+					if number_addresses::<T, _>(&mut keys, Some, &mut meter) {
+						Cursor::Accounts(None)
+					} else {
+						Cursor::Connections(Some(keys.last_raw_key().to_vec()))
+					}
+				},
+				Cursor::Accounts(last) => {
+					let mut keys = match last {
+						Some(key) => frame_system::Account::<T>::iter_keys_from(key),
+						None => frame_system::Account::<T>::iter_keys(),
+					};
+					let address_of = |who: T::AccountId| who.to_cosmos_address();
+					if number_addresses::<T, _>(&mut keys, address_of, &mut meter) {
+						MigrationCursor::<T>::kill();
+						meter.writes = meter.writes.saturating_add(1);
+						return meter.consumed();
+					}
+					Cursor::Accounts(Some(keys.last_raw_key().to_vec()))
+				},
+			};
+		}
+
+		MigrationCursor::<T>::put(cursor);
+		meter.writes = meter.writes.saturating_add(1);
+
+		meter.consumed()
+	}
+}
@@ -0,0 +1,60 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate as pallet_cosmos_accounts;
+use frame_support::{derive_impl, weights::constants::RocksDbWeight};
+use hp_account::CosmosSigner;
+use sp_core::{ecdsa, Pair};
+use sp_runtime::{traits::IdentityLookup, BuildStorage};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+pub type AccountId = CosmosSigner;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		CosmosAccounts: pallet_cosmos_accounts,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type DbWeight = RocksDbWeight;
+	type OnNewAccount = CosmosAccounts;
+}
+
+impl pallet_cosmos_accounts::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_cosmos_accounts::weights::CosmosWeight<Test>;
+}
+
+pub fn account(seed: &str) -> AccountId {
+	CosmosSigner(ecdsa::Pair::from_string(seed, None).unwrap().public())
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
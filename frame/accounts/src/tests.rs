@@ -0,0 +1,166 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	migrations::v1::{self, Cursor, MigrateToV1},
+	mock::*,
+	AccountNumbers, Connections, GenesisConfig, MigrationCursor, MAX_PUB_KEY_LENGTH,
+};
+use frame_support::{
+	assert_ok,
+	traits::{BuildGenesisConfig, Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::{constants::RocksDbWeight, Weight},
+};
+use hp_account::CosmosSigner;
+use hp_crypto::EcdsaExt;
+use sp_core::{ecdsa, H160};
+
+#[test]
+fn account_number_test() {
+	new_test_ext().execute_with(|| {
+		let alice = account("//Alice");
+		let bob = account("//Bob");
+		let alice_address = alice.to_cosmos_address().unwrap();
+		let bob_address = bob.to_cosmos_address().unwrap();
+
+		assert_eq!(CosmosAccounts::account_number(&alice_address), None);
+		assert_eq!(CosmosAccounts::next_account_number(), 0);
+
+		// Accounts with a key are numbered as they are created.
+		System::inc_providers(&alice);
+		System::inc_providers(&bob);
+		assert_eq!(CosmosAccounts::account_number(&alice_address), Some(0));
+		assert_eq!(CosmosAccounts::account_number(&bob_address), Some(1));
+
+		// Numbers are kept when the account is created again.
+		assert_ok!(System::dec_providers(&alice));
+		System::inc_providers(&alice);
+		assert_eq!(CosmosAccounts::ensure_account_number(&alice_address), 0);
+		assert_ok!(CosmosAccounts::connect(RuntimeOrigin::signed(alice)));
+		assert_eq!(CosmosAccounts::account_number(&alice_address), Some(0));
+
+		// Interim accounts have no address to number.
+		let interim = CosmosSigner(ecdsa::Public([0u8; 33]));
+		System::inc_providers(&interim);
+		assert_eq!(CosmosAccounts::next_account_number(), 2);
+
+		let address = H160::repeat_byte(1);
+		assert_eq!(CosmosAccounts::ensure_account_number(&address), 2);
+		assert_eq!(CosmosAccounts::account_number(&address), Some(2));
+		assert_eq!(CosmosAccounts::next_account_number(), 3);
+	});
+}
+
+#[test]
+fn pub_keys_test() {
+	new_test_ext().execute_with(|| {
+		let address = H160::repeat_byte(1);
+		assert_eq!(CosmosAccounts::pub_key(&address), None);
+
+		CosmosAccounts::set_pub_key(&address, vec![1, 2, 3]);
+		assert_eq!(CosmosAccounts::pub_key(&address), Some(vec![1, 2, 3]));
+
+		// Keys over the limit are not stored.
+		CosmosAccounts::set_pub_key(&address, vec![0; MAX_PUB_KEY_LENGTH as usize + 1]);
+		assert_eq!(CosmosAccounts::pub_key(&address), Some(vec![1, 2, 3]));
+
+		let pub_key = vec![0; MAX_PUB_KEY_LENGTH as usize];
+		CosmosAccounts::set_pub_key(&address, pub_key.clone());
+		assert_eq!(CosmosAccounts::pub_key(&address), Some(pub_key));
+	});
+}
+
+#[test]
+fn migrate_to_v1_test() {
+	new_test_ext().execute_with(|| {
+		let alice = account("//Alice");
+		let bob = account("//Bob");
+		let charlie = account("//Charlie");
+		let interim = CosmosSigner(ecdsa::Public([0u8; 33]));
+		let connected = H160::repeat_byte(1);
+
+		// Accounts created before the upgrade were not numbered.
+		for who in [alice, bob, charlie, interim] {
+			frame_system::Account::<Test>::insert(who, frame_system::AccountInfo::default());
+		}
+		Connections::<Test>::insert(connected, interim);
+		Connections::<Test>::insert(alice.to_cosmos_address().unwrap(), alice);
+		StorageVersion::new(0).put::<CosmosAccounts>();
+
+		assert_eq!(
+			MigrateToV1::<Test>::on_runtime_upgrade(),
+			RocksDbWeight::get().reads_writes(1, 2)
+		);
+		assert_eq!(CosmosAccounts::on_chain_storage_version(), 1);
+		assert_eq!(MigrationCursor::<Test>::get(), Some(Cursor::Connections(None)));
+
+		// Too little weight to do anything.
+		assert_eq!(v1::step::<Test>(Weight::zero()), Weight::zero());
+
+		// Enough weight to number one address per block.
+		let limit = RocksDbWeight::get().reads_writes(4, 3);
+		let mut steps = 0;
+		while MigrationCursor::<Test>::get().is_some() {
+			assert!(limit.all_gte(v1::step::<Test>(limit)));
+			steps += 1;
+			assert!(steps < 10);
+		}
+
+		let mut numbers: Vec<u64> = AccountNumbers::<Test>::iter_values().collect();
+		numbers.sort();
+		assert_eq!(numbers, vec![0, 1, 2, 3]);
+		assert_eq!(CosmosAccounts::next_account_number(), 4);
+		for who in [alice, bob, charlie] {
+			assert!(CosmosAccounts::account_number(&who.to_cosmos_address().unwrap()).is_some());
+		}
+		assert!(CosmosAccounts::account_number(&connected).is_some());
+
+		// Nothing is left to do.
+		assert_eq!(v1::step::<Test>(limit), RocksDbWeight::get().reads(1));
+		assert_eq!(MigrateToV1::<Test>::on_runtime_upgrade(), RocksDbWeight::get().reads(1));
+	});
+}
+
+#[test]
+fn genesis_account_numbers_test() {
+	new_test_ext().execute_with(|| {
+		let alice = account("//Alice");
+		let imported = H160::repeat_byte(1);
+		GenesisConfig::<Test> {
+			accounts: vec![alice.clone()],
+			account_numbers: vec![(imported, 7), (H160::repeat_byte(2), 3)],
+		}
+		.build();
+
+		assert_eq!(CosmosAccounts::account_number(&imported), Some(7));
+		// Accounts are numbered after the imported ones.
+		assert_eq!(CosmosAccounts::account_number(&alice.to_cosmos_address().unwrap()), Some(8));
+	});
+}
+
+#[test]
+#[should_panic(expected = "Account number 7 is assigned twice")]
+fn genesis_duplicate_account_number_test() {
+	new_test_ext().execute_with(|| {
+		GenesisConfig::<Test> {
+			accounts: vec![],
+			account_numbers: vec![(H160::repeat_byte(1), 7), (H160::repeat_byte(2), 7)],
+		}
+		.build();
+	});
+}
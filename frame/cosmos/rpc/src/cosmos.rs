@@ -16,19 +16,35 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::{internal_error, request_error};
-use cosmos_runtime_api::{CosmosRuntimeApi, SimulateError, SimulateResponse};
-use futures::future::TryFutureExt;
+use crate::{index, internal_error, not_found_error, request_error};
+use cosmos_runtime_api::{
+	AccountError, BalancesResponse, BaseAccount, BroadcastTxCommitResponse, BroadcastTxResponse,
+	Coin, ConsensusParams, CosmosError, CosmosRuntimeApi, EventQuery, Metadata, PageRequest,
+	QueryError, RootError, SimulateError, SimulateWithGasRecordsResponse, SupplyResponse, TxEvents,
+	TxResult, TxSearchResponse,
+};
+use futures::{future::TryFutureExt, StreamExt};
 use jsonrpsee::{
-	core::{async_trait, RpcResult},
+	core::{async_trait, RpcResult, SubscriptionResult},
 	proc_macros::rpc,
+	types::ErrorObjectOwned,
+	PendingSubscriptionSink, SubscriptionMessage,
+};
+use parity_scale_codec::Decode;
+use sc_client_api::BlockchainEvents;
+use sc_transaction_pool_api::{
+	error::{Error as PoolError, IntoPoolError},
+	TransactionPool, TransactionStatus,
 };
-use sc_transaction_pool_api::TransactionPool;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
-use sp_core::{sha2_256, Bytes, H256};
-use sp_runtime::{traits::Block as BlockT, transaction_validity::TransactionSource};
-use std::sync::Arc;
+use sp_core::{offchain::OffchainStorage, sha2_256, Bytes, H256};
+use sp_offchain::STORAGE_PREFIX;
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto},
+	transaction_validity::{InvalidTransaction, TransactionSource, UnknownTransaction},
+};
+use std::{sync::Arc, time::Duration};
 
 #[rpc(server)]
 #[async_trait]
@@ -36,30 +52,312 @@ pub trait CosmosApi {
 	#[method(name = "cosmos_broadcastTx")]
 	async fn broadcast_tx(&self, tx_bytes: Bytes) -> RpcResult<H256>;
 
+	/// Submits the tx and returns the result of checking it for the pool.
+	#[method(name = "cosmos_broadcastTxSync")]
+	async fn broadcast_tx_sync(&self, tx_bytes: Bytes) -> RpcResult<BroadcastTxResponse>;
+
+	/// Submits the tx and waits until it is included in a block, or dropped from the pool, for at
+	/// most `TIMEOUT_BROADCAST_TX_COMMIT`.
+	#[method(name = "cosmos_broadcastTxCommit")]
+	async fn broadcast_tx_commit(&self, tx_bytes: Bytes) -> RpcResult<BroadcastTxCommitResponse>;
+
+	#[method(name = "cosmos_getTx")]
+	async fn get_tx(&self, hash: H256) -> RpcResult<TxResult>;
+
+	/// The txs whose events match `query`, in the query syntax of CometBFT. Queries that require
+	/// an attribute value, such as `transfer.recipient='cosmos1...'`, are looked up in the index
+	/// of finalized blocks; the others scan the finalized blocks within their bounds on
+	/// `tx.height`. Txs in blocks yet to be finalized are not found, as with `cosmos_getTx`.
+	#[method(name = "cosmos_txSearch")]
+	async fn tx_search(
+		&self,
+		query: String,
+		page: Option<u32>,
+		per_page: Option<u32>,
+		order_by: Option<String>,
+	) -> RpcResult<TxSearchResponse>;
+
+	/// Streams the events of the txs in each new best block whose events match `query`, in the
+	/// query syntax of CometBFT.
+	#[subscription(
+		name = "cosmos_subscribeEvents" => "cosmos_events",
+		unsubscribe = "cosmos_unsubscribeEvents",
+		item = TxEvents
+	)]
+	async fn subscribe_events(&self, query: String) -> SubscriptionResult;
+
 	#[method(name = "cosmos_simulate")]
-	async fn simulate(&self, tx_bytes: Bytes) -> RpcResult<SimulateResponse>;
+	async fn simulate(&self, tx_bytes: Bytes) -> RpcResult<SimulateWithGasRecordsResponse>;
+
+	#[method(name = "cosmos_consensusParams")]
+	async fn consensus_params(&self) -> RpcResult<ConsensusParams>;
+
+	#[method(name = "cosmos_account")]
+	async fn account(&self, address: String) -> RpcResult<BaseAccount>;
+
+	#[method(name = "cosmos_balance")]
+	async fn balance(&self, address: String, denom: String) -> RpcResult<Coin>;
+
+	#[method(name = "cosmos_allBalances")]
+	async fn all_balances(
+		&self,
+		address: String,
+		pagination: Option<PageRequest>,
+	) -> RpcResult<BalancesResponse>;
+
+	#[method(name = "cosmos_spendableBalances")]
+	async fn spendable_balances(
+		&self,
+		address: String,
+		pagination: Option<PageRequest>,
+	) -> RpcResult<BalancesResponse>;
+
+	#[method(name = "cosmos_totalSupply")]
+	async fn total_supply(&self, pagination: Option<PageRequest>) -> RpcResult<SupplyResponse>;
+
+	#[method(name = "cosmos_supplyOf")]
+	async fn supply_of(&self, denom: String) -> RpcResult<Coin>;
+
+	#[method(name = "cosmos_denomMetadata")]
+	async fn denom_metadata(&self, denom: String) -> RpcResult<Metadata>;
+
+	/// Answers a protobuf-encoded request to the gRPC query method at `path`.
+	#[method(name = "cosmos_abciQuery")]
+	async fn abci_query(&self, path: String, data: Bytes) -> RpcResult<Bytes>;
+}
+
+fn query_error(e: QueryError) -> ErrorObjectOwned {
+	match e {
+		QueryError::InvalidAddress => request_error("Invalid address"),
+		QueryError::InvalidRequest => request_error("Invalid request"),
+		QueryError::NotFound => not_found_error("Not found"),
+		QueryError::UnknownRequest => request_error("Unknown request"),
+	}
+}
+
+/// The error a Cosmos app would return from CheckTx for a tx the pool rejected. Errors that
+/// are not about the tx itself are returned as they are.
+fn check_tx_error<E: IntoPoolError>(hash: [u8; 32], e: E) -> RpcResult<BroadcastTxResponse> {
+	let e = e.into_pool_error().map_err(internal_error)?;
+	let error = match &e {
+		PoolError::InvalidTransaction(e) => match e {
+			InvalidTransaction::Payment => RootError::InsufficientFee,
+			InvalidTransaction::Future | InvalidTransaction::Stale => RootError::WrongSequence,
+			InvalidTransaction::BadProof | InvalidTransaction::BadSigner => RootError::Unauthorized,
+			InvalidTransaction::ExhaustsResources => RootError::OutOfGas,
+			InvalidTransaction::Call => RootError::InvalidRequest,
+			_ => RootError::Internal,
+		},
+		PoolError::UnknownTransaction(UnknownTransaction::CannotLookup) =>
+			RootError::UnknownAddress,
+		PoolError::UnknownTransaction(_) => RootError::InvalidRequest,
+		PoolError::AlreadyImported(_) | PoolError::TemporarilyBanned => RootError::TxInMempoolCache,
+		PoolError::TooLowPriority { .. } | PoolError::ImmediatelyDropped =>
+			RootError::MempoolIsFull,
+		_ => return Err(internal_error(e)),
+	};
+	let CosmosError { codespace, code } = error.into();
+
+	Ok(BroadcastTxResponse { hash, codespace, code, log: e.to_string() })
 }
 
-pub struct Cosmos<C, P> {
+/// How long `cosmos_broadcastTxCommit` waits for the tx to be included in a block, the default
+/// `timeout_broadcast_tx_commit` of CometBFT.
+const TIMEOUT_BROADCAST_TX_COMMIT: Duration = Duration::from_secs(10);
+
+/// Txs per page of `cosmos_txSearch` results, unless requested otherwise.
+const DEFAULT_PER_PAGE: u32 = 30;
+const MAX_PER_PAGE: u32 = 100;
+/// Blocks a `cosmos_txSearch` query may go through when the attribute index cannot narrow it.
+const MAX_SCANNED_BLOCKS: u64 = 10_000;
+
+pub struct Cosmos<C, P, S> {
 	client: Arc<C>,
 	pool: Arc<P>,
+	/// Where the runtime indexes the results of Cosmos txs.
+	offchain_storage: Option<S>,
 }
 
-impl<C, P> Cosmos<C, P> {
-	pub fn new(client: Arc<C>, pool: Arc<P>) -> Self {
-		Self { client, pool }
+impl<C, P, S> Cosmos<C, P, S>
+where
+	S: OffchainStorage,
+{
+	pub fn new(client: Arc<C>, pool: Arc<P>, offchain_storage: Option<S>) -> Self {
+		Self { client, pool, offchain_storage }
+	}
+
+	fn offchain_storage(&self) -> RpcResult<&S> {
+		self.offchain_storage
+			.as_ref()
+			.ok_or_else(|| internal_error("Offchain storage is not available"))
+	}
+
+	/// The indexed results of the Cosmos txs in the block with `hash`, in the order they were
+	/// applied.
+	pub fn block_tx_results<Block>(&self, hash: Block::Hash) -> RpcResult<Vec<TxResult>>
+	where
+		Block: BlockT,
+		C: HeaderBackend<Block>,
+	{
+		let header = self
+			.client
+			.header(hash)
+			.map_err(internal_error)?
+			.ok_or_else(|| not_found_error(format!("block {:?} not found", hash)))?;
+
+		index::block_tx_results(self.offchain_storage()?, &header).map_err(internal_error)
+	}
+
+	/// The header of the block at `height` if it is finalized. Txs are looked up in finalized
+	/// blocks only, the same ones their attributes are indexed for.
+	fn finalized_header<Block>(&self, height: u64) -> RpcResult<Option<Block::Header>>
+	where
+		Block: BlockT,
+		C: HeaderBackend<Block>,
+	{
+		let finalized: u64 = self.client.info().finalized_number.unique_saturated_into();
+		if height > finalized {
+			return Ok(None);
+		}
+		let Some(hash) =
+			self.client.hash(height.unique_saturated_into()).map_err(internal_error)?
+		else {
+			return Ok(None);
+		};
+
+		self.client
+			.header(hash)
+			.map_err(internal_error)?
+			.ok_or_else(|| internal_error(format!("block {:?} not found", hash)))
+			.map(Some)
+	}
+
+	/// The indexed results of the Cosmos txs in the finalized block at `height`.
+	pub fn block_txs<Block>(&self, height: u64) -> RpcResult<Vec<TxResult>>
+	where
+		Block: BlockT,
+		C: HeaderBackend<Block>,
+	{
+		match self.finalized_header::<Block>(height)? {
+			Some(header) =>
+				index::block_tx_results(self.offchain_storage()?, &header).map_err(internal_error),
+			None => Ok(Vec::new()),
+		}
+	}
+
+	/// The indexed result of the tx with SHA-256 `hash`, if a finalized block includes it.
+	pub fn tx_result<Block>(&self, hash: &[u8; 32]) -> RpcResult<Option<TxResult>>
+	where
+		Block: BlockT,
+		C: HeaderBackend<Block>,
+	{
+		let Some(value) =
+			self.offchain_storage()?.get(STORAGE_PREFIX, &cosmos_runtime_api::tx_key(hash))
+		else {
+			return Ok(None);
+		};
+		let (height, extrinsics_root) =
+			<(u64, Vec<u8>)>::decode(&mut &*value).map_err(internal_error)?;
+
+		let Some(header) = self.finalized_header::<Block>(height)? else {
+			return Ok(None);
+		};
+		// The block that last included the tx was reorged away.
+		if header.extrinsics_root().as_ref() != extrinsics_root.as_slice() {
+			return Ok(None);
+		}
+
+		Ok(index::block_tx_results(self.offchain_storage()?, &header)
+			.map_err(internal_error)?
+			.into_iter()
+			.find(|tx_result| tx_result.hash == *hash))
+	}
+
+	/// The txs in finalized blocks whose events match `query`, in the order of their blocks.
+	pub fn search_txs<Block>(
+		&self,
+		query: &EventQuery,
+		descending: bool,
+	) -> RpcResult<Vec<TxResult>>
+	where
+		Block: BlockT,
+		C: HeaderBackend<Block>,
+	{
+		let (lower, upper) = query.range("tx.height");
+		let mut txs = Vec::new();
+
+		let mut exact_matches = query.exact_matches();
+		if let Some((key, value)) = exact_matches.next() {
+			// Only the txs with every attribute the query requires verbatim are candidates.
+			let storage = self.offchain_storage()?;
+			let mut positions =
+				index::txs_with_attribute(storage, key, value).map_err(internal_error)?;
+			for (key, value) in exact_matches {
+				let other =
+					index::txs_with_attribute(storage, key, value).map_err(internal_error)?;
+				positions.retain(|position| other.contains(position));
+			}
+			positions.retain(|(height, _)| (lower..=upper).contains(height));
+
+			// Positions are in order, so each block is read once.
+			let (mut block_height, mut tx_results) = (0, Vec::new());
+			for (height, index) in positions {
+				if height != block_height {
+					(block_height, tx_results) = (height, self.block_txs::<Block>(height)?);
+				}
+				let Some(tx_result) = tx_results.iter().find(|tx_result| tx_result.index == index)
+				else {
+					continue;
+				};
+				let attributes = tx_result.attributes();
+				if query.matches(attributes.iter().map(|(k, v)| (k.as_str(), v.as_str()))) {
+					txs.push(tx_result.clone());
+				}
+			}
+			if descending {
+				txs.reverse();
+			}
+		} else {
+			let finalized: u64 = self.client.info().finalized_number.unique_saturated_into();
+			let (lower, upper) = (lower.max(1), upper.min(finalized));
+			if upper.saturating_sub(lower) >= MAX_SCANNED_BLOCKS {
+				return Err(request_error(format!(
+					"query must require an attribute value or span at most {} blocks",
+					MAX_SCANNED_BLOCKS
+				)));
+			}
+			let heights = lower..=upper;
+			let heights: Box<dyn Iterator<Item = u64> + Send> =
+				if descending { Box::new(heights.rev()) } else { Box::new(heights) };
+
+			for height in heights {
+				let mut tx_results = self.block_txs::<Block>(height)?;
+				if descending {
+					tx_results.reverse();
+				}
+				for tx_result in tx_results {
+					let attributes = tx_result.attributes();
+					if query.matches(attributes.iter().map(|(k, v)| (k.as_str(), v.as_str()))) {
+						txs.push(tx_result);
+					}
+				}
+			}
+		}
+
+		Ok(txs)
 	}
 }
 
 #[async_trait]
-impl<Block, C, P> CosmosApiServer for Cosmos<C, P>
+impl<Block, C, P, S> CosmosApiServer for Cosmos<C, P, S>
 where
 	Block: BlockT,
 	C: Send + Sync + 'static,
 	C: ProvideRuntimeApi<Block>,
-	C: HeaderBackend<Block> + 'static,
+	C: HeaderBackend<Block> + BlockchainEvents<Block> + 'static,
 	C::Api: cosmos_runtime_api::CosmosRuntimeApi<Block>,
 	P: TransactionPool<Block = Block> + 'static,
+	S: OffchainStorage + 'static,
 {
 	async fn broadcast_tx(&self, tx_bytes: Bytes) -> RpcResult<H256> {
 		let best_hash = self.client.info().best_hash;
@@ -76,16 +374,417 @@ where
 			.await
 	}
 
-	async fn simulate(&self, tx_bytes: Bytes) -> RpcResult<SimulateResponse> {
+	async fn broadcast_tx_sync(&self, tx_bytes: Bytes) -> RpcResult<BroadcastTxResponse> {
+		let hash = sha2_256(&tx_bytes);
+		let best_hash = self.client.info().best_hash;
+		let extrinsic = self
+			.client
+			.runtime_api()
+			.convert_tx(best_hash, tx_bytes.to_vec())
+			.map_err(internal_error)?;
+
+		match self.pool.submit_one(best_hash, TransactionSource::Local, extrinsic).await {
+			Ok(_) => Ok(BroadcastTxResponse { hash, codespace: 0, code: 0, log: String::new() }),
+			Err(e) => check_tx_error(hash, e),
+		}
+	}
+
+	async fn broadcast_tx_commit(&self, tx_bytes: Bytes) -> RpcResult<BroadcastTxCommitResponse> {
+		let hash = sha2_256(&tx_bytes);
+		let best_hash = self.client.info().best_hash;
+		let extrinsic = self
+			.client
+			.runtime_api()
+			.convert_tx(best_hash, tx_bytes.to_vec())
+			.map_err(internal_error)?;
+
+		let mut statuses = match self
+			.pool
+			.submit_and_watch(best_hash, TransactionSource::Local, extrinsic)
+			.await
+		{
+			Ok(statuses) => statuses,
+			Err(e) =>
+				return Ok(BroadcastTxCommitResponse {
+					check_tx: check_tx_error(hash, e)?,
+					tx_result: None,
+				}),
+		};
+		let check_tx = BroadcastTxResponse { hash, codespace: 0, code: 0, log: String::new() };
+
+		let tx_result = async {
+			while let Some(status) = statuses.next().await {
+				match status {
+					TransactionStatus::InBlock((block_hash, _)) =>
+						return self.block_tx_results::<Block>(block_hash).and_then(|tx_results| {
+							tx_results
+								.into_iter()
+								.find(|tx_result| tx_result.hash == hash)
+								.ok_or_else(|| internal_error("Tx result is not indexed"))
+						}),
+					TransactionStatus::Usurped(_) |
+					TransactionStatus::Dropped |
+					TransactionStatus::Invalid => break,
+					_ => {},
+				}
+			}
+
+			Err(internal_error(format!("tx {:?} was dropped from the pool", H256(hash))))
+		};
+		let tx_result = tokio::time::timeout(TIMEOUT_BROADCAST_TX_COMMIT, tx_result)
+			.await
+			.map_err(|_| internal_error("timed out waiting for tx to be included in a block"))??;
+
+		Ok(BroadcastTxCommitResponse { check_tx, tx_result: Some(tx_result) })
+	}
+
+	async fn get_tx(&self, hash: H256) -> RpcResult<TxResult> {
+		self.tx_result::<Block>(&hash.0)?
+			.ok_or_else(|| not_found_error(format!("tx {:?} not found", hash)))
+	}
+
+	async fn tx_search(
+		&self,
+		query: String,
+		page: Option<u32>,
+		per_page: Option<u32>,
+		order_by: Option<String>,
+	) -> RpcResult<TxSearchResponse> {
+		let query = EventQuery::parse(&query)
+			.map_err(|e| request_error(format!("Invalid query at position {}", e.position)))?;
+		let descending = match order_by.as_deref() {
+			None | Some("") | Some("asc") => false,
+			Some("desc") => true,
+			Some(_) => return Err(request_error("order_by must be \"asc\" or \"desc\"")),
+		};
+		let per_page = match per_page {
+			None | Some(0) => DEFAULT_PER_PAGE,
+			Some(per_page) => per_page.min(MAX_PER_PAGE),
+		};
+
+		let txs = self.search_txs::<Block>(&query, descending)?;
+
+		let total_count = txs.len() as u32;
+		let pages = total_count.div_ceil(per_page).max(1);
+		let page = page.unwrap_or(1);
+		if page == 0 || page > pages {
+			return Err(request_error(format!(
+				"page should be within [1, {}] range, given {}",
+				pages, page
+			)));
+		}
+		let txs = txs
+			.into_iter()
+			.skip(((page - 1) * per_page) as usize)
+			.take(per_page as usize)
+			.collect();
+
+		Ok(TxSearchResponse { txs, total_count })
+	}
+
+	async fn subscribe_events(
+		&self,
+		pending: PendingSubscriptionSink,
+		query: String,
+	) -> SubscriptionResult {
+		let query = match EventQuery::parse(&query) {
+			Ok(query) => query,
+			Err(e) => {
+				pending
+					.reject(request_error(format!("Invalid query at position {}", e.position)))
+					.await;
+				return Ok(());
+			},
+		};
+		// Events are read back from the tx results the runtime indexes offchain.
+		if let Err(e) = self.offchain_storage() {
+			pending.reject(e).await;
+			return Ok(());
+		}
+		let mut blocks = self.client.import_notification_stream();
+		let sink = pending.accept().await?;
+
+		while let Some(block) = blocks.next().await {
+			if !block.is_new_best {
+				continue;
+			}
+			let height: u64 = (*block.header.number()).unique_saturated_into();
+			let tx_results = match self.block_tx_results::<Block>(block.hash) {
+				Ok(tx_results) => tx_results,
+				Err(e) => {
+					log::warn!("Failed to read Cosmos tx results of block {:?}: {}", block.hash, e);
+					continue;
+				},
+			};
+			for tx_result in tx_results {
+				let attributes = tx_result.attributes();
+				if query.matches(attributes.iter().map(|(k, v)| (k.as_str(), v.as_str()))) {
+					let events =
+						TxEvents { hash: tx_result.hash, height, events: tx_result.events };
+					sink.send(SubscriptionMessage::from_json(&events)?).await?;
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	async fn simulate(&self, tx_bytes: Bytes) -> RpcResult<SimulateWithGasRecordsResponse> {
+		let best_hash = self.client.info().best_hash;
+
+		let api = self.client.runtime_api();
+		let version = api
+			.api_version::<dyn CosmosRuntimeApi<Block>>(best_hash)
+			.map_err(internal_error)?
+			.unwrap_or_default();
+
+		// Runtimes before version 2 do not record what the gas is consumed for.
+		let result = if version >= 2 {
+			api.simulate_with_gas_records(best_hash, tx_bytes.to_vec())
+		} else {
+			api.simulate(best_hash, tx_bytes.to_vec()).map(|result| {
+				result.map(|response| SimulateWithGasRecordsResponse {
+					response,
+					gas_records: Vec::new(),
+				})
+			})
+		};
+
+		result.map_err(internal_error)?.map_err(|e| match e {
+			SimulateError::InvalidTx => request_error("Invalid tx"),
+			SimulateError::InternalError(e) => internal_error(String::from_utf8_lossy(&e)),
+		})
+	}
+
+	async fn consensus_params(&self) -> RpcResult<ConsensusParams> {
+		let best_hash = self.client.info().best_hash;
+
+		self.client.runtime_api().consensus_params(best_hash).map_err(internal_error)
+	}
+
+	async fn account(&self, address: String) -> RpcResult<BaseAccount> {
 		let best_hash = self.client.info().best_hash;
 
 		self.client
 			.runtime_api()
-			.simulate(best_hash, tx_bytes.to_vec())
+			.account(best_hash, address.clone())
 			.map_err(internal_error)?
 			.map_err(|e| match e {
-				SimulateError::InvalidTx => request_error("Invalid tx"),
-				SimulateError::InternalError(e) => internal_error(String::from_utf8_lossy(&e)),
+				AccountError::InvalidAddress => request_error("Invalid address"),
+				AccountError::NotFound => not_found_error(format!("account {} not found", address)),
 			})
 	}
+
+	async fn balance(&self, address: String, denom: String) -> RpcResult<Coin> {
+		let best_hash = self.client.info().best_hash;
+
+		self.client
+			.runtime_api()
+			.balance(best_hash, address, denom)
+			.map_err(internal_error)?
+			.map_err(query_error)
+	}
+
+	async fn all_balances(
+		&self,
+		address: String,
+		pagination: Option<PageRequest>,
+	) -> RpcResult<BalancesResponse> {
+		let best_hash = self.client.info().best_hash;
+
+		self.client
+			.runtime_api()
+			.all_balances(best_hash, address, pagination)
+			.map_err(internal_error)?
+			.map_err(query_error)
+	}
+
+	async fn spendable_balances(
+		&self,
+		address: String,
+		pagination: Option<PageRequest>,
+	) -> RpcResult<BalancesResponse> {
+		let best_hash = self.client.info().best_hash;
+
+		self.client
+			.runtime_api()
+			.spendable_balances(best_hash, address, pagination)
+			.map_err(internal_error)?
+			.map_err(query_error)
+	}
+
+	async fn total_supply(&self, pagination: Option<PageRequest>) -> RpcResult<SupplyResponse> {
+		let best_hash = self.client.info().best_hash;
+
+		self.client
+			.runtime_api()
+			.total_supply(best_hash, pagination)
+			.map_err(internal_error)?
+			.map_err(query_error)
+	}
+
+	async fn supply_of(&self, denom: String) -> RpcResult<Coin> {
+		let best_hash = self.client.info().best_hash;
+
+		self.client.runtime_api().supply_of(best_hash, denom).map_err(internal_error)
+	}
+
+	async fn denom_metadata(&self, denom: String) -> RpcResult<Metadata> {
+		let best_hash = self.client.info().best_hash;
+
+		self.client
+			.runtime_api()
+			.denom_metadata(best_hash, denom.clone())
+			.map_err(internal_error)?
+			.map_err(|e| match e {
+				QueryError::NotFound =>
+					not_found_error(format!("metadata for denom {} not found", denom)),
+				e => query_error(e),
+			})
+	}
+
+	async fn abci_query(&self, path: String, data: Bytes) -> RpcResult<Bytes> {
+		let best_hash = self.client.info().best_hash;
+
+		self.client
+			.runtime_api()
+			.abci_query(best_hash, path.clone(), data.to_vec())
+			.map_err(internal_error)?
+			.map(Bytes)
+			.map_err(|e| match e {
+				QueryError::UnknownRequest => request_error(format!("unknown query path {}", path)),
+				e => query_error(e),
+			})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Cosmos;
+	use crate::index;
+	use cosmos_runtime_api::{EventQuery, TxResult};
+	use pallet_cosmos_types::events::{CosmosEvent, EventAttribute};
+	use parity_scale_codec::Encode;
+	use sp_blockchain::{BlockStatus, HeaderBackend, Info};
+	use sp_core::{
+		offchain::{storage::InMemOffchainStorage, OffchainStorage},
+		H256,
+	};
+	use sp_offchain::STORAGE_PREFIX;
+	use sp_runtime::{
+		testing::{Block as TestBlock, ExtrinsicWrapper, Header},
+		traits::Header as HeaderT,
+	};
+	use std::sync::Arc;
+
+	type Block = TestBlock<ExtrinsicWrapper<u64>>;
+
+	/// A chain of blocks `0..headers.len()`, finalized up to `finalized`.
+	struct Client {
+		headers: Vec<Header>,
+		finalized: u64,
+	}
+
+	impl HeaderBackend<Block> for Client {
+		fn header(&self, hash: H256) -> sp_blockchain::Result<Option<Header>> {
+			Ok(self.headers.iter().find(|header| header.hash() == hash).cloned())
+		}
+
+		fn info(&self) -> Info<Block> {
+			let best = self.headers.last().unwrap();
+			let finalized = &self.headers[self.finalized as usize];
+			Info {
+				best_hash: best.hash(),
+				best_number: best.number,
+				genesis_hash: self.headers[0].hash(),
+				finalized_hash: finalized.hash(),
+				finalized_number: finalized.number,
+				finalized_state: None,
+				number_leaves: 1,
+				block_gap: None,
+			}
+		}
+
+		fn status(&self, hash: H256) -> sp_blockchain::Result<BlockStatus> {
+			Ok(match self.header(hash)? {
+				Some(_) => BlockStatus::InChain,
+				None => BlockStatus::Unknown,
+			})
+		}
+
+		fn number(&self, hash: H256) -> sp_blockchain::Result<Option<u64>> {
+			Ok(self.header(hash)?.map(|header| header.number))
+		}
+
+		fn hash(&self, number: u64) -> sp_blockchain::Result<Option<H256>> {
+			Ok(self.headers.get(number as usize).map(|header| header.hash()))
+		}
+	}
+
+	fn tx_result(height: u64) -> TxResult {
+		TxResult {
+			hash: [height as u8; 32],
+			height,
+			index: 0,
+			codespace: 0,
+			code: 0,
+			gas_wanted: 0,
+			gas_used: 0,
+			events: vec![CosmosEvent {
+				r#type: b"transfer".to_vec(),
+				attributes: vec![EventAttribute {
+					key: b"recipient".to_vec(),
+					value: b"alice".to_vec(),
+				}],
+			}],
+			log: String::new(),
+		}
+	}
+
+	#[test]
+	fn unfinalized_tx_test() {
+		let header = |number, extrinsics_root, parent_hash| {
+			Header::new(number, extrinsics_root, H256::zero(), parent_hash, Default::default())
+		};
+		let mut headers = vec![header(0, H256::zero(), H256::zero())];
+		let mut storage = InMemOffchainStorage::default();
+		// Block #1 is finalized and block #2 is the best block, each with a tx for alice.
+		for height in 1..=2 {
+			let header =
+				header(height, H256::repeat_byte(height as u8), headers.last().unwrap().hash());
+
+			let tx_result = tx_result(height);
+			let extrinsics_root = header.extrinsics_root.as_bytes().to_vec();
+			storage.set(
+				STORAGE_PREFIX,
+				&cosmos_runtime_api::block_txs_key(height, &extrinsics_root),
+				&vec![tx_result.clone()].encode(),
+			);
+			storage.set(
+				STORAGE_PREFIX,
+				&cosmos_runtime_api::tx_key(&tx_result.hash),
+				&(height, extrinsics_root).encode(),
+			);
+			headers.push(header);
+		}
+		// As the indexer does, only the finalized block is indexed by attributes.
+		index::index_block(&mut storage, 1, &[tx_result(1)]).unwrap();
+
+		let client = Client { headers, finalized: 1 };
+		let cosmos = Cosmos::new(Arc::new(client), Arc::new(()), Some(storage));
+		let search = |query: &str| {
+			let query = EventQuery::parse(query).unwrap();
+			let txs = cosmos.search_txs::<Block>(&query, false).unwrap();
+			txs.into_iter().map(|tx_result| tx_result.height).collect::<Vec<_>>()
+		};
+
+		assert_eq!(cosmos.tx_result::<Block>(&[1; 32]).unwrap(), Some(tx_result(1)));
+		assert_eq!(cosmos.tx_result::<Block>(&[2; 32]).unwrap(), None);
+		assert!(cosmos.block_txs::<Block>(2).unwrap().is_empty());
+		// Looked up in the index.
+		assert_eq!(search("transfer.recipient='alice'"), vec![1]);
+		// Scanned block by block.
+		assert_eq!(search("tx.height>=1"), vec![1]);
+		assert_eq!(search("transfer.recipient EXISTS"), vec![1]);
+	}
 }
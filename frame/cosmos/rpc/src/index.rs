@@ -0,0 +1,211 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! An index of the attributes of the events of the Cosmos txs in finalized blocks, by which
+//! `cosmos_txSearch` finds txs without reading every block.
+
+use cosmos_runtime_api::TxResult;
+use futures::StreamExt;
+use parity_scale_codec::{Decode, Encode, Error};
+use sc_client_api::BlockchainEvents;
+use sp_blockchain::HeaderBackend;
+use sp_core::offchain::OffchainStorage;
+use sp_offchain::STORAGE_PREFIX;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto};
+use std::{collections::BTreeSet, sync::Arc};
+
+/// Offchain storage key of the height up to which blocks are indexed.
+const INDEXED_HEIGHT_KEY: &[u8] = b"cosmos/indexed_height";
+
+/// Prefix of the offchain storage keys of the txs with each attribute.
+const ATTRIBUTE_KEY_PREFIX: &[u8] = b"cosmos/attribute/";
+
+/// Txs are listed under each attribute in chunks of this many, so that indexing a tx
+/// rewrites a single chunk rather than the whole list.
+const CHUNK_LEN: u32 = 256;
+
+/// A tx by the height of its block and its index among the extrinsics of the block.
+pub type TxPosition = (u64, u32);
+
+fn attribute_key(key: &str, value: &str) -> Vec<u8> {
+	[ATTRIBUTE_KEY_PREFIX, &(key, value).encode()].concat()
+}
+
+fn chunk_key(attribute_key: &[u8], chunk: u32) -> Vec<u8> {
+	[attribute_key, &chunk.to_be_bytes()].concat()
+}
+
+fn get<S: OffchainStorage, T: Decode>(storage: &S, key: &[u8]) -> Result<Option<T>, Error> {
+	storage
+		.get(STORAGE_PREFIX, key)
+		.map(|value| T::decode(&mut &*value))
+		.transpose()
+}
+
+/// The indexed results of the Cosmos txs in the block with `header`, in the order they were
+/// applied.
+pub fn block_tx_results<S, H>(storage: &S, header: &H) -> Result<Vec<TxResult>, Error>
+where
+	S: OffchainStorage,
+	H: HeaderT,
+{
+	let key = cosmos_runtime_api::block_txs_key(
+		(*header.number()).unique_saturated_into(),
+		header.extrinsics_root().as_ref(),
+	);
+
+	Ok(get(storage, &key)?.unwrap_or_default())
+}
+
+/// The positions of the indexed txs with an attribute of composite key `key` and `value`.
+pub fn txs_with_attribute<S>(
+	storage: &S,
+	key: &str,
+	value: &str,
+) -> Result<BTreeSet<TxPosition>, Error>
+where
+	S: OffchainStorage,
+{
+	let attribute_key = attribute_key(key, value);
+	let len: u32 = get(storage, &attribute_key)?.unwrap_or_default();
+
+	let mut positions = BTreeSet::new();
+	for chunk in 0..len.div_ceil(CHUNK_LEN) {
+		let txs: Option<Vec<TxPosition>> = get(storage, &chunk_key(&attribute_key, chunk))?;
+		positions.extend(txs.unwrap_or_default());
+	}
+
+	Ok(positions)
+}
+
+fn add_tx<S>(storage: &mut S, key: &str, value: &str, position: TxPosition) -> Result<(), Error>
+where
+	S: OffchainStorage,
+{
+	let attribute_key = attribute_key(key, value);
+	let len: u32 = get(storage, &attribute_key)?.unwrap_or_default();
+
+	let chunk_key = chunk_key(&attribute_key, len / CHUNK_LEN);
+	let mut txs: Vec<TxPosition> = get(storage, &chunk_key)?.unwrap_or_default();
+	txs.push(position);
+	storage.set(STORAGE_PREFIX, &chunk_key, &txs.encode());
+	storage.set(STORAGE_PREFIX, &attribute_key, &len.saturating_add(1).encode());
+
+	Ok(())
+}
+
+pub(crate) fn index_block<S>(
+	storage: &mut S,
+	height: u64,
+	tx_results: &[TxResult],
+) -> Result<(), Error>
+where
+	S: OffchainStorage,
+{
+	for tx_result in tx_results {
+		let attributes = tx_result.attributes().into_iter().collect::<BTreeSet<_>>();
+		for (key, value) in attributes {
+			add_tx(storage, &key, &value, (height, tx_result.index))?;
+		}
+	}
+	storage.set(STORAGE_PREFIX, INDEXED_HEIGHT_KEY, &height.encode());
+
+	Ok(())
+}
+
+/// Indexes the Cosmos txs of each block once it is finalized, and so can no longer be
+/// reorged away, resuming from the last block indexed.
+pub async fn run<Block, C, S>(client: Arc<C>, mut storage: S) -> Result<(), String>
+where
+	Block: BlockT,
+	C: HeaderBackend<Block> + BlockchainEvents<Block>,
+	S: OffchainStorage,
+{
+	let mut finality_notifications = client.finality_notification_stream();
+
+	loop {
+		let indexed: u64 = get(&storage, INDEXED_HEIGHT_KEY)
+			.map_err(|e| e.to_string())?
+			.unwrap_or_default();
+		let finalized: u64 = client.info().finalized_number.unique_saturated_into();
+
+		for height in indexed.saturating_add(1)..=finalized {
+			let hash = client
+				.hash(height.unique_saturated_into())
+				.map_err(|e| e.to_string())?
+				.ok_or_else(|| format!("Finalized block #{} not found", height))?;
+			let header = client
+				.header(hash)
+				.map_err(|e| e.to_string())?
+				.ok_or_else(|| format!("Finalized block {:?} not found", hash))?;
+			let tx_results = block_tx_results(&storage, &header).map_err(|e| e.to_string())?;
+			index_block(&mut storage, height, &tx_results).map_err(|e| e.to_string())?;
+		}
+
+		if finality_notifications.next().await.is_none() {
+			return Ok(());
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{index_block, txs_with_attribute, CHUNK_LEN};
+	use cosmos_runtime_api::TxResult;
+	use pallet_cosmos_types::events::{CosmosEvent, EventAttribute};
+	use sp_core::offchain::storage::InMemOffchainStorage;
+
+	fn tx_result(index: u32, recipient: &str) -> TxResult {
+		TxResult {
+			hash: [index as u8; 32],
+			height: 0,
+			index,
+			codespace: 0,
+			code: 0,
+			gas_wanted: 0,
+			gas_used: 0,
+			events: vec![CosmosEvent {
+				r#type: b"transfer".to_vec(),
+				attributes: vec![EventAttribute {
+					key: b"recipient".to_vec(),
+					value: recipient.as_bytes().to_vec(),
+				}],
+			}],
+			log: String::new(),
+		}
+	}
+
+	#[test]
+	fn index_block_test() {
+		let mut storage = InMemOffchainStorage::default();
+
+		index_block(&mut storage, 1, &[tx_result(1, "alice"), tx_result(2, "bob")]).unwrap();
+		let txs = (0..CHUNK_LEN + 1).map(|index| tx_result(index, "alice")).collect::<Vec<_>>();
+		index_block(&mut storage, 2, &txs).unwrap();
+
+		let alice = txs_with_attribute(&storage, "transfer.recipient", "alice").unwrap();
+		assert_eq!(alice.len(), CHUNK_LEN as usize + 2);
+		assert_eq!(alice.first(), Some(&(1, 1)));
+		assert_eq!(alice.last(), Some(&(2, CHUNK_LEN)));
+
+		let bob = txs_with_attribute(&storage, "transfer.recipient", "bob").unwrap();
+		assert_eq!(bob.into_iter().collect::<Vec<_>>(), vec![(1, 2)]);
+		assert!(txs_with_attribute(&storage, "transfer.recipient", "carol").unwrap().is_empty());
+		assert!(txs_with_attribute(&storage, "transfer.sender", "alice").unwrap().is_empty());
+	}
+}
@@ -17,6 +17,7 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 pub mod cosmos;
+pub mod index;
 
 use jsonrpsee::{
 	core::to_json_raw_value,
@@ -41,6 +42,13 @@ pub fn request_error<T: ToString>(message: T) -> ErrorObjectOwned {
 	error(INVALID_REQUEST_CODE, message, None)
 }
 
+/// Returned when the queried state, such as an account, does not exist.
+pub const NOT_FOUND_CODE: i32 = -32004;
+
+pub fn not_found_error<T: ToString>(message: T) -> ErrorObjectOwned {
+	error(NOT_FOUND_CODE, message, None)
+}
+
 pub fn internal_error<T: ToString>(message: T) -> ErrorObjectOwned {
 	error(INTERNAL_ERROR_CODE, message, None)
 }
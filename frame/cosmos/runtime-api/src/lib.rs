@@ -19,12 +19,24 @@
 
 extern crate alloc;
 
-use alloc::vec::Vec;
-use pallet_cosmos_types::{events::CosmosEvent, gas::Gas};
+use alloc::{string::String, vec::Vec};
+pub use pallet_cosmos_types::{
+	coin::Coin,
+	errors::{CosmosError, RootError},
+	events::query::EventQuery,
+	query::{PageRequest, PageResponse, QueryError},
+	tx_result::{block_txs_key, tx_key, TxResult},
+};
+use pallet_cosmos_types::{
+	events::CosmosEvent,
+	gas::{Gas, GasRecord},
+};
+pub use pallet_cosmos_x_bank_types::metadata::Metadata;
 use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 use sp_api::decl_runtime_apis;
+use sp_core::H160;
 use sp_runtime::traits::Block as BlockT;
 
 #[derive(Clone, Decode, Encode, Debug, TypeInfo, Serialize, Deserialize)]
@@ -39,6 +51,15 @@ pub struct SimulateResponse {
 	pub events: Vec<CosmosEvent>,
 }
 
+/// A [`SimulateResponse`] along with what its gas was consumed for.
+#[derive(Clone, Decode, Encode, Debug, TypeInfo, Serialize, Deserialize)]
+pub struct SimulateWithGasRecordsResponse {
+	#[cfg_attr(feature = "std", serde(flatten))]
+	pub response: SimulateResponse,
+	/// The gas consumed, in the order it was consumed, along with what it was consumed for.
+	pub gas_records: Vec<GasRecord>,
+}
+
 #[derive(Clone, Decode, Encode, Debug, Eq, PartialEq, TypeInfo)]
 pub enum SimulateError {
 	InvalidTx,
@@ -46,10 +67,137 @@ pub enum SimulateError {
 }
 
 pub type SimulateResult = Result<SimulateResponse, SimulateError>;
+pub type SimulateWithGasRecordsResult = Result<SimulateWithGasRecordsResponse, SimulateError>;
+
+/// The block size and gas limits, where `-1` means no limit.
+#[derive(Clone, Decode, Encode, Debug, Eq, PartialEq, TypeInfo, Serialize, Deserialize)]
+pub struct BlockParams {
+	pub max_bytes: i64,
+	pub max_gas: i64,
+}
+
+/// The subset of CometBFT's `ConsensusParams` that applies to Cosmos transactions.
+#[derive(Clone, Decode, Encode, Debug, Eq, PartialEq, TypeInfo, Serialize, Deserialize)]
+pub struct ConsensusParams {
+	pub block: BlockParams,
+}
+
+/// A public key packed the way a protobuf `Any` packs it.
+#[derive(Clone, Decode, Encode, Debug, Eq, PartialEq, TypeInfo, Serialize, Deserialize)]
+pub struct PubKey {
+	pub type_url: String,
+	pub value: Vec<u8>,
+}
+
+#[derive(Clone, Decode, Encode, Debug, Eq, PartialEq, TypeInfo, Serialize, Deserialize)]
+pub struct BaseAccount {
+	pub address: String,
+	/// Known once the account has signed a transaction.
+	pub pub_key: Option<PubKey>,
+	pub account_number: u64,
+	pub sequence: u64,
+}
+
+#[derive(Clone, Decode, Encode, Debug, Eq, PartialEq, TypeInfo)]
+pub enum AccountError {
+	InvalidAddress,
+	NotFound,
+}
+
+#[derive(Clone, Decode, Encode, Debug, Eq, PartialEq, TypeInfo, Serialize, Deserialize)]
+pub struct BalancesResponse {
+	pub balances: Vec<Coin>,
+	pub pagination: PageResponse,
+}
+
+#[derive(Clone, Decode, Encode, Debug, Eq, PartialEq, TypeInfo, Serialize, Deserialize)]
+pub struct SupplyResponse {
+	pub supply: Vec<Coin>,
+	pub pagination: PageResponse,
+}
+
+/// A page of the txs matching a search, out of `total_count`.
+#[derive(Clone, Decode, Encode, Debug, Eq, PartialEq, TypeInfo, Serialize, Deserialize)]
+pub struct TxSearchResponse {
+	pub txs: Vec<TxResult>,
+	pub total_count: u32,
+}
+
+/// The events of a tx in an imported block.
+#[derive(Clone, Decode, Encode, Debug, Eq, PartialEq, TypeInfo, Serialize, Deserialize)]
+pub struct TxEvents {
+	/// SHA-256 hash of the tx bytes.
+	pub hash: [u8; 32],
+	pub height: u64,
+	pub events: Vec<CosmosEvent>,
+}
+
+/// The result of checking a tx for the pool, in the manner of CometBFT's `ResultBroadcastTx`.
+#[derive(Clone, Decode, Encode, Debug, Eq, PartialEq, TypeInfo, Serialize, Deserialize)]
+pub struct BroadcastTxResponse {
+	/// SHA-256 hash of the tx bytes.
+	pub hash: [u8; 32],
+	/// Both `codespace` and `code` are zero if the tx entered the pool.
+	pub codespace: u8,
+	pub code: u8,
+	pub log: String,
+}
+
+/// The results of checking a tx and of executing it in a block.
+#[derive(Clone, Decode, Encode, Debug, Eq, PartialEq, TypeInfo, Serialize, Deserialize)]
+pub struct BroadcastTxCommitResponse {
+	pub check_tx: BroadcastTxResponse,
+	/// Missing if the tx failed the check.
+	pub tx_result: Option<TxResult>,
+}
 
 decl_runtime_apis! {
+	#[api_version(2)]
 	pub trait CosmosRuntimeApi {
 		fn convert_tx(tx_bytes: Vec<u8>) -> <Block as BlockT>::Extrinsic;
 		fn simulate(tx_bytes: Vec<u8>) -> SimulateResult;
+		/// Simulates the tx like [`Self::simulate`], also recording what its gas is consumed for.
+		#[api_version(2)]
+		fn simulate_with_gas_records(tx_bytes: Vec<u8>) -> SimulateWithGasRecordsResult;
+		/// The limits blocks place on Cosmos transactions.
+		#[api_version(2)]
+		fn consensus_params() -> ConsensusParams;
+		/// The chain id that Cosmos txs are signed for.
+		#[api_version(2)]
+		fn chain_id() -> String;
+		/// The account number of `address`, if it has been assigned one.
+		#[api_version(2)]
+		fn account_number(address: H160) -> Option<u64>;
+		/// The account behind the bech32 `address`.
+		#[api_version(2)]
+		fn account(address: String) -> Result<BaseAccount, AccountError>;
+		/// The balance of the bech32 `address` in `denom`.
+		#[api_version(2)]
+		fn balance(address: String, denom: String) -> Result<Coin, QueryError>;
+		/// The nonzero balances of the bech32 `address`.
+		#[api_version(2)]
+		fn all_balances(
+			address: String,
+			pagination: Option<PageRequest>,
+		) -> Result<BalancesResponse, QueryError>;
+		/// The nonzero balances the bech32 `address` can spend.
+		#[api_version(2)]
+		fn spendable_balances(
+			address: String,
+			pagination: Option<PageRequest>,
+		) -> Result<BalancesResponse, QueryError>;
+		/// The supply of every denomination.
+		#[api_version(2)]
+		fn total_supply(pagination: Option<PageRequest>) -> Result<SupplyResponse, QueryError>;
+		/// The supply of `denom`.
+		#[api_version(2)]
+		fn supply_of(denom: String) -> Coin;
+		/// The metadata of `denom`.
+		#[api_version(2)]
+		fn denom_metadata(denom: String) -> Result<Metadata, QueryError>;
+		/// The protobuf-encoded response of the gRPC query method at `path` to the protobuf-encoded
+		/// request `data`.
+		#[api_version(2)]
+		fn abci_query(path: String, data: Vec<u8>) -> Result<Vec<u8>, QueryError>;
 	}
 }
@@ -29,10 +29,20 @@ pub mod weights;
 
 pub use self::pallet::*;
 use crate::weights::WeightInfo;
-use alloc::{string::String, vec::Vec};
+use alloc::{
+	format,
+	string::{String, ToString},
+	vec,
+	vec::Vec,
+};
+use bech32::{Bech32, Hrp};
 use core::marker::PhantomData;
 use cosmos_sdk_proto::{
-	cosmos::{bank::v1beta1::MsgSend, tx::v1beta1::Tx},
+	cosmos::{
+		bank::v1beta1::{MsgMultiSend, MsgSend},
+		base::v1beta1::Coin,
+		tx::v1beta1::Tx,
+	},
 	cosmwasm::wasm::v1::{
 		MsgExecuteContract, MsgInstantiateContract2, MsgMigrateContract, MsgStoreCode,
 		MsgUpdateAdmin,
@@ -43,37 +53,52 @@ use cosmos_sdk_proto::{
 use frame_support::{
 	dispatch::{DispatchInfo, PostDispatchInfo, WithPostDispatchInfo},
 	pallet_prelude::{DispatchResultWithPostInfo, InvalidTransaction, Pays},
+	storage,
 	traits::{
+		fungibles::{metadata::Inspect as _, Balanced},
 		tokens::{fungibles, AssetId, Balance},
-		Currency, Get,
+		Currency, Get, Imbalance, PalletInfoAccess,
 	},
 	weights::Weight,
 };
-use frame_system::{pallet_prelude::OriginFor, CheckWeight};
+use frame_system::{
+	pallet_prelude::{BlockNumberFor, OriginFor},
+	CheckWeight,
+};
 use pallet_cosmos_types::{
 	address::acc_address_from_bech32,
+	coin::{amount_to_string, DecCoin},
 	context,
 	context::traits::Context,
 	errors::{CosmosError, RootError},
-	events::{traits::EventManager, CosmosEvent},
+	events::{
+		traits::EventManager, CosmosEvent, EventAttribute, ATTRIBUTE_KEY_REFUND, EVENT_TYPE_TX,
+	},
 	gas::{traits::GasMeter, Gas},
 	handler::AnteDecorator,
 	msgservice::MsgServiceRouter,
+	tx_result::{block_txs_key, tx_key, TxResult},
 };
 use pallet_cosmos_x_auth_signing::{
 	sign_mode_handler::traits::SignModeHandler, sign_verifiable_tx::traits::SigVerifiableTx,
 };
+use pallet_cosmos_x_bank_types::metadata::Metadata;
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 use sp_core::H160;
 use sp_runtime::{
-	traits::{Convert, DispatchInfoOf, Dispatchable},
+	helpers_128bit::multiply_by_rational_with_rounding,
+	traits::{Convert, DispatchInfoOf, Dispatchable, Zero},
 	transaction_validity::{
-		TransactionValidity, TransactionValidityError, ValidTransactionBuilder,
+		TransactionLongevity, TransactionPriority, TransactionValidity, TransactionValidityError,
+		ValidTransactionBuilder,
 	},
-	RuntimeDebug, SaturatedConversion,
+	DispatchError, ModuleError, Rounding, RuntimeDebug, SaturatedConversion,
 };
 
+/// The name of the module account fees are collected by, as in the Cosmos SDK.
+pub const FEE_COLLECTOR_NAME: &str = "fee_collector";
+
 #[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo)]
 pub enum RawOrigin {
 	CosmosTransaction(H160),
@@ -153,12 +178,109 @@ where
 			None
 		}
 	}
+
+	/// Applies the transaction without the storage layer of a dispatched call, so that the
+	/// result of a failed transaction is indexed as well.
+	pub fn apply_self_contained(self) -> Option<DispatchResultWithPostInfo> {
+		if let Call::transact { tx_bytes } = self {
+			Some(Pallet::<T>::apply_validated_transaction(tx_bytes))
+		} else {
+			None
+		}
+	}
 }
 
 pub trait AddressMapping<A> {
 	fn into_account_id(address: H160) -> A;
 }
 
+pub type NegativeImbalanceOf<T> = <<T as Config>::NativeAsset as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
+pub type CreditOf<T> =
+	fungibles::Credit<<T as frame_system::Config>::AccountId, <T as Config>::Assets>;
+
+/// Handles the fees withdrawn from the fee payer of a Cosmos transaction. Fees reach the
+/// handler through the fee collector, and the handler returns the events of the fees leaving
+/// it, as made by [`Pallet::fee_payout_events`].
+pub trait OnChargeCosmosFee<T: Config> {
+	/// Handle a fee paid in the native denomination.
+	fn on_native_fee(fee: NegativeImbalanceOf<T>) -> Vec<CosmosEvent>;
+	/// Handle a fee paid in an asset denomination.
+	fn on_asset_fee(fee: CreditOf<T>) -> Vec<CosmosEvent>;
+}
+
+/// Burns the fees.
+impl<T: Config> OnChargeCosmosFee<T> for () {
+	fn on_native_fee(fee: NegativeImbalanceOf<T>) -> Vec<CosmosEvent> {
+		let amount = fee.peek().saturated_into::<u128>();
+		Pallet::<T>::fee_payout_events(None, T::NativeDenom::get(), amount)
+	}
+
+	fn on_asset_fee(fee: CreditOf<T>) -> Vec<CosmosEvent> {
+		let denom = T::AssetToDenom::convert(fee.asset());
+		Pallet::<T>::fee_payout_events(None, &denom, fee.peek().into())
+	}
+}
+
+/// Fee allowances granted by one account to another.
+pub trait FeeGrant {
+	/// Charge `fee` for `msgs` against the allowance `granter` has given `grantee`.
+	fn use_granted_fees(
+		granter: &str,
+		grantee: &str,
+		fee: &[Coin],
+		msgs: &[Any],
+	) -> Result<(), CosmosError>;
+}
+
+/// Fee grants are not enabled.
+impl FeeGrant for () {
+	fn use_granted_fees(
+		_granter: &str,
+		_grantee: &str,
+		_fee: &[Coin],
+		_msgs: &[Any],
+	) -> Result<(), CosmosError> {
+		Err(RootError::InvalidRequest.into())
+	}
+}
+
+/// Account numbers, assigned once per address in the order addresses are first seen.
+pub trait AccountNumbers {
+	/// The number of `address`, or `None` if it has not been assigned one yet.
+	fn account_number(address: &H160) -> Option<u64>;
+	/// Assigns the next account number to `address` unless it already has one.
+	fn ensure_account_number(address: &H160);
+}
+
+/// Every account has number zero.
+impl AccountNumbers for () {
+	fn account_number(_address: &H160) -> Option<u64> {
+		Some(0)
+	}
+
+	fn ensure_account_number(_address: &H160) {}
+}
+
+/// Public keys of the accounts that have signed a transaction.
+pub trait AccountPubKeys {
+	/// The public key stored for `address`, if any.
+	fn pub_key(address: &H160) -> Option<Any>;
+	/// Stores the public key of `address`.
+	fn set_pub_key(address: &H160, pub_key: &Any);
+}
+
+/// Public keys are not stored.
+impl AccountPubKeys for () {
+	fn pub_key(_address: &H160) -> Option<Any> {
+		None
+	}
+
+	fn set_pub_key(_address: &H160, _pub_key: &Any) {}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -191,6 +313,7 @@ pub mod pallet {
 				any_match!(
 					msg, {
 						MsgSend => true,
+						MsgMultiSend => true,
 						MsgStoreCode => true,
 						MsgInstantiateContract2 => true,
 						MsgExecuteContract => true,
@@ -223,6 +346,11 @@ pub mod pallet {
 			pub const MaxDenomLimit: u32 = 128;
 			pub const AddressPrefix: &'static str = "cosmos";
 			pub const NativeAssetId: u32 = u32::MAX;
+			pub const TxLongevity: TransactionLongevity = TransactionLongevity::MAX;
+			pub const RefundUnusedGas: bool = false;
+			pub const TxSizeCostPerByte: Gas = 10;
+			pub const SigVerifyCostSecp256k1: Gas = 1000;
+			pub const MaxBlockGas: Option<Gas> = None;
 		}
 
 		#[frame_support::register_default_impl(TestDefaultConfig)]
@@ -242,6 +370,12 @@ pub mod pallet {
 			type AddressPrefix = AddressPrefix;
 			type Context = pallet_cosmos_types::context::Context;
 			type NativeAssetId = NativeAssetId;
+			type MinGasPrices = ();
+			type TxLongevity = TxLongevity;
+			type RefundUnusedGas = RefundUnusedGas;
+			type TxSizeCostPerByte = TxSizeCostPerByte;
+			type SigVerifyCostSecp256k1 = SigVerifyCostSecp256k1;
+			type MaxBlockGas = MaxBlockGas;
 		}
 	}
 
@@ -255,6 +389,35 @@ pub mod pallet {
 	pub type AssetDenomRouter<T: Config> =
 		StorageMap<_, Twox64Concat, T::AssetId, BoundedVec<u8, T::MaxDenomLimit>, OptionQuery>;
 
+	/// The client-facing metadata set for denominations, keyed by the base denomination.
+	/// Denominations without an entry have metadata derived from their asset's.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	#[pallet::getter(fn denom_metadata)]
+	pub type DenomMetadata<T: Config> =
+		StorageMap<_, Twox64Concat, BoundedVec<u8, T::MaxDenomLimit>, Metadata, OptionQuery>;
+
+	#[pallet::type_value]
+	pub fn DefaultSendEnabledOnEmpty() -> bool {
+		true
+	}
+
+	/// Whether denominations without a [`SendEnabled`] entry can be sent.
+	#[pallet::storage]
+	#[pallet::getter(fn default_send_enabled)]
+	pub type DefaultSendEnabled<T: Config> =
+		StorageValue<_, bool, ValueQuery, DefaultSendEnabledOnEmpty>;
+
+	/// Whether each listed denomination can be sent, overriding [`DefaultSendEnabled`].
+	#[pallet::storage]
+	pub type SendEnabled<T: Config> =
+		StorageMap<_, Twox64Concat, BoundedVec<u8, T::MaxDenomLimit>, bool, OptionQuery>;
+
+	/// Accounts, such as those of modules, that may not receive funds from bank transfers.
+	#[pallet::storage]
+	pub type BlockedAddrs<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
 	#[pallet::config(with_default)]
 	pub trait Config: frame_system::Config {
 		/// Mapping an address to an account id.
@@ -278,9 +441,9 @@ pub mod pallet {
 			+ fungibles::Balanced<Self::AccountId, Balance = Self::Balance, AssetId = Self::AssetId>;
 		/// The overarching event type.
 		#[pallet::no_default_bounds]
-		type RuntimeEvent: From<Event> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		/// Verify the validity of a Cosmos transaction.
-		type AnteHandler: AnteDecorator;
+		type AnteHandler: AnteDecorator<Self::Context>;
 		/// The maximum number of characters allowed in a memo.
 		#[pallet::constant]
 		type MaxMemoCharacters: Get<u64>;
@@ -289,7 +452,7 @@ pub mod pallet {
 		type NativeDenom: Get<&'static str>;
 		/// Router for handling message services.
 		#[pallet::no_default]
-		type MsgServiceRouter: MsgServiceRouter<Self::Context>;
+		type MsgServiceRouter: MsgServiceRouter;
 		/// The chain ID.
 		#[pallet::constant]
 		type ChainId: Get<&'static str>;
@@ -323,12 +486,107 @@ pub mod pallet {
 		type Context: Context;
 
 		type NativeAssetId: Get<Self::AssetId>;
+		/// The minimum gas prices accepted into the transaction pool.
+		type MinGasPrices: Get<Vec<DecCoin>>;
+		/// The number of blocks a Cosmos transaction stays valid in the pool.
+		#[pallet::constant]
+		type TxLongevity: Get<TransactionLongevity>;
+		/// Whether the fee for unused gas is refunded to the fee payer after execution, with only
+		/// the rest of the fee going to [`Config::OnChargeFee`].
+		#[pallet::constant]
+		type RefundUnusedGas: Get<bool>;
+		/// Handler for the fees withdrawn from the fee payer.
+		#[pallet::no_default]
+		type OnChargeFee: OnChargeCosmosFee<Self>;
+		/// Fee allowances that let a granter pay the fees of a grantee.
+		#[pallet::no_default]
+		type FeeGrant: FeeGrant;
+		/// Registry of the account numbers signers sign over.
+		#[pallet::no_default]
+		type AccountNumbers: AccountNumbers;
+		/// Storage for the public keys of the accounts that have signed a transaction.
+		#[pallet::no_default]
+		type AccountPubKeys: AccountPubKeys;
+		/// The gas charged for each byte of a transaction.
+		#[pallet::constant]
+		type TxSizeCostPerByte: Get<Gas>;
+		/// The gas charged for verifying a secp256k1 signature.
+		#[pallet::constant]
+		type SigVerifyCostSecp256k1: Get<Gas>;
+		/// The maximum gas the Cosmos transactions of a block may use, or `None` for no limit.
+		/// Like CometBFT, a block is charged the gas limit of each of its transactions.
+		#[pallet::constant]
+		type MaxBlockGas: Get<Option<Gas>>;
+		/// The origin allowed to manage the bank module, such as its denomination metadata.
+		#[pallet::no_default]
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+	}
+
+	/// The gas the ante handlers consumed in `pre_dispatch` for the transaction being applied.
+	#[pallet::storage]
+	pub type AnteGasUsed<T: Config> = StorageValue<_, Gas, ValueQuery>;
+
+	/// The events the ante handlers emitted in `pre_dispatch` for the transaction being applied.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub type AnteEvents<T: Config> = StorageValue<_, Vec<CosmosEvent>, ValueQuery>;
+
+	/// The fees, by denomination, the ante handlers withdrew in `pre_dispatch` for the
+	/// transaction being applied and withheld until the fee for unused gas is refunded.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub type WithheldFees<T: Config> = StorageValue<_, Vec<(String, u128)>, ValueQuery>;
+
+	/// The results of the Cosmos transactions applied in the current block, in order.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub type BlockTxResults<T: Config> = StorageValue<_, Vec<TxResult>, ValueQuery>;
+
+	/// The gas charged to the current block by its Cosmos transactions.
+	#[pallet::storage]
+	#[pallet::getter(fn block_gas_used)]
+	pub type BlockGasUsed<T: Config> = StorageValue<_, Gas, ValueQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			BlockGasUsed::<T>::kill();
+
+			// `on_finalize` takes `BlockTxResults`.
+			T::DbWeight::get().reads_writes(1, 2)
+		}
+
+		fn on_finalize(n: BlockNumberFor<T>) {
+			let tx_results = BlockTxResults::<T>::take();
+			if tx_results.is_empty() {
+				return;
+			}
+
+			// The same root `frame_system` puts in the header, which the RPC reads it back from.
+			let extrinsics = (0..frame_system::Pallet::<T>::extrinsic_count())
+				.map(frame_system::Pallet::<T>::extrinsic_data)
+				.collect();
+			let extrinsics_root = frame_system::extrinsics_data_root::<T::Hashing>(extrinsics);
+			let height: u64 = n.saturated_into();
+
+			for tx_result in tx_results.iter() {
+				sp_io::offchain_index::set(
+					&tx_key(&tx_result.hash),
+					&(height, extrinsics_root.as_ref()).encode(),
+				);
+			}
+			sp_io::offchain_index::set(
+				&block_txs_key(height, extrinsics_root.as_ref()),
+				&tx_results.encode(),
+			);
+		}
 	}
 
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
 		pub assets: Vec<(Vec<u8>, T::AssetId)>,
+		pub blocked_addrs: Vec<T::AccountId>,
 	}
 
 	#[pallet::genesis_build]
@@ -349,19 +607,31 @@ pub mod pallet {
 				DenomAssetRouter::<T>::insert(denom.clone(), asset_id.clone());
 				AssetDenomRouter::<T>::insert(asset_id, denom);
 			}
+
+			for who in &self.blocked_addrs {
+				BlockedAddrs::<T>::insert(who, ());
+			}
 		}
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub fn deposit_event)]
-	pub enum Event {
+	pub enum Event<T: Config> {
 		AnteHandled(Vec<CosmosEvent>),
 		Executed { gas_wanted: u64, gas_used: u64, events: Vec<CosmosEvent> },
+		DenomMetadataSet { denom: Vec<u8> },
+		DefaultSendEnabledSet { enabled: bool },
+		SendEnabledSet { denom: Vec<u8>, enabled: Option<bool> },
+		BlockedAddrSet { who: T::AccountId, blocked: bool },
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
 		CosmosError(CosmosError),
+		/// The denomination is not mapped to any asset.
+		UnknownDenom,
+		/// The denomination metadata is malformed.
+		InvalidDenomMetadata,
 	}
 
 	#[pallet::call]
@@ -385,27 +655,136 @@ pub mod pallet {
 		pub fn transact(origin: OriginFor<T>, tx_bytes: Vec<u8>) -> DispatchResultWithPostInfo {
 			let _source = ensure_cosmos_transaction(origin)?;
 
-			let tx = Tx::decode(&mut &*tx_bytes).map_err(|_| {
-				Error::<T>::CosmosError(RootError::TxDecodeError.into())
-					.with_weight(T::WeightInfo::default_weight())
-			})?;
+			Self::apply_validated_transaction(tx_bytes)
+		}
 
-			Self::apply_validated_transaction(tx)
+		/// Replaces the metadata of the denomination `metadata.base`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::set_denom_metadata())]
+		pub fn set_denom_metadata(origin: OriginFor<T>, metadata: Metadata) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			ensure!(metadata.is_valid(), Error::<T>::InvalidDenomMetadata);
+			let denom =
+				BoundedVec::<u8, T::MaxDenomLimit>::try_from(metadata.base.as_bytes().to_vec())
+					.map_err(|_| Error::<T>::InvalidDenomMetadata)?;
+			ensure!(DenomAssetRouter::<T>::contains_key(&denom), Error::<T>::UnknownDenom);
+
+			DenomMetadata::<T>::insert(denom.clone(), metadata);
+			Self::deposit_event(Event::DenomMetadataSet { denom: denom.into_inner() });
+
+			Ok(())
+		}
+
+		/// Sets whether denominations without their own setting can be sent.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::set_default_send_enabled())]
+		pub fn set_default_send_enabled(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			DefaultSendEnabled::<T>::put(enabled);
+			Self::deposit_event(Event::DefaultSendEnabledSet { enabled });
+
+			Ok(())
+		}
+
+		/// Sets whether `denom` can be sent, or makes it follow the default if `enabled` is
+		/// `None`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::set_send_enabled())]
+		pub fn set_send_enabled(
+			origin: OriginFor<T>,
+			denom: Vec<u8>,
+			enabled: Option<bool>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			let denom = BoundedVec::<u8, T::MaxDenomLimit>::try_from(denom)
+				.map_err(|_| Error::<T>::UnknownDenom)?;
+			ensure!(DenomAssetRouter::<T>::contains_key(&denom), Error::<T>::UnknownDenom);
+
+			SendEnabled::<T>::set(&denom, enabled);
+			Self::deposit_event(Event::SendEnabledSet { denom: denom.into_inner(), enabled });
+
+			Ok(())
+		}
+
+		/// Sets whether `who` is blocked from receiving funds from bank transfers.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::set_blocked_addr())]
+		pub fn set_blocked_addr(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			blocked: bool,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			if blocked {
+				BlockedAddrs::<T>::insert(&who, ());
+			} else {
+				BlockedAddrs::<T>::remove(&who);
+			}
+			Self::deposit_event(Event::BlockedAddrSet { who, blocked });
+
+			Ok(())
 		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// Whether `denom` can be sent with bank transfers.
+	pub fn is_send_enabled(denom: &str) -> bool {
+		BoundedVec::<u8, T::MaxDenomLimit>::try_from(denom.as_bytes().to_vec())
+			.ok()
+			.and_then(SendEnabled::<T>::get)
+			.unwrap_or_else(DefaultSendEnabled::<T>::get)
+	}
+
+	/// Whether `who` is blocked from receiving funds from bank transfers.
+	pub fn is_blocked_addr(who: &T::AccountId) -> bool {
+		BlockedAddrs::<T>::contains_key(who)
+	}
+
+	/// Metadata of the denomination `denom` of `asset_id`, derived from the asset's name and
+	/// decimals.
+	pub fn asset_denom_metadata(denom: String, asset_id: T::AssetId) -> Metadata {
+		let name = T::Assets::name(asset_id.clone());
+		let symbol = T::Assets::symbol(asset_id.clone());
+		let name = if name.is_empty() { symbol.clone() } else { name };
+
+		Metadata::from_asset(
+			denom,
+			String::from_utf8_lossy(&name).into_owned(),
+			String::from_utf8_lossy(&symbol).into_owned(),
+			T::Assets::decimals(asset_id),
+		)
+	}
+
 	fn validate_transaction_in_pool(origin: H160, tx_bytes: &[u8]) -> TransactionValidity {
 		let tx = Tx::decode(&mut &*tx_bytes).map_err(|_| InvalidTransaction::Call)?;
 
-		T::AnteHandler::ante_handle(&tx, true)?;
+		let fee = tx
+			.auth_info
+			.as_ref()
+			.and_then(|auth_info| auth_info.fee.as_ref())
+			.ok_or(InvalidTransaction::Call)?;
+
+		// A transaction that can't fit in any block will never leave the pool.
+		if T::MaxBlockGas::get().is_some_and(|max_block_gas| fee.gas_limit > max_block_gas) {
+			return Err(InvalidTransaction::ExhaustsResources.into());
+		}
+
+		let mut ctx = T::Context::new(fee.gas_limit);
+		ctx.set_check_tx(true);
+		T::AnteHandler::ante_handle(&mut ctx, &tx, false)?;
 
 		let transaction_nonce =
 			T::SigVerifiableTx::sequence(&tx).map_err(|_| InvalidTransaction::Call)?;
 
-		let mut builder =
-			ValidTransactionBuilder::default().and_provides((origin, transaction_nonce));
+		let mut builder = ValidTransactionBuilder::default()
+			.and_provides((origin, transaction_nonce))
+			.priority(Self::priority(&tx))
+			.longevity(T::TxLongevity::get());
 
 		let who = T::AddressMapping::into_account_id(origin);
 		let sequence = frame_system::Pallet::<T>::account_nonce(&who).saturated_into();
@@ -419,15 +798,59 @@ impl<T: Config> Pallet<T> {
 		builder.build()
 	}
 
+	/// Effective gas price of the transaction in the native denomination.
+	fn priority(tx: &Tx) -> TransactionPriority {
+		let Some(fee) = tx.auth_info.as_ref().and_then(|auth_info| auth_info.fee.as_ref()) else {
+			return 0;
+		};
+
+		fee.amount
+			.iter()
+			.find(|coin| coin.denom == T::NativeDenom::get())
+			.and_then(|coin| coin.amount.parse::<u128>().ok())
+			.and_then(|amount| amount.checked_div(fee.gas_limit.into()))
+			.unwrap_or_default()
+			.saturated_into()
+	}
+
 	pub fn validate_transaction_in_block(tx_bytes: &[u8]) -> Result<(), TransactionValidityError> {
 		let tx = Tx::decode(&mut &*tx_bytes).map_err(|_| InvalidTransaction::Call)?;
+		let gas_limit = tx
+			.auth_info
+			.as_ref()
+			.and_then(|auth_info| auth_info.fee.as_ref())
+			.ok_or(InvalidTransaction::Call)?
+			.gas_limit;
+
+		let block_gas_used = BlockGasUsed::<T>::get()
+			.checked_add(gas_limit)
+			.ok_or(InvalidTransaction::ExhaustsResources)?;
+		if T::MaxBlockGas::get().is_some_and(|max_block_gas| block_gas_used > max_block_gas) {
+			return Err(InvalidTransaction::ExhaustsResources.into());
+		}
+
+		let mut ctx = T::Context::new(gas_limit);
+		AnteEvents::<T>::kill();
+		WithheldFees::<T>::kill();
+		T::AnteHandler::ante_handle(&mut ctx, &tx, false)?;
 
-		T::AnteHandler::ante_handle(&tx, false)?;
+		AnteGasUsed::<T>::put(ctx.gas_meter().consumed_gas());
+		BlockGasUsed::<T>::put(block_gas_used);
 
 		Ok(())
 	}
 
-	pub fn apply_validated_transaction(tx: Tx) -> DispatchResultWithPostInfo {
+	/// Runs the messages of a transaction whose ante handlers ran in `pre_dispatch`, and indexes
+	/// its result by hash whether it succeeded or not.
+	pub fn apply_validated_transaction(tx_bytes: Vec<u8>) -> DispatchResultWithPostInfo {
+		let hash = sp_io::hashing::sha2_256(&tx_bytes);
+		let ante_gas_used = AnteGasUsed::<T>::take();
+		let mut events = AnteEvents::<T>::take();
+
+		let tx = Tx::decode(&mut &*tx_bytes).map_err(|_| {
+			Error::<T>::CosmosError(RootError::TxDecodeError.into())
+				.with_weight(T::WeightInfo::default_weight())
+		})?;
 		let gas_limit = tx
 			.auth_info
 			.as_ref()
@@ -438,15 +861,161 @@ impl<T: Config> Pallet<T> {
 			)?
 			.gas_limit;
 
+		// The ante handlers already ran in `pre_dispatch` with a meter of their own.
 		let mut ctx = T::Context::new(gas_limit);
+		let result = storage::with_storage_layer(|| {
+			ctx.gas_meter().consume_gas(ante_gas_used, "ante").map_err(|_| {
+				Error::<T>::CosmosError(RootError::OutOfGas.into())
+					.with_weight(T::WeightInfo::default_weight())
+			})?;
+
+			Self::execute_transaction(tx, &mut ctx)
+		});
+		// Fees still withheld here belong to a failed transaction, which gets no refund.
+		events.extend(Self::charge_withheld_fees());
+
+		let (codespace, code, log) = match &result {
+			Ok(_) => {
+				events.extend(ctx.event_manager().events());
+				(0, 0, String::new())
+			},
+			Err(e) => {
+				let CosmosError { codespace, code } = Self::cosmos_error(&e.error);
+				(codespace, code, format!("codespace: {}, code: {}", codespace, code))
+			},
+		};
+		let tx_result = TxResult {
+			hash,
+			height: frame_system::Pallet::<T>::block_number().saturated_into(),
+			index: frame_system::Pallet::<T>::extrinsic_index().unwrap_or_default(),
+			codespace,
+			code,
+			gas_wanted: gas_limit,
+			gas_used: ctx.gas_meter().consumed_gas(),
+			events,
+			log,
+		};
+		BlockTxResults::<T>::append(tx_result);
+
+		result
+	}
+
+	/// The Cosmos error behind a failed transaction.
+	fn cosmos_error(error: &DispatchError) -> CosmosError {
+		match error {
+			DispatchError::Module(ModuleError { index, error, .. })
+				if *index as usize == <Pallet<T> as PalletInfoAccess>::index() =>
+				match Error::<T>::decode(&mut &error[..]) {
+					Ok(Error::<T>::CosmosError(e)) => e,
+					_ => RootError::Internal.into(),
+				},
+			_ => RootError::Internal.into(),
+		}
+	}
+
+	/// Hands a fee in the native denomination withdrawn by the ante handlers to
+	/// [`Config::OnChargeFee`], or withholds it if the fee for unused gas is refunded. Returns
+	/// the events of the fee leaving the fee collector, if it did.
+	pub fn charge_native_fee(fee: NegativeImbalanceOf<T>) -> Vec<CosmosEvent> {
+		if T::RefundUnusedGas::get() {
+			// An imbalance can't outlive `pre_dispatch`, so the fee is dropped and issued again
+			// once the refund is known.
+			WithheldFees::<T>::append((
+				T::NativeDenom::get().to_string(),
+				fee.peek().saturated_into::<u128>(),
+			));
+			Vec::new()
+		} else {
+			T::OnChargeFee::on_native_fee(fee)
+		}
+	}
+
+	/// Hands a fee in the asset denomination `denom` withdrawn by the ante handlers to
+	/// [`Config::OnChargeFee`], or withholds it if the fee for unused gas is refunded. Returns
+	/// the events of the fee leaving the fee collector, if it did.
+	pub fn charge_asset_fee(denom: &str, fee: CreditOf<T>) -> Vec<CosmosEvent> {
+		if T::RefundUnusedGas::get() {
+			WithheldFees::<T>::append((denom.to_string(), fee.peek().into()));
+			Vec::new()
+		} else {
+			T::OnChargeFee::on_asset_fee(fee)
+		}
+	}
+
+	/// Hands the fees withheld for the transaction being applied to [`Config::OnChargeFee`].
+	fn charge_withheld_fees() -> Vec<CosmosEvent> {
+		let mut events = Vec::new();
+		for (denom, amount) in WithheldFees::<T>::take() {
+			if denom == T::NativeDenom::get() {
+				events.extend(T::OnChargeFee::on_native_fee(T::NativeAsset::issue(
+					amount.saturated_into(),
+				)));
+			} else if let Ok(asset_id) = T::AssetToDenom::convert(denom) {
+				events.extend(T::OnChargeFee::on_asset_fee(T::Assets::issue(
+					asset_id,
+					amount.saturated_into(),
+				)));
+			}
+		}
+		events
+	}
+
+	/// The address of the module account of the fee collector, which fees pass through from
+	/// the fee payer to [`Config::OnChargeFee`]. Derived as in the Cosmos SDK, though no
+	/// account holds the fees in between.
+	pub fn fee_collector() -> String {
+		let address = &sp_io::hashing::sha2_256(FEE_COLLECTOR_NAME.as_bytes())[..20];
+		Hrp::parse(T::AddressPrefix::get())
+			.ok()
+			.and_then(|hrp| bech32::encode::<Bech32>(hrp, address).ok())
+			.unwrap_or_default()
+	}
+
+	/// The events of `amount` of `denom` leaving the fee collector for `receiver`, or being
+	/// burned without one.
+	pub fn fee_payout_events(
+		receiver: Option<&str>,
+		denom: &str,
+		amount: u128,
+	) -> Vec<CosmosEvent> {
+		if amount.is_zero() {
+			return Vec::new();
+		}
+
+		let fee_collector = Self::fee_collector();
+		let amount = [Coin { denom: denom.to_string(), amount: amount.to_string() }];
+		vec![
+			CosmosEvent::coin_spent(&fee_collector, &amount),
+			match receiver {
+				Some(receiver) => CosmosEvent::coin_received(receiver, &amount),
+				None => CosmosEvent::coin_burn(&fee_collector, &amount),
+			},
+		]
+	}
+
+	/// Emits the events of an ante handler, to be included in the result of the transaction.
+	pub fn deposit_ante_events(events: Vec<CosmosEvent>) {
+		AnteEvents::<T>::mutate(|ante_events| ante_events.extend(events.clone()));
+		Self::deposit_event(Event::AnteHandled(events));
+	}
+
+	/// Runs the messages of `tx`, charging their gas to `ctx`, which may already hold the gas
+	/// of the ante handlers.
+	pub fn execute_transaction<C: Context>(tx: Tx, ctx: &mut C) -> DispatchResultWithPostInfo {
+		let fee = tx.auth_info.as_ref().and_then(|auth_info| auth_info.fee.as_ref()).ok_or(
+			Error::<T>::CosmosError(RootError::TxDecodeError.into())
+				.with_weight(T::WeightInfo::default_weight()),
+		)?;
+		let gas_limit = fee.gas_limit;
+
 		ctx.gas_meter()
-			.consume_gas(T::WeightInfo::default_weight().ref_time(), "")
+			.consume_gas(T::WeightInfo::default_weight().ref_time(), "default_weight")
 			.map_err(|_| {
 				Error::<T>::CosmosError(RootError::OutOfGas.into())
 					.with_weight(T::WeightInfo::default_weight())
 			})?;
 
-		let body = tx.body.ok_or(
+		let body = tx.body.as_ref().ok_or(
 			Error::<T>::CosmosError(RootError::TxDecodeError.into())
 				.with_weight(T::WeightInfo::default_weight()),
 		)?;
@@ -456,7 +1025,23 @@ impl<T: Config> Pallet<T> {
 					.with_weight(T::WeightToGas::convert(ctx.gas_meter().consumed_gas())),
 			)?;
 
-			handler.handle(msg, &mut ctx).map_err(|e| {
+			let sender = T::SigVerifiableTx::get_msg_signers(msg)
+				.ok()
+				.and_then(|signers| signers.into_iter().next())
+				.ok_or(
+					Error::<T>::CosmosError(RootError::TxDecodeError.into())
+						.with_weight(T::WeightToGas::convert(ctx.gas_meter().consumed_gas())),
+				)?;
+			ctx.event_manager().emit_event(CosmosEvent::message(&msg.type_url, &sender));
+
+			handler.handle(msg, ctx).map_err(|e| {
+				Error::<T>::CosmosError(e)
+					.with_weight(T::WeightToGas::convert(ctx.gas_meter().consumed_gas()))
+			})?;
+		}
+
+		if T::RefundUnusedGas::get() {
+			Self::refund_unused_gas(&tx, fee, ctx).map_err(|e| {
 				Error::<T>::CosmosError(e)
 					.with_weight(T::WeightToGas::convert(ctx.gas_meter().consumed_gas()))
 			})?;
@@ -473,4 +1058,70 @@ impl<T: Config> Pallet<T> {
 			pays_fee: Pays::Yes,
 		})
 	}
+
+	/// Credits the fee paid for unused gas back to whoever paid the fee, out of the fees withheld
+	/// for the transaction, and hands the rest to [`Config::OnChargeFee`].
+	fn refund_unused_gas<C: Context>(tx: &Tx, fee: &Fee, ctx: &mut C) -> Result<(), CosmosError> {
+		// The granter, if any, is the one who paid the fee.
+		let fee_payer = if !fee.granter.is_empty() {
+			fee.granter.clone()
+		} else {
+			T::SigVerifiableTx::fee_payer(tx).map_err(|_| RootError::InvalidAddress)?
+		};
+		let (_hrp, address_raw) =
+			acc_address_from_bech32(&fee_payer).map_err(|_| RootError::InvalidAddress)?;
+		if address_raw.len() != 20 {
+			return Err(RootError::InvalidAddress.into());
+		}
+		let who = T::AddressMapping::into_account_id(H160::from_slice(&address_raw));
+
+		let unused_gas = fee.gas_limit.saturating_sub(ctx.gas_meter().consumed_gas());
+		let mut refund = Vec::new();
+		let mut payouts = Vec::new();
+		for (denom, amount) in WithheldFees::<T>::take() {
+			let refund_amount = multiply_by_rational_with_rounding(
+				amount,
+				unused_gas.into(),
+				fee.gas_limit.into(),
+				Rounding::Down,
+			)
+			.unwrap_or_default();
+
+			if denom == T::NativeDenom::get() {
+				let (refunded, charged) = T::NativeAsset::issue(amount.saturated_into())
+					.split(refund_amount.saturated_into());
+				T::NativeAsset::resolve_creating(&who, refunded);
+				payouts.extend(T::OnChargeFee::on_native_fee(charged));
+			} else {
+				let asset_id =
+					T::AssetToDenom::convert(denom.clone()).map_err(|_| RootError::InvalidCoins)?;
+
+				let (refunded, charged) = T::Assets::issue(asset_id, amount.saturated_into())
+					.split(refund_amount.saturated_into());
+				T::Assets::resolve(&who, refunded).map_err(|_| RootError::InvalidCoins)?;
+				payouts.extend(T::OnChargeFee::on_asset_fee(charged));
+			}
+
+			if !refund_amount.is_zero() {
+				refund.push(Coin { denom, amount: refund_amount.to_string() });
+			}
+		}
+
+		if !refund.is_empty() {
+			ctx.event_manager().emit_events(vec![
+				CosmosEvent::coin_spent(&Self::fee_collector(), &refund),
+				CosmosEvent::coin_received(&fee_payer, &refund),
+				CosmosEvent {
+					r#type: EVENT_TYPE_TX.into(),
+					attributes: vec![EventAttribute {
+						key: ATTRIBUTE_KEY_REFUND.into(),
+						value: amount_to_string(&refund).into(),
+					}],
+				},
+			]);
+		}
+		ctx.event_manager().emit_events(payouts);
+
+		Ok(())
+	}
 }
@@ -22,22 +22,26 @@ use bech32::{Bech32, Hrp};
 use config_preludes::{NativeAssetId, NativeDenom};
 use core::marker::PhantomData;
 use cosmos_sdk_proto::{
-	cosmos::bank::v1beta1::MsgSend,
+	cosmos::bank::v1beta1::{MsgMultiSend, MsgSend},
 	cosmwasm::wasm::v1::{
 		MsgExecuteContract, MsgInstantiateContract2, MsgMigrateContract, MsgStoreCode,
 		MsgUpdateAdmin,
 	},
 	Any,
 };
-use frame_support::{derive_impl, parameter_types, traits::AsEnsureOriginWithArg, PalletId};
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{fungibles::Balanced, AsEnsureOriginWithArg, Currency, Everything, Nothing},
+	PalletId,
+};
 use hp_account::CosmosSigner;
 use hp_crypto::EcdsaExt;
 use pallet_cosmos::{config_preludes::ChainId, AddressMapping};
-use pallet_cosmos_types::{any_match, msgservice::MsgHandler};
+use pallet_cosmos_types::{any_match, coin::DecCoin, msgservice::MsgHandler};
 use pallet_cosmos_x_auth_signing::{
 	sign_mode_handler::SignModeHandler, sign_verifiable_tx::SigVerifiableTx,
 };
-use pallet_cosmos_x_bank::msgs::MsgSendHandler;
+use pallet_cosmos_x_bank::msgs::{MsgMultiSendHandler, MsgSendHandler};
 use pallet_cosmos_x_wasm::msgs::{
 	MsgExecuteContractHandler, MsgInstantiateContract2Handler, MsgMigrateContractHandler,
 	MsgStoreCodeHandler, MsgUpdateAdminHandler,
@@ -111,16 +115,19 @@ impl pallet_timestamp::Config for Test {
 }
 
 pub struct MsgServiceRouter<T>(PhantomData<T>);
-impl<T, Context> pallet_cosmos_types::msgservice::MsgServiceRouter<Context> for MsgServiceRouter<T>
+impl<T> pallet_cosmos_types::msgservice::MsgServiceRouter for MsgServiceRouter<T>
 where
 	T: frame_system::Config + pallet_cosmos::Config + pallet_cosmwasm::Config,
 	T::AccountId: EcdsaExt,
-	Context: context::traits::Context,
 {
-	fn route(msg: &Any) -> Option<Box<dyn MsgHandler<Context>>> {
+	fn route<Context>(msg: &Any) -> Option<Box<dyn MsgHandler<Context>>>
+	where
+		Context: context::traits::Context,
+	{
 		any_match!(
 			msg, {
 				MsgSend => Some(Box::<MsgSendHandler<T>>::default()),
+				MsgMultiSend => Some(Box::<MsgMultiSendHandler<T>>::default()),
 				MsgStoreCode => Some(Box::<MsgStoreCodeHandler<T>>::default()),
 				MsgInstantiateContract2 => Some(Box::<MsgInstantiateContract2Handler<T>>::default()),
 				MsgExecuteContract => Some(Box::<MsgExecuteContractHandler<T>>::default()),
@@ -171,6 +178,35 @@ impl pallet_cosmos::Config for Test {
 	type WeightInfo = pallet_cosmos::weights::CosmosWeight<Test>;
 	type AssetToDenom = AssetToDenom;
 	type SignModeHandler = SignModeHandler;
+	type OnChargeFee = DealWithFees;
+	type FeeGrant = ();
+	type AccountNumbers = ();
+	type AccountPubKeys = ();
+	type AdminOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type MinGasPrices = MinGasPrices;
+	type RefundUnusedGas = RefundUnusedGas;
+}
+
+/// Pays the fees to [`FeeCollector`].
+pub struct DealWithFees;
+impl OnChargeCosmosFee<Test> for DealWithFees {
+	fn on_native_fee(fee: NegativeImbalanceOf<Test>) -> Vec<CosmosEvent> {
+		let events = Cosmos::fee_payout_events(
+			Some(&AccountToAddr::<Test>::convert(FeeCollector::get())),
+			NativeDenom::get(),
+			fee.peek(),
+		);
+		Balances::resolve_creating(&FeeCollector::get(), fee);
+		events
+	}
+
+	fn on_asset_fee(fee: CreditOf<Test>) -> Vec<CosmosEvent> {
+		let (denom, amount) = (AssetToDenom::convert(fee.asset()), fee.peek());
+		let receiver = Assets::resolve(&FeeCollector::get(), fee)
+			.is_ok()
+			.then(|| AccountToAddr::<Test>::convert(FeeCollector::get()));
+		Cosmos::fee_payout_events(receiver.as_deref(), &denom, amount)
+	}
 }
 
 parameter_types! {
@@ -187,6 +223,9 @@ parameter_types! {
 	pub const ContractStorageByteReadPrice: u32 = 1;
 	pub const ContractStorageByteWritePrice: u32 = 1;
 	pub WasmCostRules: CostRules<Test> = Default::default();
+	pub static MinGasPrices: Vec<DecCoin> = Vec::new();
+	pub static RefundUnusedGas: bool = false;
+	pub FeeCollector: AccountId = CosmosSigner(ecdsa::Public::from_raw([1u8; 33]));
 }
 
 pub struct AssetToDenom;
@@ -253,6 +292,9 @@ impl pallet_cosmwasm::Config for Test {
 	type MaxMessageSize = ConstU32<{ 64 * 1024 }>;
 	type AccountToAddr = AccountToAddr<Test>;
 	type AssetToDenom = AssetToDenom;
+	type DenomToMetadata = ();
+	type SendEnabled = Everything;
+	type BlockedAddrs = Nothing;
 	type Balance = Balance;
 	type AssetId = AssetId;
 	type Assets = Assets;
@@ -345,13 +387,10 @@ impl fp_self_contained::SelfContainedCall for RuntimeCall {
 
 	fn apply_self_contained(
 		self,
-		info: Self::SignedInfo,
+		_info: Self::SignedInfo,
 	) -> Option<sp_runtime::DispatchResultWithInfo<PostDispatchInfoOf<Self>>> {
 		match self {
-			call @ RuntimeCall::Cosmos(pallet_cosmos::Call::transact { .. }) =>
-				Some(call.dispatch(RuntimeOrigin::from(
-					pallet_cosmos::RawOrigin::CosmosTransaction(info.to_cosmos_address().unwrap()),
-				))),
+			RuntimeCall::Cosmos(call) => call.apply_self_contained(),
 			_ => None,
 		}
 	}
@@ -377,9 +416,12 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	.assimilate_storage(&mut t)
 	.unwrap();
 
-	pallet_cosmos_accounts::GenesisConfig::<Test> { accounts: vec![alice, bob] }
-		.assimilate_storage(&mut t)
-		.unwrap();
+	pallet_cosmos_accounts::GenesisConfig::<Test> {
+		accounts: vec![alice, bob],
+		account_numbers: vec![],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
 
 	sp_io::TestExternalities::new(t)
 }
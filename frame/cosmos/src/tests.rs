@@ -16,17 +16,56 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::mock::*;
+use crate::{
+	config_preludes::{MaxDenomLimit, SigVerifyCostSecp256k1, TxSizeCostPerByte},
+	mock::*,
+	DenomAssetRouter, Error,
+};
 use base64ct::{Base64, Encoding};
-use cosmos_sdk_proto::{cosmos::tx::v1beta1::Tx, prost::Message};
+use bech32::{Bech32, Hrp};
+use cosmos_sdk_proto::{
+	cosmos::{
+		bank::v1beta1::{Input, MsgMultiSend, Output},
+		base::v1beta1::Coin,
+		tx::v1beta1::{AuthInfo, Fee, Tx},
+	},
+	prost::{Message, Name},
+	Any,
+};
 use fp_self_contained::{CheckedExtrinsic, SelfContainedCall};
-use frame_support::{assert_ok, dispatch::GetDispatchInfo, traits::fungible::Inspect};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::GetDispatchInfo,
+	traits::{fungible::Inspect, Get, Hooks},
+	BoundedVec,
+};
 use hp_account::CosmosSigner;
-use pallet_cosmos_types::events::{CosmosEvent, EventAttribute};
-use pallet_cosmos_x_bank_types::msgs::msg_send::MsgSend;
+use pallet_cosmos_types::{
+	coin::parse_dec_coins,
+	context::{traits::Context as _, Context},
+	errors::RootError,
+	events::{
+		traits::EventManager as _, CosmosEvent, EventAttribute, ATTRIBUTE_KEY_AMOUNT,
+		ATTRIBUTE_KEY_SENDER, EVENT_TYPE_MESSAGE,
+	},
+	gas::BasicGasMeter,
+	msgservice::MsgHandler,
+	tx_result::{block_txs_key, tx_key, TxResult},
+};
+use pallet_cosmos_x_bank::msgs::MsgMultiSendHandler;
+use pallet_cosmos_x_bank_types::{
+	errors::BankError,
+	events::{ATTRIBUTE_KEY_RECIPIENT, EVENT_TYPE_TRANSFER},
+	metadata::Metadata,
+	msgs::msg_send::MsgSend,
+};
+use parity_scale_codec::Decode;
 use sp_core::{ecdsa, Pair, H160};
+use sp_runtime::{traits::Convert, transaction_validity::InvalidTransaction, DispatchError};
 use std::fs;
 
+const MSG_SEND_TX: &str = "CpoBCpcBChwvY29zbW9zLmJhbmsudjFiZXRhMS5Nc2dTZW5kEncKLWNvc21vczFxZDY5bnV3ajk1Z3RhNGFramd5eHRqOXVqbXo0dzhlZG1xeXNxdxItY29zbW9zMW41amd4NjR6dzM4c3M3Nm16dXU0dWM3amV5cXcydmZqazYwZmR6GhcKBGFjZHQSDzEwMDAwMDAwMDAwMDAwMBJsCk4KRgofL2Nvc21vcy5jcnlwdG8uc2VjcDI1NmsxLlB1YktleRIjCiECChCRNB/lZkv6F4LV4Ed5aJBoyRawTLNl7DFTdVaE2aESBAoCCH8SGgoSCgRhY2R0EgoxMDQwMDAwMDAwEIDa8esEGkBgXIiPoBpecG7QpKDJPaztFogqvmxjDHF5ORfWBrOoSzf0+AAmch1CXrG4OmiKL0y8v9ITx0QzWYUc7ueXcdIm";
+
 #[test]
 fn pallet_cosmos_msg_send_test() {
 	new_test_ext().execute_with(|| {
@@ -35,9 +74,9 @@ fn pallet_cosmos_msg_send_test() {
 
 		let alice = CosmosSigner(ecdsa::Pair::from_string("//Alice", None).unwrap().public());
 
-		let tx_raw =  "CpoBCpcBChwvY29zbW9zLmJhbmsudjFiZXRhMS5Nc2dTZW5kEncKLWNvc21vczFxZDY5bnV3ajk1Z3RhNGFramd5eHRqOXVqbXo0dzhlZG1xeXNxdxItY29zbW9zMW41amd4NjR6dzM4c3M3Nm16dXU0dWM3amV5cXcydmZqazYwZmR6GhcKBGFjZHQSDzEwMDAwMDAwMDAwMDAwMBJsCk4KRgofL2Nvc21vcy5jcnlwdG8uc2VjcDI1NmsxLlB1YktleRIjCiECChCRNB/lZkv6F4LV4Ed5aJBoyRawTLNl7DFTdVaE2aESBAoCCH8SGgoSCgRhY2R0EgoxMDQwMDAwMDAwEIDa8esEGkBgXIiPoBpecG7QpKDJPaztFogqvmxjDHF5ORfWBrOoSzf0+AAmch1CXrG4OmiKL0y8v9ITx0QzWYUc7ueXcdIm";
-		let tx_bytes = Base64::decode_vec(&tx_raw).unwrap();
+		let tx_bytes = Base64::decode_vec(MSG_SEND_TX).unwrap();
 		let tx = Tx::decode(&mut &*tx_bytes).unwrap();
+		let tx_size = tx_bytes.len() as u64;
 
 		let mut expected_balance = 1_000_000_000_000_000_000u128;
 		assert_eq!(Balances::balance(&alice), expected_balance);
@@ -53,6 +92,9 @@ fn pallet_cosmos_msg_send_test() {
 		assert_ok!(call.pre_dispatch_self_contained(&dispatch_info, 0).unwrap());
 		assert_ok!(extrinsic.function.apply_self_contained(alice).unwrap());
 
+		let gas_limit = tx.auth_info.as_ref().unwrap().fee.as_ref().unwrap().gas_limit;
+		assert_eq!(Cosmos::block_gas_used(), gas_limit);
+
 		let msg = tx.body.as_ref().unwrap().messages.first().unwrap();
 		let msg = MsgSend::try_from(msg).unwrap();
 
@@ -64,6 +106,128 @@ fn pallet_cosmos_msg_send_test() {
 		expected_balance -= fee_amount;
 
 		assert_eq!(Balances::balance(&alice), expected_balance);
+
+		let (gas_used, events) = System::events()
+			.into_iter()
+			.find_map(|record| {
+				if let RuntimeEvent::Cosmos(pallet_cosmos::Event::Executed {
+					gas_used,
+					events,
+					..
+				}) = record.event
+				{
+					Some((gas_used, events))
+				} else {
+					None
+				}
+			})
+			.unwrap();
+
+		let event_types = events.iter().map(|event| event.r#type.as_slice()).collect::<Vec<_>>();
+		assert_eq!(
+			event_types,
+			vec![&b"message"[..], &b"coin_spent"[..], &b"coin_received"[..], &b"transfer"[..]]
+		);
+		assert_eq!(
+			events[0],
+			CosmosEvent::message("/cosmos.bank.v1beta1.MsgSend", &msg.from_address)
+		);
+		// The ante handlers charge for the size of the tx and its signature.
+		assert!(gas_used >= tx_size * TxSizeCostPerByte::get() + SigVerifyCostSecp256k1::get());
+	});
+}
+
+#[test]
+fn pallet_cosmos_msg_multi_send_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = CosmosSigner(ecdsa::Pair::from_string("//Alice", None).unwrap().public());
+		let bob = CosmosSigner(ecdsa::Pair::from_string("//Bob", None).unwrap().public());
+		let alice_address = AccountToAddr::<Test>::convert(alice);
+		let bob_address = AccountToAddr::<Test>::convert(bob);
+		let charlie_address = bech32::encode::<Bech32>(
+			Hrp::parse("cosmos").unwrap(),
+			H160::repeat_byte(1).as_bytes(),
+		)
+		.unwrap();
+
+		let coins = |amount: u128| vec![Coin { denom: "acdt".into(), amount: amount.to_string() }];
+		let input = |amount| Input { address: alice_address.clone(), coins: coins(amount) };
+		let output =
+			|address: &String, amount| Output { address: address.clone(), coins: coins(amount) };
+		let multi_send = |inputs, outputs| {
+			let msg = Any {
+				type_url: MsgMultiSend::type_url(),
+				value: MsgMultiSend { inputs, outputs }.encode_to_vec(),
+			};
+			let mut ctx = Context::<BasicGasMeter>::new(u64::MAX);
+			MsgMultiSendHandler::<Test>::default()
+				.handle(&msg, &mut ctx)
+				.map(|_| ctx.event_manager.events())
+		};
+
+		assert_eq!(
+			multi_send(
+				vec![input(300)],
+				vec![output(&bob_address, 100), output(&charlie_address, 100)]
+			),
+			Err(BankError::InputOutputMismatch.into())
+		);
+		assert_eq!(
+			multi_send(
+				vec![input(100), input(100)],
+				vec![output(&bob_address, 100), output(&charlie_address, 100)]
+			),
+			Err(BankError::MultipleSenders.into())
+		);
+
+		assert_ok!(Cosmos::set_blocked_addr(RuntimeOrigin::root(), bob, true));
+		assert_eq!(
+			multi_send(
+				vec![input(300)],
+				vec![output(&bob_address, 100), output(&charlie_address, 200)]
+			),
+			Err(RootError::Unauthorized.into())
+		);
+		assert_ok!(Cosmos::set_blocked_addr(RuntimeOrigin::root(), bob, false));
+
+		let alice_balance = Balances::balance(&alice);
+		let bob_balance = Balances::balance(&bob);
+		let events = multi_send(
+			vec![input(300)],
+			vec![output(&bob_address, 100), output(&charlie_address, 200)],
+		)
+		.unwrap();
+		assert_eq!(Balances::balance(&alice), alice_balance - 300);
+		assert_eq!(Balances::balance(&bob), bob_balance + 100);
+
+		// The message event is emitted once per message, outside the handler.
+		assert!(!events.iter().any(|event| event.r#type == EVENT_TYPE_MESSAGE.as_bytes()));
+		let transfers = events
+			.iter()
+			.filter(|event| event.r#type == EVENT_TYPE_TRANSFER.as_bytes())
+			.collect::<Vec<_>>();
+		assert_eq!(transfers.len(), 2);
+		for (transfer, (recipient, amount)) in transfers
+			.into_iter()
+			.zip([(&bob_address, "100acdt"), (&charlie_address, "200acdt")])
+		{
+			assert_eq!(
+				transfer.attributes,
+				vec![
+					EventAttribute {
+						key: ATTRIBUTE_KEY_SENDER.into(),
+						value: alice_address.clone().into()
+					},
+					EventAttribute {
+						key: ATTRIBUTE_KEY_RECIPIENT.into(),
+						value: recipient.clone().into()
+					},
+					EventAttribute { key: ATTRIBUTE_KEY_AMOUNT.into(), value: amount.into() },
+				]
+			);
+		}
 	});
 }
 
@@ -230,3 +394,257 @@ fn pallet_cosmos_msg_execute_contract_test() {
 		assert_ok!(extrinsic.function.apply_self_contained(alice).unwrap());
 	});
 }
+
+#[test]
+fn pallet_cosmos_tx_priority_test() {
+	let tx = |amount: Vec<Coin>, gas_limit: u64| Tx {
+		auth_info: Some(AuthInfo {
+			fee: Some(Fee { amount, gas_limit, ..Default::default() }),
+			..Default::default()
+		}),
+		..Default::default()
+	};
+	let coin = |denom: &str, amount: &str| Coin { denom: denom.into(), amount: amount.into() };
+
+	assert_eq!(Cosmos::priority(&tx(vec![coin("acdt", "2600000000")], 1_300_000)), 2_000);
+	assert_eq!(Cosmos::priority(&tx(vec![coin("uatom", "9999"), coin("acdt", "200")], 100)), 2);
+	assert_eq!(Cosmos::priority(&tx(vec![coin("uatom", "2600000000")], 1_300_000)), 0);
+	assert_eq!(Cosmos::priority(&tx(vec![coin("acdt", "2600000000")], 0)), 0);
+	assert_eq!(Cosmos::priority(&Tx::default()), 0);
+}
+
+#[test]
+fn pallet_cosmos_min_gas_prices_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = CosmosSigner(ecdsa::Pair::from_string("//Alice", None).unwrap().public());
+
+		let tx_bytes = Base64::decode_vec(MSG_SEND_TX).unwrap();
+		let fee = Tx::decode(&mut &*tx_bytes).unwrap().auth_info.unwrap().fee.unwrap();
+		let fee_amount = fee.amount.first().unwrap().amount.parse::<u128>().unwrap();
+		let gas_price = fee_amount / u128::from(fee.gas_limit);
+
+		let call = pallet_cosmos::Call::<Test>::transact { tx_bytes };
+		let source = call.check_self_contained().unwrap().unwrap();
+		let extrinsic = CheckedExtrinsic::<CosmosSigner, _, (), H160> {
+			signed: fp_self_contained::CheckedSignature::SelfContained(source),
+			function: RuntimeCall::Cosmos(call.clone()),
+		};
+		let dispatch_info = extrinsic.get_dispatch_info();
+
+		// A price the tx pays less than keeps it out of the pool, but not out of a block.
+		MinGasPrices::set(parse_dec_coins(&format!("{}acdt", gas_price + 1)).unwrap());
+		assert_eq!(
+			call.validate_self_contained(&source, &dispatch_info, 0).unwrap(),
+			Err(InvalidTransaction::Payment.into())
+		);
+		assert_ok!(call.pre_dispatch_self_contained(&dispatch_info, 0).unwrap());
+		assert_ok!(extrinsic.function.apply_self_contained(alice).unwrap());
+	});
+}
+
+#[test]
+fn pallet_cosmos_refund_unused_gas_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		RefundUnusedGas::set(true);
+
+		let alice = CosmosSigner(ecdsa::Pair::from_string("//Alice", None).unwrap().public());
+
+		let tx_bytes = Base64::decode_vec(MSG_SEND_TX).unwrap();
+		let tx = Tx::decode(&mut &*tx_bytes).unwrap();
+
+		let total_issuance = Balances::total_issuance();
+		let balance = Balances::balance(&alice);
+
+		let call = pallet_cosmos::Call::<Test>::transact { tx_bytes };
+		let source = call.check_self_contained().unwrap().unwrap();
+		let extrinsic = CheckedExtrinsic::<CosmosSigner, _, (), H160> {
+			signed: fp_self_contained::CheckedSignature::SelfContained(source),
+			function: RuntimeCall::Cosmos(call.clone()),
+		};
+		let dispatch_info = extrinsic.get_dispatch_info();
+
+		assert_ok!(call.pre_dispatch_self_contained(&dispatch_info, 0).unwrap());
+		assert_ok!(extrinsic.function.apply_self_contained(alice).unwrap());
+
+		let gas_used = System::events()
+			.into_iter()
+			.find_map(|record| match record.event {
+				RuntimeEvent::Cosmos(pallet_cosmos::Event::Executed { gas_used, .. }) =>
+					Some(gas_used),
+				_ => None,
+			})
+			.unwrap();
+
+		let fee = tx.auth_info.as_ref().unwrap().fee.clone().unwrap();
+		let fee_amount = fee.amount.first().unwrap().amount.parse::<u128>().unwrap();
+		let refund = fee_amount * u128::from(fee.gas_limit - gas_used) / u128::from(fee.gas_limit);
+		assert!(refund > 0);
+
+		let msg = MsgSend::try_from(tx.body.as_ref().unwrap().messages.first().unwrap()).unwrap();
+		let amount = msg.amount.first().unwrap().amount.parse::<u128>().unwrap();
+
+		// The refund is taken out of the fee rather than minted.
+		assert_eq!(Balances::total_issuance(), total_issuance);
+		assert_eq!(Balances::balance(&alice), balance - amount - fee_amount + refund);
+		assert_eq!(Balances::balance(&FeeCollector::get()), fee_amount - refund);
+
+		// Every coin spent, including the fee passing through the fee collector, is received.
+		let events = crate::BlockTxResults::<Test>::get().remove(0).events;
+		let total = |r#type: &[u8]| {
+			events
+				.iter()
+				.filter(|event| event.r#type == r#type)
+				.map(|event| {
+					let amount = String::from_utf8(event.attributes[1].value.clone()).unwrap();
+					amount.trim_end_matches(char::is_alphabetic).parse::<u128>().unwrap()
+				})
+				.sum::<u128>()
+		};
+		assert_eq!(total(b"coin_spent"), amount + fee_amount + refund + (fee_amount - refund));
+		assert_eq!(total(b"coin_spent"), total(b"coin_received"));
+		assert!(events.iter().any(|event| event.r#type == b"coin_received" &&
+			event.attributes[0].value == Cosmos::fee_collector().into_bytes()));
+	});
+}
+
+#[test]
+fn pallet_cosmos_set_denom_metadata_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = CosmosSigner(ecdsa::Pair::from_string("//Alice", None).unwrap().public());
+
+		let denom = BoundedVec::<u8, MaxDenomLimit>::try_from(b"stake".to_vec()).unwrap();
+		DenomAssetRouter::<Test>::insert(denom.clone(), 0);
+
+		let metadata = Cosmos::asset_denom_metadata("stake".into(), 0);
+		assert_eq!(metadata.denom_units.last().unwrap().exponent, 18);
+
+		assert_noop!(
+			Cosmos::set_denom_metadata(RuntimeOrigin::signed(alice), metadata.clone()),
+			DispatchError::BadOrigin
+		);
+
+		let mut invalid = metadata.clone();
+		invalid.display = "ustake".into();
+		assert_noop!(
+			Cosmos::set_denom_metadata(RuntimeOrigin::root(), invalid),
+			Error::<Test>::InvalidDenomMetadata
+		);
+
+		let unknown = Metadata::from_asset("uatom".into(), "Atom".into(), "ATOM".into(), 6);
+		assert_noop!(
+			Cosmos::set_denom_metadata(RuntimeOrigin::root(), unknown),
+			Error::<Test>::UnknownDenom
+		);
+
+		assert_ok!(Cosmos::set_denom_metadata(RuntimeOrigin::root(), metadata.clone()));
+		assert_eq!(Cosmos::denom_metadata(denom), Some(metadata));
+	});
+}
+
+#[test]
+fn pallet_cosmos_send_restrictions_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = CosmosSigner(ecdsa::Pair::from_string("//Alice", None).unwrap().public());
+		let bob = CosmosSigner(ecdsa::Pair::from_string("//Bob", None).unwrap().public());
+
+		let denom = BoundedVec::<u8, MaxDenomLimit>::try_from(b"stake".to_vec()).unwrap();
+		DenomAssetRouter::<Test>::insert(denom, 0);
+
+		assert!(Cosmos::is_send_enabled("stake"));
+		assert_noop!(
+			Cosmos::set_send_enabled(RuntimeOrigin::signed(alice), b"stake".to_vec(), Some(false)),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Cosmos::set_send_enabled(RuntimeOrigin::root(), b"uatom".to_vec(), Some(false)),
+			Error::<Test>::UnknownDenom
+		);
+
+		assert_ok!(Cosmos::set_send_enabled(RuntimeOrigin::root(), b"stake".to_vec(), Some(false)));
+		assert!(!Cosmos::is_send_enabled("stake"));
+
+		assert_ok!(Cosmos::set_default_send_enabled(RuntimeOrigin::root(), false));
+		assert_ok!(Cosmos::set_send_enabled(RuntimeOrigin::root(), b"stake".to_vec(), Some(true)));
+		assert!(Cosmos::is_send_enabled("stake"));
+		assert_ok!(Cosmos::set_send_enabled(RuntimeOrigin::root(), b"stake".to_vec(), None));
+		assert!(!Cosmos::is_send_enabled("stake"));
+
+		assert!(!Cosmos::is_blocked_addr(&bob));
+		assert_ok!(Cosmos::set_blocked_addr(RuntimeOrigin::root(), bob, true));
+		assert!(Cosmos::is_blocked_addr(&bob));
+		assert_ok!(Cosmos::set_blocked_addr(RuntimeOrigin::root(), bob, false));
+		assert!(!Cosmos::is_blocked_addr(&bob));
+	});
+}
+
+#[test]
+fn pallet_cosmos_tx_result_test() {
+	let mut ext = new_test_ext();
+
+	let tx_bytes = Base64::decode_vec(MSG_SEND_TX).unwrap();
+	let hash = sp_io::hashing::sha2_256(&tx_bytes);
+	let tx = Tx::decode(&mut &*tx_bytes).unwrap();
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = CosmosSigner(ecdsa::Pair::from_string("//Alice", None).unwrap().public());
+
+		let call = pallet_cosmos::Call::<Test>::transact { tx_bytes };
+		let source = call.check_self_contained().unwrap().unwrap();
+		let extrinsic = CheckedExtrinsic::<CosmosSigner, _, (), H160> {
+			signed: fp_self_contained::CheckedSignature::SelfContained(source),
+			function: RuntimeCall::Cosmos(call.clone()),
+		};
+		let dispatch_info = extrinsic.get_dispatch_info();
+
+		assert_ok!(call.pre_dispatch_self_contained(&dispatch_info, 0).unwrap());
+		assert_ok!(extrinsic.function.apply_self_contained(alice).unwrap());
+		Cosmos::on_finalize(1);
+	});
+	ext.persist_offchain_overlay();
+
+	// No extrinsics were noted, as the call was applied outside of `Executive`.
+	let extrinsics_root =
+		frame_system::extrinsics_data_root::<<Test as frame_system::Config>::Hashing>(vec![]);
+	let block = ext
+		.offchain_db()
+		.get(&tx_key(&hash))
+		.map(|value| <(u64, Vec<u8>)>::decode(&mut &*value).unwrap());
+	assert_eq!(block, Some((1, extrinsics_root.as_ref().to_vec())));
+
+	let mut block_txs = ext
+		.offchain_db()
+		.get(&block_txs_key(1, extrinsics_root.as_ref()))
+		.map(|value| Vec::<TxResult>::decode(&mut &*value).unwrap())
+		.unwrap();
+	assert_eq!(block_txs.len(), 1);
+	let tx_result = block_txs.remove(0);
+
+	assert!(tx_result.is_ok());
+	assert_eq!(tx_result.hash, hash);
+	assert_eq!(tx_result.height, 1);
+	assert_eq!(tx_result.gas_wanted, tx.auth_info.unwrap().fee.unwrap().gas_limit);
+
+	// The fee paid in the ante handlers comes before the events of the msgs.
+	let event_types =
+		tx_result.events.iter().map(|event| event.r#type.as_slice()).collect::<Vec<_>>();
+	assert_eq!(event_types.first(), Some(&&b"coin_spent"[..]));
+	assert!(event_types.contains(&&b"transfer"[..]));
+
+	let attributes = tx_result.attributes();
+	assert!(attributes.contains(&("tx.height".into(), "1".into())));
+	assert!(attributes
+		.contains(&("tx.hash".into(), hash.iter().map(|b| format!("{:02X}", b)).collect())));
+	assert!(attributes.contains(&(
+		"transfer.recipient".into(),
+		"cosmos1n5jgx64zw38ss76mzuu4uc7jeyqw2vfjk60fdz".into()
+	)));
+}
@@ -22,6 +22,10 @@ use sp_core::Get;
 
 pub trait WeightInfo {
 	fn default_weight() -> Weight;
+	fn set_denom_metadata() -> Weight;
+	fn set_default_send_enabled() -> Weight;
+	fn set_send_enabled() -> Weight;
+	fn set_blocked_addr() -> Weight;
 }
 
 pub struct CosmosWeight<T>(PhantomData<T>);
@@ -32,4 +36,20 @@ where
 	fn default_weight() -> Weight {
 		T::BlockWeights::get().get(DispatchClass::Normal).base_extrinsic
 	}
+
+	fn set_denom_metadata() -> Weight {
+		Weight::from_parts(10_000_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+
+	fn set_default_send_enabled() -> Weight {
+		Weight::from_parts(10_000_000, 0).saturating_add(T::DbWeight::get().writes(1))
+	}
+
+	fn set_send_enabled() -> Weight {
+		Weight::from_parts(10_000_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+
+	fn set_blocked_addr() -> Weight {
+		Weight::from_parts(10_000_000, 0).saturating_add(T::DbWeight::get().writes(1))
+	}
 }
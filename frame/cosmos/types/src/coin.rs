@@ -15,10 +15,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use alloc::string::{String, ToString};
+use crate::errors::{CosmosError, RootError};
+use alloc::{
+	collections::BTreeMap,
+	string::{String, ToString},
+	vec::Vec,
+};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
+use sp_runtime::{FixedPointNumber, FixedU128};
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Error {
+	InvalidAmount,
+	InvalidDenom,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Decode, Encode, TypeInfo, Serialize, Deserialize)]
 pub struct Coin {
 	pub amount: String,
 	pub denom: String,
@@ -30,6 +44,12 @@ impl From<&cosmos_sdk_proto::cosmos::base::v1beta1::Coin> for Coin {
 	}
 }
 
+impl From<Coin> for cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+	fn from(coin: Coin) -> Self {
+		Self { amount: coin.amount, denom: coin.denom }
+	}
+}
+
 pub fn amount_to_string(amount: &[cosmos_sdk_proto::cosmos::base::v1beta1::Coin]) -> String {
 	let mut ret = "".to_string();
 	for (i, coin) in amount.iter().enumerate() {
@@ -42,10 +62,101 @@ pub fn amount_to_string(amount: &[cosmos_sdk_proto::cosmos::base::v1beta1::Coin]
 	ret
 }
 
+/// Coin amounts keyed by denomination. Zero amounts are never stored.
+pub type Coins = BTreeMap<String, u128>;
+
+pub fn coins_from(
+	coins: &[cosmos_sdk_proto::cosmos::base::v1beta1::Coin],
+) -> Result<Coins, CosmosError> {
+	let mut ret = Coins::new();
+	for coin in coins.iter() {
+		let amount = coin.amount.parse::<u128>().map_err(|_| RootError::InvalidCoins)?;
+		if ret.insert(coin.denom.clone(), amount).is_some() {
+			return Err(RootError::InvalidCoins.into());
+		}
+	}
+	ret.retain(|_, amount| *amount > 0);
+
+	Ok(ret)
+}
+
+/// Subtracts `b` from `a`, failing if any denomination would go negative.
+pub fn safe_sub(a: &Coins, b: &Coins) -> Option<Coins> {
+	let mut ret = a.clone();
+	for (denom, amount) in b.iter() {
+		let balance = ret.get_mut(denom)?;
+		*balance = balance.checked_sub(*amount)?;
+	}
+	ret.retain(|_, amount| *amount > 0);
+
+	Some(ret)
+}
+
+/// A coin with a decimal amount, e.g. a gas price of `0.0025uatom`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecCoin {
+	pub denom: String,
+	pub amount: FixedU128,
+}
+
+impl DecCoin {
+	/// Returns the amount required for the given gas, rounded up to an integer.
+	pub fn required_amount(&self, gas: u64) -> u128 {
+		let required = self.amount.into_inner().saturating_mul(gas.into());
+		required.div_ceil(FixedU128::accuracy())
+	}
+}
+
+/// Parses a comma separated list of decimal coins, e.g. `0.0025uatom,0.1stake`.
+pub fn parse_dec_coins(coins: &str) -> Result<Vec<DecCoin>, Error> {
+	let coins = coins.trim();
+	if coins.is_empty() {
+		return Ok(Vec::new());
+	}
+
+	coins.split(',').map(|coin| parse_dec_coin(coin.trim())).collect()
+}
+
+fn parse_dec_coin(coin: &str) -> Result<DecCoin, Error> {
+	let split = coin
+		.find(|c: char| !c.is_ascii_digit() && c != '.')
+		.ok_or(Error::InvalidDenom)?;
+	let (amount, denom) = coin.split_at(split);
+
+	if denom.len() < 3 ||
+		denom.len() > 128 ||
+		!denom.starts_with(|c: char| c.is_ascii_alphabetic()) ||
+		!denom.chars().all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c))
+	{
+		return Err(Error::InvalidDenom);
+	}
+
+	let (integer, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+	if integer.is_empty() || fraction.len() > 18 {
+		return Err(Error::InvalidAmount);
+	}
+	let integer = integer.parse::<u128>().map_err(|_| Error::InvalidAmount)?;
+	let fraction = if fraction.is_empty() {
+		0
+	} else {
+		fraction.parse::<u128>().map_err(|_| Error::InvalidAmount)? *
+			10u128.pow(18 - fraction.len() as u32)
+	};
+
+	let amount = integer
+		.checked_mul(FixedU128::accuracy())
+		.and_then(|integer| integer.checked_add(fraction))
+		.map(FixedU128::from_inner)
+		.ok_or(Error::InvalidAmount)?;
+
+	Ok(DecCoin { denom: denom.to_string(), amount })
+}
+
 #[cfg(test)]
 mod tests {
-	use crate::coin::amount_to_string;
+	use crate::coin::{amount_to_string, parse_dec_coins, DecCoin, Error};
 	use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+	use sp_runtime::FixedU128;
 
 	#[test]
 	fn amount_to_string_test() {
@@ -59,4 +170,35 @@ mod tests {
 
 		assert_eq!(amount_to_string(&amounts), "1000uatom,2000stake");
 	}
+
+	#[test]
+	fn parse_dec_coins_test() {
+		assert_eq!(parse_dec_coins(""), Ok(vec![]));
+
+		let coins = parse_dec_coins("0.0025uatom,1stake").unwrap();
+		assert_eq!(
+			coins,
+			vec![
+				DecCoin {
+					denom: "uatom".to_string(),
+					amount: FixedU128::from_rational(25, 10_000)
+				},
+				DecCoin { denom: "stake".to_string(), amount: FixedU128::from_u32(1) },
+			]
+		);
+
+		assert_eq!(parse_dec_coins("uatom"), Err(Error::InvalidAmount));
+		assert_eq!(parse_dec_coins("0.1"), Err(Error::InvalidDenom));
+		assert_eq!(parse_dec_coins("0.1u"), Err(Error::InvalidDenom));
+		assert_eq!(parse_dec_coins("1.2.3uatom"), Err(Error::InvalidAmount));
+	}
+
+	#[test]
+	fn required_amount_test() {
+		let price =
+			DecCoin { denom: "uatom".to_string(), amount: FixedU128::from_rational(25, 10_000) };
+		assert_eq!(price.required_amount(200_000), 500);
+		assert_eq!(price.required_amount(200_001), 501);
+		assert_eq!(price.required_amount(0), 0);
+	}
 }
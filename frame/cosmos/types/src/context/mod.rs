@@ -22,17 +22,23 @@ use crate::{
 	gas::{traits::GasMeter, BasicGasMeter, Gas},
 };
 
-pub struct Context {
-	pub gas_meter: BasicGasMeter,
+/// A context whose gas meter is [`BasicGasMeter`] unless another one is given.
+pub struct Context<M = BasicGasMeter> {
+	pub gas_meter: M,
 	pub event_manager: EventManager,
+	pub check_tx: bool,
 }
 
-impl traits::Context for Context {
-	type GasMeter = BasicGasMeter;
+impl<M: GasMeter> traits::Context for Context<M> {
+	type GasMeter = M;
 	type EventManager = EventManager;
 
 	fn new(limit: Gas) -> Self {
-		Self { gas_meter: Self::GasMeter::new(limit), event_manager: Self::EventManager::new() }
+		Self {
+			gas_meter: Self::GasMeter::new(limit),
+			event_manager: Self::EventManager::new(),
+			check_tx: false,
+		}
 	}
 
 	fn gas_meter(&mut self) -> &mut Self::GasMeter {
@@ -42,4 +48,12 @@ impl traits::Context for Context {
 	fn event_manager(&mut self) -> &mut Self::EventManager {
 		&mut self.event_manager
 	}
+
+	fn is_check_tx(&self) -> bool {
+		self.check_tx
+	}
+
+	fn set_check_tx(&mut self, check_tx: bool) {
+		self.check_tx = check_tx;
+	}
 }
@@ -27,4 +27,7 @@ pub trait Context {
 	fn new(limit: Gas) -> Self;
 	fn gas_meter(&mut self) -> &mut Self::GasMeter;
 	fn event_manager(&mut self) -> &mut Self::EventManager;
+	/// Whether the transaction is being checked for the transaction pool.
+	fn is_check_tx(&self) -> bool;
+	fn set_check_tx(&mut self, check_tx: bool);
 }
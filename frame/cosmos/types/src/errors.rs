@@ -29,13 +29,23 @@ pub const ROOT_CODESPACE: u8 = 0;
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum RootError {
+	Internal = 1,
 	TxDecodeError = 2,
+	Unauthorized = 4,
 	InsufficientFunds = 5,
 	UnknownRequest = 6,
 	InvalidAddress = 7,
+	UnknownAddress = 9,
 	InvalidCoins = 10,
 	OutOfGas = 11,
+	InsufficientFee = 13,
+	InvalidRequest = 18,
+	TxInMempoolCache = 19,
+	MempoolIsFull = 20,
+	InvalidType = 29,
+	WrongSequence = 32,
 	UnpackAnyError = 34,
+	NotFound = 38,
 }
 
 impl From<RootError> for CosmosError {
@@ -15,9 +15,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod query;
 pub mod traits;
 
-use alloc::vec::Vec;
+use crate::coin::amount_to_string;
+use alloc::{vec, vec::Vec};
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
 use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
@@ -26,12 +29,25 @@ pub const EVENT_TYPE_TX: &str = "tx";
 
 pub const ATTRIBUTE_KEY_FEE: &str = "fee";
 pub const ATTRIBUTE_KEY_FEE_PAYER: &str = "fee_payer";
+pub const ATTRIBUTE_KEY_REFUND: &str = "refund";
 
 pub const EVENT_TYPE_MESSAGE: &str = "message";
 
+pub const ATTRIBUTE_KEY_ACTION: &str = "action";
+pub const ATTRIBUTE_KEY_MODULE: &str = "module";
 pub const ATTRIBUTE_KEY_SENDER: &str = "sender";
 pub const ATTRIBUTE_KEY_AMOUNT: &str = "amount";
 
+pub const EVENT_TYPE_COIN_SPENT: &str = "coin_spent";
+pub const EVENT_TYPE_COIN_RECEIVED: &str = "coin_received";
+pub const EVENT_TYPE_COIN_MINT: &str = "coinbase";
+pub const EVENT_TYPE_COIN_BURN: &str = "burn";
+
+pub const ATTRIBUTE_KEY_SPENDER: &str = "spender";
+pub const ATTRIBUTE_KEY_RECEIVER: &str = "receiver";
+pub const ATTRIBUTE_KEY_MINTER: &str = "minter";
+pub const ATTRIBUTE_KEY_BURNER: &str = "burner";
+
 #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, Serialize, Deserialize)]
 pub struct CosmosEvent {
 	#[serde(rename = "type")]
@@ -47,6 +63,66 @@ pub struct EventAttribute {
 
 pub type CosmosEvents = Vec<CosmosEvent>;
 
+impl CosmosEvent {
+	/// `amount` was taken from the balance of `spender`.
+	pub fn coin_spent(spender: &str, amount: &[Coin]) -> Self {
+		Self::coin_event(EVENT_TYPE_COIN_SPENT, ATTRIBUTE_KEY_SPENDER, spender, amount)
+	}
+
+	/// `amount` was added to the balance of `receiver`.
+	pub fn coin_received(receiver: &str, amount: &[Coin]) -> Self {
+		Self::coin_event(EVENT_TYPE_COIN_RECEIVED, ATTRIBUTE_KEY_RECEIVER, receiver, amount)
+	}
+
+	/// `amount` was minted to the balance of `minter`.
+	pub fn coin_mint(minter: &str, amount: &[Coin]) -> Self {
+		Self::coin_event(EVENT_TYPE_COIN_MINT, ATTRIBUTE_KEY_MINTER, minter, amount)
+	}
+
+	/// `amount` was burned from the balance of `burner`.
+	pub fn coin_burn(burner: &str, amount: &[Coin]) -> Self {
+		Self::coin_event(EVENT_TYPE_COIN_BURN, ATTRIBUTE_KEY_BURNER, burner, amount)
+	}
+
+	/// The `message` event of a msg of `type_url` signed by `sender`.
+	pub fn message(type_url: &str, sender: &str) -> Self {
+		Self {
+			r#type: EVENT_TYPE_MESSAGE.into(),
+			attributes: vec![
+				EventAttribute { key: ATTRIBUTE_KEY_ACTION.into(), value: type_url.into() },
+				EventAttribute {
+					key: ATTRIBUTE_KEY_MODULE.into(),
+					value: module_name_from_type_url(type_url).into(),
+				},
+				EventAttribute { key: ATTRIBUTE_KEY_SENDER.into(), value: sender.into() },
+			],
+		}
+	}
+
+	fn coin_event(r#type: &str, key: &str, address: &str, amount: &[Coin]) -> Self {
+		Self {
+			r#type: r#type.into(),
+			attributes: vec![
+				EventAttribute { key: key.into(), value: address.into() },
+				EventAttribute {
+					key: ATTRIBUTE_KEY_AMOUNT.into(),
+					value: amount_to_string(amount).into(),
+				},
+			],
+		}
+	}
+}
+
+/// Module name of a msg type URL, e.g. `bank` for `/cosmos.bank.v1beta1.MsgSend`.
+pub fn module_name_from_type_url(type_url: &str) -> &str {
+	let mut parts = type_url.split('.');
+	match (parts.next(), parts.next()) {
+		(Some("/cosmos"), Some(module)) => module,
+		(Some(package), Some(_)) => package.trim_start_matches('/'),
+		_ => "",
+	}
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct EventManager {
 	events: CosmosEvents,
@@ -69,3 +145,15 @@ impl traits::EventManager for EventManager {
 		self.events.extend(events);
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::module_name_from_type_url;
+
+	#[test]
+	fn module_name_from_type_url_test() {
+		assert_eq!(module_name_from_type_url("/cosmos.bank.v1beta1.MsgSend"), "bank");
+		assert_eq!(module_name_from_type_url("/cosmwasm.wasm.v1.MsgExecuteContract"), "cosmwasm");
+		assert_eq!(module_name_from_type_url("MsgSend"), "");
+	}
+}
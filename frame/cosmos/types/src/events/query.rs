@@ -0,0 +1,355 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Queries over event attributes in the syntax of CometBFT, such as
+//! `transfer.recipient='cosmos1...' AND tx.height>100`.
+
+use alloc::{
+	string::{String, ToString},
+	vec::Vec,
+};
+use core::cmp::Ordering;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Operand {
+	String(String),
+	/// An integer or decimal number, kept as written.
+	Number(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Operator {
+	Eq(Operand),
+	Lt(Operand),
+	Le(Operand),
+	Gt(Operand),
+	Ge(Operand),
+	Contains(String),
+	Exists,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Condition {
+	/// The composite key of an attribute, `<event type>.<attribute key>`.
+	pub key: String,
+	pub op: Operator,
+}
+
+impl Condition {
+	/// Whether the attribute `value` satisfies the condition.
+	pub fn matches(&self, value: &str) -> bool {
+		match &self.op {
+			Operator::Eq(operand) => compare(value, operand) == Some(Ordering::Equal),
+			Operator::Lt(operand) => compare(value, operand) == Some(Ordering::Less),
+			Operator::Le(operand) =>
+				compare(value, operand).is_some_and(|o| o != Ordering::Greater),
+			Operator::Gt(operand) => compare(value, operand) == Some(Ordering::Greater),
+			Operator::Ge(operand) => compare(value, operand).is_some_and(|o| o != Ordering::Less),
+			Operator::Contains(s) => value.contains(s.as_str()),
+			Operator::Exists => true,
+		}
+	}
+}
+
+/// Compares `value` to `operand`, numerically if `operand` is a number.
+fn compare(value: &str, operand: &Operand) -> Option<Ordering> {
+	match operand {
+		Operand::String(s) => Some(value.cmp(s.as_str())),
+		Operand::Number(n) => match (value.parse::<i128>(), n.parse::<i128>()) {
+			(Ok(value), Ok(n)) => Some(value.cmp(&n)),
+			_ => value.parse::<f64>().ok()?.partial_cmp(&n.parse::<f64>().ok()?),
+		},
+	}
+}
+
+/// A conjunction of conditions, all of which must hold.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EventQuery {
+	pub conditions: Vec<Condition>,
+}
+
+/// The query is malformed at byte `position`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+	pub position: usize,
+}
+
+impl EventQuery {
+	pub fn parse(query: &str) -> Result<Self, ParseError> {
+		let mut parser = Parser { input: query, position: 0 };
+		let mut conditions = Vec::new();
+
+		parser.skip_whitespace();
+		if parser.is_empty() {
+			return Ok(Self { conditions });
+		}
+		loop {
+			conditions.push(parser.condition()?);
+			parser.skip_whitespace();
+			if parser.is_empty() {
+				return Ok(Self { conditions });
+			}
+			parser.keyword("AND")?;
+		}
+	}
+
+	/// Whether `attributes`, given as composite keys and values, satisfy every condition.
+	/// A condition holds if any attribute with its key satisfies it.
+	pub fn matches<'a, I>(&self, attributes: I) -> bool
+	where
+		I: IntoIterator<Item = (&'a str, &'a str)> + Clone,
+	{
+		self.conditions.iter().all(|condition| {
+			attributes
+				.clone()
+				.into_iter()
+				.any(|(key, value)| key == condition.key && condition.matches(value))
+		})
+	}
+
+	/// The attributes, as composite keys and values, that the conditions require verbatim.
+	pub fn exact_matches(&self) -> impl Iterator<Item = (&str, &str)> {
+		self.conditions.iter().filter_map(|condition| match &condition.op {
+			Operator::Eq(Operand::String(value)) => Some((condition.key.as_str(), value.as_str())),
+			_ => None,
+		})
+	}
+
+	/// The inclusive range of integers the conditions on `key` allow.
+	pub fn range(&self, key: &str) -> (u64, u64) {
+		self.conditions.iter().filter(|condition| condition.key == key).fold(
+			(u64::MIN, u64::MAX),
+			|(lower, upper), condition| {
+				let bound = |operand: &Operand| match operand {
+					Operand::Number(n) => n.parse::<u64>().ok(),
+					Operand::String(_) => None,
+				};
+				match &condition.op {
+					Operator::Eq(operand) => match bound(operand) {
+						Some(n) => (lower.max(n), upper.min(n)),
+						None => (lower, upper),
+					},
+					Operator::Lt(operand) => match bound(operand) {
+						Some(n) => (lower, upper.min(n.saturating_sub(1))),
+						None => (lower, upper),
+					},
+					Operator::Le(operand) =>
+						(lower, bound(operand).map_or(upper, |n| upper.min(n))),
+					Operator::Gt(operand) => match bound(operand) {
+						Some(n) => (lower.max(n.saturating_add(1)), upper),
+						None => (lower, upper),
+					},
+					Operator::Ge(operand) =>
+						(bound(operand).map_or(lower, |n| lower.max(n)), upper),
+					Operator::Contains(_) | Operator::Exists => (lower, upper),
+				}
+			},
+		)
+	}
+}
+
+struct Parser<'a> {
+	input: &'a str,
+	position: usize,
+}
+
+impl<'a> Parser<'a> {
+	fn rest(&self) -> &'a str {
+		&self.input[self.position..]
+	}
+
+	fn is_empty(&self) -> bool {
+		self.rest().is_empty()
+	}
+
+	fn error(&self) -> ParseError {
+		ParseError { position: self.position }
+	}
+
+	fn skip_whitespace(&mut self) {
+		let rest = self.rest();
+		self.position += rest.len() - rest.trim_start().len();
+	}
+
+	/// Consumes the longest prefix whose chars satisfy `f`.
+	fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+		let rest = self.rest();
+		let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+		self.position += len;
+		&rest[..len]
+	}
+
+	fn keyword(&mut self, keyword: &str) -> Result<(), ParseError> {
+		self.skip_whitespace();
+		match self.rest().strip_prefix(keyword) {
+			Some(after) if !after.starts_with(|c: char| c.is_ascii_alphanumeric()) => {
+				self.position += keyword.len();
+				Ok(())
+			},
+			_ => Err(self.error()),
+		}
+	}
+
+	fn condition(&mut self) -> Result<Condition, ParseError> {
+		self.skip_whitespace();
+		let key = self.take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | '-' | '/'));
+		if key.is_empty() {
+			return Err(self.error());
+		}
+		let key = key.to_string();
+
+		self.skip_whitespace();
+		let rest = self.rest();
+		let op = if rest.starts_with("<=") {
+			self.position += 2;
+			Operator::Le(self.operand()?)
+		} else if rest.starts_with(">=") {
+			self.position += 2;
+			Operator::Ge(self.operand()?)
+		} else if rest.starts_with('=') {
+			self.position += 1;
+			Operator::Eq(self.operand()?)
+		} else if rest.starts_with('<') {
+			self.position += 1;
+			Operator::Lt(self.operand()?)
+		} else if rest.starts_with('>') {
+			self.position += 1;
+			Operator::Gt(self.operand()?)
+		} else if self.keyword("CONTAINS").is_ok() {
+			match self.operand()? {
+				Operand::String(s) => Operator::Contains(s),
+				Operand::Number(_) => return Err(self.error()),
+			}
+		} else if self.keyword("EXISTS").is_ok() {
+			Operator::Exists
+		} else {
+			return Err(self.error());
+		};
+
+		Ok(Condition { key, op })
+	}
+
+	fn operand(&mut self) -> Result<Operand, ParseError> {
+		self.skip_whitespace();
+		if let Some(quoted) = self.rest().strip_prefix('\'') {
+			let len = quoted.find('\'').ok_or_else(|| self.error())?;
+			self.position += len + 2;
+			return Ok(Operand::String(quoted[..len].to_string()));
+		}
+
+		let start = self.position;
+		self.take_while(|c| c == '-');
+		let integer = self.take_while(|c| c.is_ascii_digit());
+		if integer.is_empty() || self.position - start > integer.len() + 1 {
+			return Err(ParseError { position: start });
+		}
+		if self.rest().starts_with('.') {
+			self.position += 1;
+			if self.take_while(|c| c.is_ascii_digit()).is_empty() {
+				return Err(self.error());
+			}
+		}
+		Ok(Operand::Number(self.input[start..self.position].to_string()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Condition, EventQuery, Operand, Operator, ParseError};
+	use alloc::{vec, vec::Vec};
+
+	#[test]
+	fn parse_test() {
+		let query = EventQuery::parse(
+			"transfer.recipient='cosmos1abc' AND tx.height>100 AND message.module CONTAINS 'ban' \
+			 AND fee.amount<=1.5 AND message.sender EXISTS",
+		)
+		.unwrap();
+		assert_eq!(
+			query.conditions,
+			vec![
+				Condition {
+					key: "transfer.recipient".into(),
+					op: Operator::Eq(Operand::String("cosmos1abc".into())),
+				},
+				Condition {
+					key: "tx.height".into(),
+					op: Operator::Gt(Operand::Number("100".into()))
+				},
+				Condition { key: "message.module".into(), op: Operator::Contains("ban".into()) },
+				Condition {
+					key: "fee.amount".into(),
+					op: Operator::Le(Operand::Number("1.5".into()))
+				},
+				Condition { key: "message.sender".into(), op: Operator::Exists },
+			]
+		);
+
+		assert_eq!(EventQuery::parse("").unwrap(), EventQuery::default());
+		assert_eq!(EventQuery::parse("tx.height=").unwrap_err(), ParseError { position: 10 });
+		assert_eq!(
+			EventQuery::parse("a.b='c' OR d.e='f'").unwrap_err(),
+			ParseError { position: 8 }
+		);
+		assert_eq!(EventQuery::parse("a.b='c").unwrap_err(), ParseError { position: 4 });
+		assert!(EventQuery::parse("a.b EXISTSX").is_err());
+	}
+
+	#[test]
+	fn matches_test() {
+		let attributes = [
+			("tx.height", "120"),
+			("transfer.recipient", "cosmos1abc"),
+			("transfer.amount", "100stake"),
+			("transfer.recipient", "cosmos1def"),
+		];
+
+		let matches = |query: &str| EventQuery::parse(query).unwrap().matches(attributes);
+		assert!(matches("transfer.recipient='cosmos1def' AND tx.height>100"));
+		assert!(matches("tx.height>=120 AND tx.height<=120 AND tx.height=120"));
+		assert!(matches("tx.height>99.5"));
+		assert!(matches("transfer.amount CONTAINS 'stake' AND transfer.recipient EXISTS"));
+		assert!(matches(""));
+		assert!(!matches("transfer.recipient='cosmos1ghi'"));
+		assert!(!matches("tx.height<120"));
+		assert!(!matches("transfer.amount>10"));
+		assert!(!matches("transfer.sender EXISTS"));
+	}
+
+	#[test]
+	fn exact_matches_test() {
+		let query = EventQuery::parse(
+			"transfer.recipient='cosmos1abc' AND tx.height=5 AND message.sender EXISTS \
+			 AND message.action='send'",
+		)
+		.unwrap();
+		assert_eq!(
+			query.exact_matches().collect::<Vec<_>>(),
+			vec![("transfer.recipient", "cosmos1abc"), ("message.action", "send")]
+		);
+		assert_eq!(EventQuery::parse("tx.height>5").unwrap().exact_matches().count(), 0);
+	}
+
+	#[test]
+	fn range_test() {
+		let range = |query: &str| EventQuery::parse(query).unwrap().range("tx.height");
+		assert_eq!(range("tx.height>100 AND tx.height<=200"), (101, 200));
+		assert_eq!(range("tx.height>=100 AND tx.height<200"), (100, 199));
+		assert_eq!(range("tx.height=5 AND tx.height>3"), (5, 5));
+		assert_eq!(range("transfer.recipient='cosmos1abc'"), (0, u64::MAX));
+	}
+}
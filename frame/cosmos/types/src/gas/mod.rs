@@ -17,6 +17,12 @@
 
 pub mod traits;
 
+use alloc::{string::String, vec::Vec};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use traits::GasMeter as _;
+
 pub type Gas = u64;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -48,7 +54,6 @@ impl traits::GasMeter for BasicGasMeter {
 		self.limit
 	}
 
-	// TODO: Handle or remove descriptor
 	fn consume_gas(&mut self, amount: Gas, _descriptor: &str) -> Result<Gas, Error> {
 		let consumed = self.consumed.checked_add(amount).ok_or(Error::GasOverflow)?;
 		if consumed > self.limit {
@@ -59,3 +64,71 @@ impl traits::GasMeter for BasicGasMeter {
 		Ok(self.consumed)
 	}
 }
+
+/// Gas consumed for the reason given by `descriptor`.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, Serialize, Deserialize)]
+pub struct GasRecord {
+	pub descriptor: String,
+	pub amount: Gas,
+}
+
+/// A gas meter that records every consumption along with its descriptor.
+#[derive(Clone, Debug)]
+pub struct TracingGasMeter {
+	pub meter: BasicGasMeter,
+	pub records: Vec<GasRecord>,
+}
+
+impl TracingGasMeter {
+	pub fn records(&self) -> &[GasRecord] {
+		&self.records
+	}
+}
+
+impl traits::GasMeter for TracingGasMeter {
+	fn new(limit: Gas) -> Self {
+		Self { meter: BasicGasMeter::new(limit), records: Vec::new() }
+	}
+
+	fn consumed_gas(&self) -> Gas {
+		self.meter.consumed_gas()
+	}
+
+	fn gas_remaining(&self) -> Gas {
+		self.meter.gas_remaining()
+	}
+
+	fn limit(&self) -> Gas {
+		self.meter.limit()
+	}
+
+	fn consume_gas(&mut self, amount: Gas, descriptor: &str) -> Result<Gas, Error> {
+		let consumed = self.meter.consume_gas(amount, descriptor)?;
+		self.records.push(GasRecord { descriptor: descriptor.into(), amount });
+
+		Ok(consumed)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{traits::GasMeter, Error, GasRecord, TracingGasMeter};
+
+	#[test]
+	fn tracing_gas_meter_test() {
+		let mut meter = TracingGasMeter::new(100);
+
+		assert_eq!(meter.consume_gas(30, "ReadFlat"), Ok(30));
+		assert_eq!(meter.consume_gas(50, "WriteFlat"), Ok(80));
+		assert_eq!(meter.consume_gas(30, "ReadFlat"), Err(Error::OutOfGas));
+
+		assert_eq!(meter.consumed_gas(), 80);
+		assert_eq!(
+			meter.records(),
+			&[
+				GasRecord { descriptor: "ReadFlat".into(), amount: 30 },
+				GasRecord { descriptor: "WriteFlat".into(), amount: 50 },
+			]
+		);
+	}
+}
@@ -18,21 +18,22 @@
 use cosmos_sdk_proto::cosmos::tx::v1beta1::Tx;
 use sp_runtime::transaction_validity::{TransactionValidity, ValidTransaction};
 
-pub trait AnteDecorator {
-	fn ante_handle(tx: &Tx, simulate: bool) -> TransactionValidity;
+pub trait AnteDecorator<Context> {
+	fn ante_handle(ctx: &mut Context, tx: &Tx, simulate: bool) -> TransactionValidity;
 }
 
-impl AnteDecorator for () {
-	fn ante_handle(_tx: &Tx, _simulate: bool) -> TransactionValidity {
+impl<Context> AnteDecorator<Context> for () {
+	fn ante_handle(_ctx: &mut Context, _tx: &Tx, _simulate: bool) -> TransactionValidity {
 		Ok(ValidTransaction::default())
 	}
 }
 
 #[impl_trait_for_tuples::impl_for_tuples(1, 12)]
-impl AnteDecorator for Tuple {
-	fn ante_handle(tx: &Tx, simulate: bool) -> TransactionValidity {
+#[tuple_types_custom_trait_bound(AnteDecorator<Context>)]
+impl<Context> AnteDecorator<Context> for Tuple {
+	fn ante_handle(ctx: &mut Context, tx: &Tx, simulate: bool) -> TransactionValidity {
 		let valid = ValidTransaction::default();
-		for_tuples!( #( let valid = valid.combine_with(Tuple::ante_handle(tx, simulate)?); )* );
+		for_tuples!( #( let valid = valid.combine_with(Tuple::ante_handle(ctx, tx, simulate)?); )* );
 		Ok(valid)
 	}
 }
@@ -28,4 +28,6 @@ pub mod gas;
 pub mod handler;
 pub mod macros;
 pub mod msgservice;
+pub mod query;
 pub mod tx_msgs;
+pub mod tx_result;
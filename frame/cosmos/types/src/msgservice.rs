@@ -15,7 +15,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::errors::CosmosError;
+use crate::{context, errors::CosmosError};
 use alloc::boxed::Box;
 use cosmos_sdk_proto::Any;
 
@@ -23,6 +23,10 @@ pub trait MsgHandler<Context> {
 	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError>;
 }
 
-pub trait MsgServiceRouter<Context> {
-	fn route(msg: &Any) -> Option<Box<dyn MsgHandler<Context>>>;
+/// Routes messages to their handlers, which run in whatever context they are given, such as one
+/// tracing gas for a simulation.
+pub trait MsgServiceRouter {
+	fn route<Context>(msg: &Any) -> Option<Box<dyn MsgHandler<Context>>>
+	where
+		Context: context::traits::Context;
 }
@@ -0,0 +1,169 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::{boxed::Box, vec::Vec};
+use cosmos_sdk_proto::cosmos::base::query::v1beta1 as proto;
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+
+/// The page size used when a request does not set a limit.
+pub const DEFAULT_LIMIT: u64 = 100;
+
+/// Selects a page of a query result, either by the key to start from or by an offset.
+#[derive(
+	Clone, Debug, Default, Eq, PartialEq, Decode, Encode, TypeInfo, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct PageRequest {
+	pub key: Option<Vec<u8>>,
+	pub offset: u64,
+	pub limit: u64,
+	pub count_total: bool,
+}
+
+#[derive(
+	Clone, Debug, Default, Eq, PartialEq, Decode, Encode, TypeInfo, Serialize, Deserialize,
+)]
+pub struct PageResponse {
+	/// The key to request the next page with, if there is one.
+	pub next_key: Option<Vec<u8>>,
+	/// The total number of results, set only if `count_total` was requested with an offset.
+	pub total: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Decode, Encode, TypeInfo)]
+pub enum QueryError {
+	InvalidAddress,
+	InvalidRequest,
+	NotFound,
+	/// No handler serves the query path.
+	UnknownRequest,
+}
+
+/// Handles the requests to a gRPC query method.
+pub trait QueryHandler {
+	/// Answers the protobuf-encoded `request` with a protobuf-encoded response.
+	fn handle(&self, request: &[u8]) -> Result<Vec<u8>, QueryError>;
+}
+
+pub trait QueryRouter {
+	/// The handler of the gRPC method at `path`, such as `/cosmos.bank.v1beta1.Query/Balance`.
+	fn route(path: &str) -> Option<Box<dyn QueryHandler>>;
+}
+
+impl TryFrom<proto::PageRequest> for PageRequest {
+	type Error = QueryError;
+
+	/// Pages in reverse order are not supported.
+	fn try_from(request: proto::PageRequest) -> Result<Self, Self::Error> {
+		if request.reverse {
+			return Err(QueryError::InvalidRequest);
+		}
+
+		Ok(Self {
+			key: (!request.key.is_empty()).then_some(request.key),
+			offset: request.offset,
+			limit: request.limit,
+			count_total: request.count_total,
+		})
+	}
+}
+
+impl From<PageResponse> for proto::PageResponse {
+	fn from(response: PageResponse) -> Self {
+		Self { next_key: response.next_key.unwrap_or_default(), total: response.total }
+	}
+}
+
+/// Returns the page of `items` selected by `pagination`. `items` must be sorted by key.
+pub fn paginate<K, V>(
+	items: Vec<(K, V)>,
+	pagination: Option<PageRequest>,
+) -> Result<(Vec<V>, PageResponse), QueryError>
+where
+	K: AsRef<[u8]>,
+{
+	let PageRequest { key, offset, limit, count_total } = pagination.unwrap_or_default();
+	if key.is_some() && offset > 0 {
+		return Err(QueryError::InvalidRequest);
+	}
+	let limit = if limit == 0 { DEFAULT_LIMIT } else { limit };
+	let limit = usize::try_from(limit).unwrap_or(usize::MAX);
+
+	let total = items.len() as u64;
+	let start = match &key {
+		Some(key) => items
+			.iter()
+			.position(|(k, _)| k.as_ref() >= key.as_slice())
+			.unwrap_or(items.len()),
+		None => usize::try_from(offset).unwrap_or(usize::MAX),
+	};
+
+	let mut rest = items.into_iter().skip(start);
+	let page = rest.by_ref().take(limit).map(|(_, v)| v).collect();
+	let next_key = rest.next().map(|(k, _)| k.as_ref().to_vec());
+	let total = if count_total && key.is_none() { total } else { 0 };
+
+	Ok((page, PageResponse { next_key, total }))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::vec;
+
+	#[test]
+	fn paginate_test() {
+		let items = vec![("a", 1), ("b", 2), ("c", 3)];
+
+		let (page, res) = paginate(items.clone(), None).unwrap();
+		assert_eq!(page, vec![1, 2, 3]);
+		assert_eq!(res, PageResponse { next_key: None, total: 0 });
+
+		let req = PageRequest { limit: 2, count_total: true, ..Default::default() };
+		let (page, res) = paginate(items.clone(), Some(req)).unwrap();
+		assert_eq!(page, vec![1, 2]);
+		assert_eq!(res, PageResponse { next_key: Some(b"c".to_vec()), total: 3 });
+
+		let req = PageRequest { key: res.next_key, limit: 2, ..Default::default() };
+		let (page, res) = paginate(items.clone(), Some(req)).unwrap();
+		assert_eq!(page, vec![3]);
+		assert_eq!(res, PageResponse { next_key: None, total: 0 });
+
+		let req = PageRequest { offset: 5, ..Default::default() };
+		assert_eq!(paginate(items.clone(), Some(req)).unwrap().0, Vec::<i32>::new());
+
+		let req = PageRequest { key: Some(b"b".to_vec()), offset: 1, ..Default::default() };
+		assert_eq!(paginate(items, Some(req)), Err(QueryError::InvalidRequest));
+	}
+
+	#[test]
+	fn page_request_from_proto_test() {
+		let req = proto::PageRequest { limit: 2, count_total: true, ..Default::default() };
+		assert_eq!(
+			PageRequest::try_from(req),
+			Ok(PageRequest { limit: 2, count_total: true, ..Default::default() })
+		);
+
+		let req = proto::PageRequest { key: b"b".to_vec(), ..Default::default() };
+		assert_eq!(PageRequest::try_from(req).unwrap().key, Some(b"b".to_vec()));
+
+		let req = proto::PageRequest { reverse: true, ..Default::default() };
+		assert_eq!(PageRequest::try_from(req), Err(QueryError::InvalidRequest));
+	}
+}
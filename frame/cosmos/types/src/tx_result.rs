@@ -0,0 +1,113 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{events::CosmosEvents, gas::Gas};
+use alloc::{
+	format,
+	string::{String, ToString},
+	vec,
+	vec::Vec,
+};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+
+/// Prefix of the offchain index keys of the blocks that include Cosmos txs.
+pub const TX_KEY_PREFIX: &[u8] = b"cosmos/tx_block/";
+
+/// Offchain index key of the block that last included the tx whose SHA-256 hash is `hash`,
+/// as its height and extrinsics root.
+pub fn tx_key(hash: &[u8; 32]) -> Vec<u8> {
+	[TX_KEY_PREFIX, hash].concat()
+}
+
+/// Prefix of the offchain index keys of the tx results of each block.
+pub const BLOCK_TXS_KEY_PREFIX: &[u8] = b"cosmos/block_txs/";
+
+/// Offchain index key of the results of the Cosmos txs in the block at `height`, in order.
+///
+/// The runtime does not know the hash of the block it builds, so blocks at the same height
+/// are told apart by their extrinsics root instead. Siblings only share it if they apply the
+/// same extrinsics, and with them the same results.
+pub fn block_txs_key(height: u64, extrinsics_root: &[u8]) -> Vec<u8> {
+	[BLOCK_TXS_KEY_PREFIX, &height.to_be_bytes(), extrinsics_root].concat()
+}
+
+/// The outcome of a Cosmos tx included in a block, in the manner of `TxResponse`.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, Serialize, Deserialize)]
+pub struct TxResult {
+	/// SHA-256 hash of the tx bytes.
+	pub hash: [u8; 32],
+	pub height: u64,
+	/// Index of the tx among the extrinsics of its block.
+	pub index: u32,
+	/// Both `codespace` and `code` are zero if the tx succeeded.
+	pub codespace: u8,
+	pub code: u8,
+	pub gas_wanted: Gas,
+	pub gas_used: Gas,
+	/// Events of the ante handlers, followed by those of the msgs if the tx succeeded.
+	pub events: CosmosEvents,
+	pub log: String,
+}
+
+impl TxResult {
+	pub fn is_ok(&self) -> bool {
+		self.codespace == 0 && self.code == 0
+	}
+
+	/// The attributes of the events of the tx by composite key, `<event type>.<attribute key>`,
+	/// along with `tx.hash` and `tx.height`.
+	pub fn attributes(&self) -> Vec<(String, String)> {
+		let mut attributes = vec![
+			("tx.hash".to_string(), self.hash.iter().map(|b| format!("{:02X}", b)).collect()),
+			("tx.height".to_string(), self.height.to_string()),
+		];
+		attributes.extend(self.events.iter().flat_map(|event| {
+			let r#type = String::from_utf8_lossy(&event.r#type);
+			event.attributes.iter().map(move |attribute| {
+				(
+					format!("{}.{}", r#type, String::from_utf8_lossy(&attribute.key)),
+					String::from_utf8_lossy(&attribute.value).into_owned(),
+				)
+			})
+		}));
+		attributes
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{block_txs_key, tx_key, BLOCK_TXS_KEY_PREFIX, TX_KEY_PREFIX};
+
+	#[test]
+	fn tx_key_test() {
+		let key = tx_key(&[0xab; 32]);
+		assert_eq!(key.len(), TX_KEY_PREFIX.len() + 32);
+		assert!(key.starts_with(TX_KEY_PREFIX));
+		assert_eq!(key[TX_KEY_PREFIX.len()..], [0xab; 32]);
+	}
+
+	#[test]
+	fn block_txs_key_test() {
+		let key = block_txs_key(1, &[0xcd; 32]);
+		assert!(key.starts_with(BLOCK_TXS_KEY_PREFIX));
+		assert_eq!(key[BLOCK_TXS_KEY_PREFIX.len()..][..8], 1u64.to_be_bytes());
+		assert_eq!(key[BLOCK_TXS_KEY_PREFIX.len() + 8..], [0xcd; 32]);
+		assert_ne!(key, block_txs_key(1, &[0xef; 32]));
+	}
+}
@@ -17,6 +17,7 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use alloc::{
+	format,
 	string::{String, ToString},
 	vec::Vec,
 };
@@ -57,3 +58,53 @@ pub trait LegacyMsg {
 		serde_json::json!({ "type": Self::AMINO_NAME.to_string(), "value": serde_json::to_value(self).unwrap() })
 	}
 }
+
+/// Seconds from the Unix epoch to `0001-01-01T00:00:00Z`, Go's zero time.
+pub const ZERO_TIME_SECONDS: i64 = -62_135_596_800;
+
+/// Formats a Unix timestamp as RFC 3339 the way Go's `time.Time` marshals to JSON.
+pub fn format_timestamp(seconds: i64, nanos: i32) -> String {
+	let days = seconds.div_euclid(86_400);
+	let secs = seconds.rem_euclid(86_400);
+
+	// Converts days since the epoch to a proleptic Gregorian date.
+	let z = days + 719_468;
+	let era = z.div_euclid(146_097);
+	let doe = z.rem_euclid(146_097);
+	let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+	let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+	let mp = (5 * doy + 2) / 153;
+	let day = doy - (153 * mp + 2) / 5 + 1;
+	let month = if mp < 10 { mp + 3 } else { mp - 9 };
+	let year = yoe + era * 400 + i64::from(month <= 2);
+
+	let mut timestamp = format!(
+		"{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+		year,
+		month,
+		day,
+		secs / 3_600,
+		secs % 3_600 / 60,
+		secs % 60
+	);
+	if nanos > 0 {
+		let fraction = format!("{:09}", nanos);
+		timestamp.push('.');
+		timestamp.push_str(fraction.trim_end_matches('0'));
+	}
+	timestamp.push('Z');
+
+	timestamp
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{format_timestamp, ZERO_TIME_SECONDS};
+
+	#[test]
+	fn format_timestamp_test() {
+		assert_eq!(format_timestamp(0, 0), "1970-01-01T00:00:00Z");
+		assert_eq!(format_timestamp(1_709_251_199, 500_000_000), "2024-02-29T23:59:59.5Z");
+		assert_eq!(format_timestamp(ZERO_TIME_SECONDS, 0), "0001-01-01T00:00:00Z");
+	}
+}
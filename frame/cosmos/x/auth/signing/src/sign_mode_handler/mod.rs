@@ -24,7 +24,7 @@ use alloc::{
 };
 use cosmos_sdk_proto::{
 	cosmos::{
-		bank,
+		authz, bank, feegrant,
 		tx::{
 			signing::v1beta1::SignMode,
 			v1beta1::{
@@ -39,13 +39,19 @@ use cosmos_sdk_proto::{
 };
 use pallet_cosmos_types::any_match;
 use pallet_cosmos_x_auth_migrations::legacytx::stdsign::{LegacyMsg, StdSignDoc};
-use pallet_cosmos_x_bank_types::msgs::msg_send::MsgSend;
+use pallet_cosmos_x_authz_types::msgs::{
+	msg_exec::MsgExec, msg_grant::MsgGrant, msg_revoke::MsgRevoke,
+};
+use pallet_cosmos_x_bank_types::msgs::{msg_multi_send::MsgMultiSend, msg_send::MsgSend};
+use pallet_cosmos_x_feegrant_types::msgs::{
+	msg_grant_allowance::MsgGrantAllowance, msg_revoke_allowance::MsgRevokeAllowance,
+};
 use pallet_cosmos_x_wasm_types::tx::{
 	msg_execute_contract::MsgExecuteContract, msg_instantiate_contract2::MsgInstantiateContract2,
 	msg_migrate_contract::MsgMigrateContract, msg_store_code::MsgStoreCode,
 	msg_update_admin::MsgUpdateAdmin,
 };
-use serde_json::Value;
+use serde_json::{json, Value};
 
 #[derive(Clone)]
 pub struct SignerData {
@@ -93,21 +99,11 @@ impl traits::SignModeHandler for SignModeHandler {
 				},
 				SIGN_MODE_LEGACY_AMINO_JSON => {
 					let body = tx.body.as_ref().ok_or(SignModeHandlerError::EmptyTxBody)?;
-					let mut msgs = Vec::<Value>::new();
-					for msg in body.messages.iter() {
-						let legacy_msg = any_match!(
-							msg, {
-								bank::v1beta1::MsgSend => MsgSend::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
-								wasm::v1::MsgStoreCode => MsgStoreCode::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
-								wasm::v1::MsgInstantiateContract2 => MsgInstantiateContract2::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
-								wasm::v1::MsgExecuteContract => MsgExecuteContract::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
-								wasm::v1::MsgMigrateContract => MsgMigrateContract::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
-								wasm::v1::MsgUpdateAdmin => MsgUpdateAdmin::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
-							},
-							Err(SignModeHandlerError::InvalidMsg))?;
-
-						msgs.push(legacy_msg);
-					}
+					let msgs = body
+						.messages
+						.iter()
+						.map(Self::legacy_msg)
+						.collect::<Result<Vec<Value>, _>>()?;
 					let fee = tx
 						.auth_info
 						.as_ref()
@@ -134,6 +130,35 @@ impl traits::SignModeHandler for SignModeHandler {
 	}
 }
 
+impl SignModeHandler {
+	fn legacy_msg(msg: &Any) -> Result<Value, SignModeHandlerError> {
+		any_match!(
+			msg, {
+				authz::v1beta1::MsgExec => MsgExec::try_from(msg).map_err(|_| SignModeHandlerError::InvalidMsg).and_then(|msg_exec| {
+					let msgs = msg_exec
+						.msgs
+						.iter()
+						.map(Self::legacy_msg)
+						.collect::<Result<Vec<Value>, _>>()?;
+					Ok(json!({ "type": MsgExec::AMINO_NAME, "value": { "grantee": msg_exec.grantee, "msgs": msgs } }))
+				}),
+				authz::v1beta1::MsgGrant => MsgGrant::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
+				authz::v1beta1::MsgRevoke => MsgRevoke::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
+				bank::v1beta1::MsgSend => MsgSend::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
+				bank::v1beta1::MsgMultiSend => MsgMultiSend::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
+				feegrant::v1beta1::MsgGrantAllowance => MsgGrantAllowance::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
+				feegrant::v1beta1::MsgRevokeAllowance => MsgRevokeAllowance::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
+				wasm::v1::MsgStoreCode => MsgStoreCode::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
+				wasm::v1::MsgInstantiateContract2 => MsgInstantiateContract2::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
+				wasm::v1::MsgExecuteContract => MsgExecuteContract::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
+				wasm::v1::MsgMigrateContract => MsgMigrateContract::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
+				wasm::v1::MsgUpdateAdmin => MsgUpdateAdmin::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
+			},
+			Err(SignModeHandlerError::InvalidMsg)
+		)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::sign_mode_handler::{traits::SignModeHandler as _, SignModeHandler, SignerData};
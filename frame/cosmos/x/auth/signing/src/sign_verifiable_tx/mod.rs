@@ -20,11 +20,18 @@ pub mod traits;
 
 use alloc::{string::String, vec::Vec};
 use cosmos_sdk_proto::{
-	cosmos::{bank, tx::v1beta1::Tx},
+	cosmos::{authz, bank, feegrant, tx::v1beta1::Tx},
 	cosmwasm::wasm,
+	Any,
 };
 use pallet_cosmos_types::{any_match, tx_msgs::Msg};
-use pallet_cosmos_x_bank_types::msgs::msg_send::MsgSend;
+use pallet_cosmos_x_authz_types::msgs::{
+	msg_exec::MsgExec, msg_grant::MsgGrant, msg_revoke::MsgRevoke,
+};
+use pallet_cosmos_x_bank_types::msgs::{msg_multi_send::MsgMultiSend, msg_send::MsgSend};
+use pallet_cosmos_x_feegrant_types::msgs::{
+	msg_grant_allowance::MsgGrantAllowance, msg_revoke_allowance::MsgRevokeAllowance,
+};
 use pallet_cosmos_x_wasm_types::tx::{
 	msg_execute_contract::MsgExecuteContract, msg_instantiate_contract2::MsgInstantiateContract2,
 	msg_migrate_contract::MsgMigrateContract, msg_store_code::MsgStoreCode,
@@ -47,17 +54,7 @@ impl traits::SigVerifiableTx for SigVerifiableTx {
 
 		let body = tx.body.as_ref().ok_or(SigVerifiableTxError::EmptyTxBody)?;
 		for msg in body.messages.iter() {
-			let msg_signers = any_match!(
-				msg, {
-					bank::v1beta1::MsgSend => MsgSend::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
-					wasm::v1::MsgStoreCode => MsgStoreCode::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
-					wasm::v1::MsgInstantiateContract2 => MsgInstantiateContract2::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
-					wasm::v1::MsgExecuteContract => MsgExecuteContract::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
-					wasm::v1::MsgMigrateContract => MsgMigrateContract::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
-					wasm::v1::MsgUpdateAdmin => MsgUpdateAdmin::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
-				},
-				Err(SigVerifiableTxError::InvalidMsg)
-			)?;
+			let msg_signers = Self::get_msg_signers(msg)?;
 
 			for msg_signer in msg_signers.iter() {
 				if !signers.contains(msg_signer) {
@@ -80,6 +77,34 @@ impl traits::SigVerifiableTx for SigVerifiableTx {
 		Ok(signers)
 	}
 
+	fn get_msg_signers(msg: &Any) -> Result<Vec<String>, SigVerifiableTxError> {
+		any_match!(
+			msg, {
+				authz::v1beta1::MsgExec => MsgExec::try_from(msg).map_err(|_| SigVerifiableTxError::InvalidMsg).and_then(|msg_exec| {
+					if msg_exec.msgs.is_empty() {
+						return Err(SigVerifiableTxError::InvalidMsg);
+					}
+					for inner in msg_exec.msgs.iter() {
+						Self::get_msg_signers(inner)?;
+					}
+					Ok(msg_exec.get_signers())
+				}),
+				authz::v1beta1::MsgGrant => MsgGrant::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				authz::v1beta1::MsgRevoke => MsgRevoke::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				bank::v1beta1::MsgSend => MsgSend::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				bank::v1beta1::MsgMultiSend => MsgMultiSend::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				feegrant::v1beta1::MsgGrantAllowance => MsgGrantAllowance::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				feegrant::v1beta1::MsgRevokeAllowance => MsgRevokeAllowance::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				wasm::v1::MsgStoreCode => MsgStoreCode::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				wasm::v1::MsgInstantiateContract2 => MsgInstantiateContract2::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				wasm::v1::MsgExecuteContract => MsgExecuteContract::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				wasm::v1::MsgMigrateContract => MsgMigrateContract::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				wasm::v1::MsgUpdateAdmin => MsgUpdateAdmin::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+			},
+			Err(SigVerifiableTxError::InvalidMsg)
+		)
+	}
+
 	fn fee_payer(tx: &Tx) -> Result<String, SigVerifiableTxError> {
 		let fee = tx
 			.auth_info
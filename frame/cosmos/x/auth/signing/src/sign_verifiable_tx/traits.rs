@@ -18,10 +18,11 @@
 
 use super::SigVerifiableTxError;
 use alloc::{string::String, vec::Vec};
-use cosmos_sdk_proto::cosmos::tx::v1beta1::Tx;
+use cosmos_sdk_proto::{cosmos::tx::v1beta1::Tx, Any};
 
 pub trait SigVerifiableTx {
 	fn get_signers(tx: &Tx) -> Result<Vec<String>, SigVerifiableTxError>;
+	fn get_msg_signers(msg: &Any) -> Result<Vec<String>, SigVerifiableTxError>;
 	fn fee_payer(tx: &Tx) -> Result<String, SigVerifiableTxError>;
 	fn sequence(tx: &Tx) -> Result<u64, SigVerifiableTxError>;
 }
@@ -17,8 +17,12 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use core::marker::PhantomData;
-use cosmos_sdk_proto::cosmos::tx::v1beta1::Tx;
-use pallet_cosmos_types::handler::AnteDecorator;
+use cosmos_sdk_proto::{cosmos::tx::v1beta1::Tx, prost::Message};
+use pallet_cosmos_types::{
+	context,
+	gas::{traits::GasMeter, Gas},
+	handler::AnteDecorator,
+};
 use sp_runtime::{
 	traits::Get,
 	transaction_validity::{InvalidTransaction, TransactionValidity, ValidTransaction},
@@ -27,11 +31,11 @@ use sp_runtime::{
 
 pub struct ValidateBasicDecorator<T>(PhantomData<T>);
 
-impl<T> AnteDecorator for ValidateBasicDecorator<T>
+impl<T, Context> AnteDecorator<Context> for ValidateBasicDecorator<T>
 where
 	T: frame_system::Config,
 {
-	fn ante_handle(tx: &Tx, _simulate: bool) -> TransactionValidity {
+	fn ante_handle(_ctx: &mut Context, tx: &Tx, _simulate: bool) -> TransactionValidity {
 		if tx.signatures.is_empty() {
 			return Err(InvalidTransaction::BadProof.into());
 		}
@@ -47,11 +51,11 @@ where
 
 pub struct TxTimeoutHeightDecorator<T>(PhantomData<T>);
 
-impl<T> AnteDecorator for TxTimeoutHeightDecorator<T>
+impl<T, Context> AnteDecorator<Context> for TxTimeoutHeightDecorator<T>
 where
 	T: frame_system::Config,
 {
-	fn ante_handle(tx: &Tx, _simulate: bool) -> TransactionValidity {
+	fn ante_handle(_ctx: &mut Context, tx: &Tx, _simulate: bool) -> TransactionValidity {
 		let body = tx.body.as_ref().ok_or(InvalidTransaction::Call)?;
 
 		if body.timeout_height > 0 &&
@@ -67,11 +71,11 @@ where
 
 pub struct ValidateMemoDecorator<T>(PhantomData<T>);
 
-impl<T> AnteDecorator for ValidateMemoDecorator<T>
+impl<T, Context> AnteDecorator<Context> for ValidateMemoDecorator<T>
 where
 	T: pallet_cosmos::Config,
 {
-	fn ante_handle(tx: &Tx, _simulate: bool) -> TransactionValidity {
+	fn ante_handle(_ctx: &mut Context, tx: &Tx, _simulate: bool) -> TransactionValidity {
 		let body = tx.body.as_ref().ok_or(InvalidTransaction::Call)?;
 
 		if body.memo.len().saturated_into::<u64>() > T::MaxMemoCharacters::get() {
@@ -81,3 +85,31 @@ where
 		Ok(ValidTransaction::default())
 	}
 }
+
+/// Estimated size of a secp256k1 signature together with its public key, charged for each
+/// signature a simulated transaction leaves empty.
+const SIMULATED_SIGNATURE_SIZE: Gas = 64 + 33 + 6;
+
+pub struct ConsumeTxSizeGasDecorator<T>(PhantomData<T>);
+
+impl<T, Context> AnteDecorator<Context> for ConsumeTxSizeGasDecorator<T>
+where
+	T: pallet_cosmos::Config,
+	Context: context::traits::Context,
+{
+	fn ante_handle(ctx: &mut Context, tx: &Tx, simulate: bool) -> TransactionValidity {
+		let mut size = tx.encoded_len().saturated_into::<Gas>();
+
+		if simulate {
+			let unsigned = tx.signatures.iter().filter(|sig| sig.is_empty()).count();
+			size = size
+				.saturating_add(SIMULATED_SIGNATURE_SIZE.saturating_mul(unsigned.saturated_into()));
+		}
+
+		ctx.gas_meter()
+			.consume_gas(size.saturating_mul(T::TxSizeCostPerByte::get()), "txSize")
+			.map_err(|_| InvalidTransaction::ExhaustsResources)?;
+
+		Ok(ValidTransaction::default())
+	}
+}
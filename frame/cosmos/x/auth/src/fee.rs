@@ -16,7 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use alloc::vec;
+use alloc::{string::String, vec, vec::Vec};
 use core::marker::PhantomData;
 use cosmos_sdk_proto::cosmos::tx::v1beta1::{Fee, Tx};
 use frame_support::{
@@ -27,10 +27,11 @@ use frame_support::{
 		Currency, ExistenceRequirement, WithdrawReasons,
 	},
 };
-use pallet_cosmos::AddressMapping;
+use pallet_cosmos::{AddressMapping, FeeGrant};
 use pallet_cosmos_types::{
 	address::acc_address_from_bech32,
-	coin::amount_to_string,
+	coin::{amount_to_string, parse_dec_coins, DecCoin},
+	context,
 	events::{
 		CosmosEvent, EventAttribute, ATTRIBUTE_KEY_FEE, ATTRIBUTE_KEY_FEE_PAYER, EVENT_TYPE_TX,
 	},
@@ -40,17 +41,18 @@ use pallet_cosmos_x_auth_signing::sign_verifiable_tx::traits::SigVerifiableTx;
 use sp_core::{Get, H160};
 use sp_runtime::{
 	traits::{Convert, Zero},
-	transaction_validity::{TransactionValidity, ValidTransaction},
+	transaction_validity::{TransactionValidity, TransactionValidityError, ValidTransaction},
 	SaturatedConversion,
 };
 
 pub struct DeductFeeDecorator<T>(PhantomData<T>);
 
-impl<T> AnteDecorator for DeductFeeDecorator<T>
+impl<T, Context> AnteDecorator<Context> for DeductFeeDecorator<T>
 where
 	T: frame_system::Config + pallet_cosmos::Config,
+	Context: context::traits::Context,
 {
-	fn ante_handle(tx: &Tx, simulate: bool) -> TransactionValidity {
+	fn ante_handle(ctx: &mut Context, tx: &Tx, simulate: bool) -> TransactionValidity {
 		let fee = tx
 			.auth_info
 			.as_ref()
@@ -64,7 +66,9 @@ where
 			return Err(InvalidTransaction::Call.into());
 		}
 
-		// TODO: Implements txFeeChecker
+		if ctx.is_check_tx() && !simulate {
+			Self::check_tx_fee_with_min_gas_prices(fee)?;
+		}
 
 		Self::check_deduct_fee(tx)?;
 
@@ -76,6 +80,40 @@ impl<T> DeductFeeDecorator<T>
 where
 	T: pallet_cosmos::Config,
 {
+	/// Node-local minimum gas prices take precedence over the chain-level ones.
+	fn min_gas_prices() -> Vec<DecCoin> {
+		hp_io::cosmos::min_gas_prices()
+			.and_then(|min_gas_prices| String::from_utf8(min_gas_prices).ok())
+			.and_then(|min_gas_prices| parse_dec_coins(&min_gas_prices).ok())
+			.unwrap_or_else(T::MinGasPrices::get)
+	}
+
+	/// Rejects transactions paying less than the minimum gas prices. Only applies to the
+	/// transaction pool, since the node-local prices must not affect block validity.
+	fn check_tx_fee_with_min_gas_prices(fee: &Fee) -> TransactionValidity {
+		let min_gas_prices = Self::min_gas_prices()
+			.into_iter()
+			.filter(|min_gas_price| !min_gas_price.amount.is_zero())
+			.collect::<Vec<_>>();
+		if min_gas_prices.is_empty() {
+			return Ok(ValidTransaction::default());
+		}
+
+		let sufficient = min_gas_prices.iter().any(|min_gas_price| {
+			let required = min_gas_price.required_amount(fee.gas_limit);
+			fee.amount.iter().any(|amt| {
+				amt.denom == min_gas_price.denom &&
+					amt.amount.parse::<u128>().is_ok_and(|amount| amount >= required) &&
+					!required.is_zero()
+			})
+		});
+		if !sufficient {
+			return Err(InvalidTransaction::Payment.into());
+		}
+
+		Ok(ValidTransaction::default())
+	}
+
 	fn check_deduct_fee(tx: &Tx) -> TransactionValidity {
 		let fee_payer = T::SigVerifiableTx::fee_payer(tx).map_err(|_| InvalidTransaction::Call)?;
 
@@ -85,45 +123,73 @@ where
 			.and_then(|auth_info| auth_info.fee.as_ref())
 			.ok_or(InvalidTransaction::Call)?;
 
-		// TODO: Fee granter not supported
-		if !fee.granter.is_empty() {
-			return Err(InvalidTransaction::Call.into());
-		}
+		let deduct_fees_from = if !fee.granter.is_empty() {
+			if fee.granter != fee_payer {
+				let body = tx.body.as_ref().ok_or(InvalidTransaction::Call)?;
+				T::FeeGrant::use_granted_fees(
+					&fee.granter,
+					&fee_payer,
+					&fee.amount,
+					&body.messages,
+				)
+				.map_err(|_| InvalidTransaction::Payment)?;
+			}
+
+			fee.granter.clone()
+		} else {
+			fee_payer
+		};
 
-		let (_hrp, address_raw) =
-			acc_address_from_bech32(&fee_payer).map_err(|_| InvalidTransaction::BadSigner)?;
+		let (_hrp, address_raw) = acc_address_from_bech32(&deduct_fees_from)
+			.map_err(|_| InvalidTransaction::BadSigner)?;
 		if address_raw.len() != 20 {
 			return Err(InvalidTransaction::BadSigner.into());
 		}
-		let deduct_fees_from = T::AddressMapping::into_account_id(H160::from_slice(&address_raw));
+		let deduct_fees_from_account =
+			T::AddressMapping::into_account_id(H160::from_slice(&address_raw));
+
+		let mut events = Vec::new();
 
-		// TODO: Check fee is zero
 		if !fee.amount.is_empty() {
-			Self::deduct_fees(&deduct_fees_from, fee)?;
+			let payouts = Self::deduct_fees(&deduct_fees_from_account, fee)?;
+			events.push(CosmosEvent::coin_spent(&deduct_fees_from, &fee.amount));
+			events.push(CosmosEvent::coin_received(
+				&pallet_cosmos::Pallet::<T>::fee_collector(),
+				&fee.amount,
+			));
+			events.extend(payouts);
 		}
 
-		pallet_cosmos::Pallet::<T>::deposit_event(pallet_cosmos::Event::AnteHandled(vec![
-			CosmosEvent {
-				r#type: EVENT_TYPE_TX.into(),
-				attributes: vec![
-					EventAttribute {
-						key: ATTRIBUTE_KEY_FEE.into(),
-						value: amount_to_string(&fee.amount).into(),
-					},
-					EventAttribute { key: ATTRIBUTE_KEY_FEE_PAYER.into(), value: fee_payer.into() },
-				],
-			},
-		]));
+		events.push(CosmosEvent {
+			r#type: EVENT_TYPE_TX.into(),
+			attributes: vec![
+				EventAttribute {
+					key: ATTRIBUTE_KEY_FEE.into(),
+					value: amount_to_string(&fee.amount).into(),
+				},
+				EventAttribute {
+					key: ATTRIBUTE_KEY_FEE_PAYER.into(),
+					value: deduct_fees_from.into(),
+				},
+			],
+		});
+		pallet_cosmos::Pallet::<T>::deposit_ante_events(events);
 
 		Ok(ValidTransaction::default())
 	}
 
-	fn deduct_fees(acc: &T::AccountId, fee: &Fee) -> TransactionValidity {
+	/// Withdraws `fee` from `acc`, returning the events of the fees paid out of the fee
+	/// collector.
+	fn deduct_fees(
+		acc: &T::AccountId,
+		fee: &Fee,
+	) -> Result<Vec<CosmosEvent>, TransactionValidityError> {
+		let mut payouts = Vec::new();
 		for amt in fee.amount.iter() {
 			let amount = amt.amount.parse::<u128>().map_err(|_| InvalidTransaction::Call)?;
 
 			if amt.denom == T::NativeDenom::get() {
-				let _imbalance = T::NativeAsset::withdraw(
+				let imbalance = T::NativeAsset::withdraw(
 					acc,
 					amount.saturated_into(),
 					WithdrawReasons::TRANSACTION_PAYMENT,
@@ -131,12 +197,12 @@ where
 				)
 				.map_err(|_| InvalidTransaction::Payment)?;
 
-			// TODO: Resolve imbalance
+				payouts.extend(pallet_cosmos::Pallet::<T>::charge_native_fee(imbalance));
 			} else {
 				let asset_id = T::AssetToDenom::convert(amt.denom.clone())
 					.map_err(|_| InvalidTransaction::Call)?;
 
-				let _imbalance = T::Assets::withdraw(
+				let credit = T::Assets::withdraw(
 					asset_id,
 					acc,
 					amount.saturated_into(),
@@ -146,10 +212,10 @@ where
 				)
 				.map_err(|_| InvalidTransaction::Payment)?;
 
-				// TODO: Resolve imbalance
+				payouts.extend(pallet_cosmos::Pallet::<T>::charge_asset_fee(&amt.denom, credit));
 			}
 		}
 
-		Ok(ValidTransaction::default())
+		Ok(payouts)
 	}
 }
@@ -24,15 +24,19 @@ extern crate alloc;
 pub mod basic;
 pub mod fee;
 pub mod msg;
+pub mod query;
 pub mod sigverify;
 
 pub type AnteDecorators<T> = (
 	basic::ValidateBasicDecorator<T>,
 	basic::TxTimeoutHeightDecorator<T>,
 	basic::ValidateMemoDecorator<T>,
+	basic::ConsumeTxSizeGasDecorator<T>,
 	sigverify::ValidateSigCountDecorator<T>,
 	msg::KnownMsgDecorator<T>,
+	sigverify::SigGasConsumeDecorator<T>,
 	sigverify::SigVerificationDecorator<T>,
+	sigverify::SetPubKeyDecorator<T>,
 	fee::DeductFeeDecorator<T>,
 	sigverify::IncrementSequenceDecorator<T>,
 );
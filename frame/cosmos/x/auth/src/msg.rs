@@ -23,11 +23,11 @@ use sp_runtime::transaction_validity::{InvalidTransaction, TransactionValidity,
 
 pub struct KnownMsgDecorator<T>(core::marker::PhantomData<T>);
 
-impl<T> AnteDecorator for KnownMsgDecorator<T>
+impl<T, Context> AnteDecorator<Context> for KnownMsgDecorator<T>
 where
 	T: pallet_cosmos::Config,
 {
-	fn ante_handle(tx: &Tx, _simulate: bool) -> TransactionValidity {
+	fn ante_handle(_ctx: &mut Context, tx: &Tx, _simulate: bool) -> TransactionValidity {
 		let body = tx.body.as_ref().ok_or(InvalidTransaction::Call)?;
 
 		for msg in body.messages.iter() {
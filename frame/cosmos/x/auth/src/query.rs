@@ -0,0 +1,81 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use alloc::{string::ToString, vec::Vec};
+use core::marker::PhantomData;
+use cosmos_sdk_proto::{
+	cosmos::auth::v1beta1::{BaseAccount, QueryAccountRequest, QueryAccountResponse},
+	prost::{Message, Name},
+	Any,
+};
+use pallet_cosmos::{AccountNumbers, AccountPubKeys, AddressMapping};
+use pallet_cosmos_types::{
+	address::acc_address_from_bech32,
+	query::{QueryError, QueryHandler},
+};
+use sp_core::H160;
+use sp_runtime::SaturatedConversion;
+
+/// The account of `address`. An address has an account once it has been assigned an account
+/// number, as accounts are numbered when they are first funded or sign.
+pub fn account<T: pallet_cosmos::Config>(address: &str) -> Result<BaseAccount, QueryError> {
+	let (_hrp, address_raw) =
+		acc_address_from_bech32(address).map_err(|_| QueryError::InvalidAddress)?;
+	if address_raw.len() != 20 {
+		return Err(QueryError::InvalidAddress);
+	}
+	let address_h160 = H160::from_slice(&address_raw);
+
+	let account_number =
+		T::AccountNumbers::account_number(&address_h160).ok_or(QueryError::NotFound)?;
+	let who = T::AddressMapping::into_account_id(address_h160);
+
+	Ok(BaseAccount {
+		address: address.to_string(),
+		pub_key: T::AccountPubKeys::pub_key(&address_h160),
+		account_number,
+		sequence: frame_system::Pallet::<T>::account_nonce(&who).saturated_into(),
+	})
+}
+
+pub struct QueryAccountHandler<T>(PhantomData<T>);
+
+impl<T> Default for QueryAccountHandler<T> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T> QueryHandler for QueryAccountHandler<T>
+where
+	T: pallet_cosmos::Config,
+{
+	fn handle(&self, request: &[u8]) -> Result<Vec<u8>, QueryError> {
+		let QueryAccountRequest { address } =
+			QueryAccountRequest::decode(request).map_err(|_| QueryError::InvalidRequest)?;
+		let account = account::<T>(&address)?;
+
+		Ok(QueryAccountResponse {
+			account: Some(Any {
+				type_url: BaseAccount::type_url(),
+				value: account.encode_to_vec(),
+			}),
+		}
+		.encode_to_vec())
+	}
+}
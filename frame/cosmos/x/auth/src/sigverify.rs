@@ -16,18 +16,30 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use alloc::string::ToString;
+use alloc::{string::ToString, vec::Vec};
 use core::marker::PhantomData;
 use cosmos_sdk_proto::{
 	cosmos::{
-		crypto::{multisig::LegacyAminoPubKey, secp256k1},
-		tx::v1beta1::{ModeInfo, SignerInfo, Tx},
+		crypto::{
+			multisig::{
+				v1beta1::{CompactBitArray, MultiSignature},
+				LegacyAminoPubKey,
+			},
+			secp256k1,
+		},
+		tx::v1beta1::{
+			mode_info::{Multi, Sum},
+			ModeInfo, SignerInfo, Tx,
+		},
 	},
 	prost::Message,
 	Any,
 };
-use pallet_cosmos::AddressMapping;
-use pallet_cosmos_types::{address::acc_address_from_bech32, any_match, handler::AnteDecorator};
+use pallet_cosmos::{AccountNumbers, AccountPubKeys, AddressMapping};
+use pallet_cosmos_types::{
+	address::acc_address_from_bech32, any_match, context, gas::traits::GasMeter,
+	handler::AnteDecorator,
+};
 use pallet_cosmos_x_auth_signing::{
 	sign_mode_handler::{traits::SignModeHandler, SignerData},
 	sign_verifiable_tx::traits::SigVerifiableTx,
@@ -43,11 +55,11 @@ use sp_runtime::{
 
 pub struct SigVerificationDecorator<T>(PhantomData<T>);
 
-impl<T> AnteDecorator for SigVerificationDecorator<T>
+impl<T, Context> AnteDecorator<Context> for SigVerificationDecorator<T>
 where
 	T: frame_system::Config + pallet_cosmos::Config,
 {
-	fn ante_handle(tx: &Tx, _simulate: bool) -> TransactionValidity {
+	fn ante_handle(_ctx: &mut Context, tx: &Tx, simulate: bool) -> TransactionValidity {
 		let signatures = &tx.signatures;
 		let signers =
 			T::SigVerifiableTx::get_signers(tx).map_err(|_| InvalidTransaction::BadSigner)?;
@@ -73,7 +85,8 @@ where
 				return Err(InvalidTransaction::BadSigner.into());
 			}
 
-			let who = T::AddressMapping::into_account_id(H160::from_slice(&signer_addr_raw));
+			let address = H160::from_slice(&signer_addr_raw);
+			let who = T::AddressMapping::into_account_id(address);
 			let sequence = frame_system::Pallet::<T>::account_nonce(&who).saturated_into();
 
 			if signer_info.sequence > sequence {
@@ -82,13 +95,19 @@ where
 				return Err(InvalidTransaction::Stale.into());
 			}
 
+			// Transactions are usually simulated before they are signed.
+			if simulate && sig.is_empty() {
+				continue;
+			}
+
 			let public_key =
 				signer_info.public_key.as_ref().ok_or(InvalidTransaction::BadSigner)?;
 			let chain_id = T::ChainId::get().to_string();
 			let signer_data = SignerData {
 				address: signer.clone(),
 				chain_id,
-				account_number: 0,
+				account_number: T::AccountNumbers::account_number(&address)
+					.ok_or(InvalidTransaction::BadSigner)?,
 				sequence: signer_info.sequence,
 				pub_key: public_key.clone(),
 			};
@@ -113,41 +132,195 @@ where
 		signature: &[u8],
 		tx: &Tx,
 	) -> Result<(), TransactionValidityError> {
-		any_match!(
-			public_key, {
-				secp256k1::PubKey => {
-					let public_key =
-						secp256k1::PubKey::decode(&mut &*public_key.value).map_err(|_| {
-							InvalidTransaction::BadSigner
-						})?;
-					let mut hasher = ripemd::Ripemd160::new();
-					hasher.update(sha2_256(&public_key.key));
-					let address = H160::from_slice(&hasher.finalize());
+		let address = pub_key_address(public_key).ok_or(InvalidTransaction::BadSigner)?;
 
-					let (_hrp, signer_addr_raw) =
-						acc_address_from_bech32(&signer_data.address).map_err(|_| {
-							InvalidTransaction::BadSigner
-						})?;
-					if signer_addr_raw.len() != 20 {
-						return Err(InvalidTransaction::BadSigner.into());
-					}
-					if  H160::from_slice(&signer_addr_raw) != address {
-						return Err(InvalidTransaction::BadSigner.into());
-					}
+		let (_hrp, signer_addr_raw) = acc_address_from_bech32(&signer_data.address)
+			.map_err(|_| InvalidTransaction::BadSigner)?;
+		if signer_addr_raw.len() != 20 {
+			return Err(InvalidTransaction::BadSigner.into());
+		}
+		if H160::from_slice(&signer_addr_raw) != address {
+			return Err(InvalidTransaction::BadSigner.into());
+		}
+
+		verify::<T::SignModeHandler>(public_key, signer_data, sign_mode, signature, tx)
+	}
+}
 
-					let sign_bytes = T::SignModeHandler::get_sign_bytes(sign_mode, signer_data, tx)
-						.map_err(|_| InvalidTransaction::Call)?;
+/// Verifies `signature` of `tx` by `public_key`, with the sign bytes `H` produces.
+fn verify<H: SignModeHandler>(
+	public_key: &Any,
+	signer_data: &SignerData,
+	sign_mode: &ModeInfo,
+	signature: &[u8],
+	tx: &Tx,
+) -> Result<(), TransactionValidityError> {
+	any_match!(
+		public_key, {
+			secp256k1::PubKey => {
+				let public_key =
+					secp256k1::PubKey::decode(&mut &*public_key.value).map_err(|_| {
+						InvalidTransaction::BadSigner
+					})?;
+				if !matches!(sign_mode.sum, Some(Sum::Single(_))) {
+					return Err(InvalidTransaction::BadProof.into());
+				}
 
-					if !ecdsa_verify(signature, &sign_bytes, &public_key.key) {
-						return Err(InvalidTransaction::BadProof.into());
-					}
+				let sign_bytes = H::get_sign_bytes(sign_mode, signer_data, tx)
+					.map_err(|_| InvalidTransaction::Call)?;
 
-					Ok(())
+				if !ecdsa_verify(signature, &sign_bytes, &public_key.key) {
+					return Err(InvalidTransaction::BadProof.into());
 				}
+
+				Ok(())
 			},
-			Err(InvalidTransaction::BadSigner.into())
-		)
+			LegacyAminoPubKey => {
+				let public_key =
+					LegacyAminoPubKey::decode(&mut &*public_key.value).map_err(|_| {
+						InvalidTransaction::BadSigner
+					})?;
+				let Some(Sum::Multi(Multi { bitarray: Some(bitarray), mode_infos })) = &sign_mode.sum else {
+					return Err(InvalidTransaction::BadProof.into());
+				};
+				let signature = MultiSignature::decode(signature).map_err(|_| InvalidTransaction::BadProof)?;
+
+				verify_multisig::<H>(&public_key, signer_data, bitarray, mode_infos, &signature.signatures, tx)
+			}
+		},
+		Err(InvalidTransaction::BadSigner.into())
+	)
+}
+
+/// Verifies that at least `threshold` of the sub-keys marked in `bitarray` signed the tx.
+/// `mode_infos` and `signatures` hold one entry for each marked sub-key, in order.
+fn verify_multisig<H: SignModeHandler>(
+	public_key: &LegacyAminoPubKey,
+	signer_data: &SignerData,
+	bitarray: &CompactBitArray,
+	mode_infos: &[ModeInfo],
+	signatures: &[Vec<u8>],
+	tx: &Tx,
+) -> Result<(), TransactionValidityError> {
+	if public_key.public_keys.iter().any(is_multisig) {
+		return Err(InvalidTransaction::BadSigner.into());
+	}
+
+	let size = bit_array_count(bitarray);
+	if size != public_key.public_keys.len() {
+		return Err(InvalidTransaction::BadProof.into());
+	}
+
+	let threshold = public_key.threshold as usize;
+	if threshold == 0 ||
+		signatures.len() < threshold ||
+		signatures.len() != mode_infos.len() ||
+		(0..size).filter(|i| bit_array_get(bitarray, *i)).count() != signatures.len()
+	{
+		return Err(InvalidTransaction::BadProof.into());
 	}
+
+	let marked = public_key
+		.public_keys
+		.iter()
+		.enumerate()
+		.filter_map(|(i, sub_key)| bit_array_get(bitarray, i).then_some(sub_key));
+	for ((sub_key, mode_info), signature) in marked.zip(mode_infos).zip(signatures) {
+		verify::<H>(sub_key, signer_data, mode_info, signature, tx)?;
+	}
+
+	Ok(())
+}
+
+/// Derives the account address of a public key the way the Cosmos SDK does.
+pub fn pub_key_address(public_key: &Any) -> Option<H160> {
+	any_match!(
+		public_key, {
+			secp256k1::PubKey => {
+				let public_key = secp256k1::PubKey::decode(&mut &*public_key.value).ok()?;
+				let mut hasher = ripemd::Ripemd160::new();
+				hasher.update(sha2_256(&public_key.key));
+
+				Some(H160::from_slice(&hasher.finalize()))
+			},
+			LegacyAminoPubKey => {
+				let amino = legacy_amino_bytes(public_key)?;
+
+				Some(H160::from_slice(&sha2_256(&amino)[..20]))
+			}
+		},
+		None
+	)
+}
+
+const SECP256K1_AMINO_PREFIX: [u8; 4] = [0xeb, 0x5a, 0xe9, 0x87];
+const MULTISIG_THRESHOLD_AMINO_PREFIX: [u8; 4] = [0x22, 0xc1, 0xf7, 0xe2];
+
+/// Encodes a public key in the legacy amino binary format, which multisig addresses hash.
+fn legacy_amino_bytes(public_key: &Any) -> Option<Vec<u8>> {
+	any_match!(
+		public_key, {
+			secp256k1::PubKey => {
+				let public_key = secp256k1::PubKey::decode(&mut &*public_key.value).ok()?;
+				let mut amino = SECP256K1_AMINO_PREFIX.to_vec();
+				encode_uvarint(&mut amino, public_key.key.len() as u64);
+				amino.extend_from_slice(&public_key.key);
+
+				Some(amino)
+			},
+			LegacyAminoPubKey => {
+				let public_key = LegacyAminoPubKey::decode(&mut &*public_key.value).ok()?;
+				if public_key.public_keys.iter().any(is_multisig) {
+					return None;
+				}
+
+				let mut amino = MULTISIG_THRESHOLD_AMINO_PREFIX.to_vec();
+				if public_key.threshold != 0 {
+					amino.push(0x08);
+					encode_uvarint(&mut amino, public_key.threshold.into());
+				}
+				for sub_key in public_key.public_keys.iter() {
+					let sub_key = legacy_amino_bytes(sub_key)?;
+					amino.push(0x12);
+					encode_uvarint(&mut amino, sub_key.len() as u64);
+					amino.extend_from_slice(&sub_key);
+				}
+
+				Some(amino)
+			}
+		},
+		None
+	)
+}
+
+/// Whether `public_key` is a multisig key. Multisig keys may not be nested in one another, which
+/// also bounds the recursion over sub-keys.
+fn is_multisig(public_key: &Any) -> bool {
+	any_match!(public_key, { LegacyAminoPubKey => true }, false)
+}
+
+fn encode_uvarint(buf: &mut Vec<u8>, mut value: u64) {
+	while value >= 0x80 {
+		buf.push((value as u8) | 0x80);
+		value >>= 7;
+	}
+	buf.push(value as u8);
+}
+
+fn bit_array_count(bitarray: &CompactBitArray) -> usize {
+	if bitarray.extra_bits_stored == 0 {
+		bitarray.elems.len() * 8
+	} else {
+		bitarray.elems.len().saturating_sub(1) * 8 + bitarray.extra_bits_stored as usize
+	}
+}
+
+fn bit_array_get(bitarray: &CompactBitArray, index: usize) -> bool {
+	index < bit_array_count(bitarray) &&
+		bitarray
+			.elems
+			.get(index >> 3)
+			.is_some_and(|elem| elem & (1 << (7 - index % 8)) != 0)
 }
 
 pub fn ecdsa_verify(signature: &[u8], message: &[u8], public_key: &[u8]) -> bool {
@@ -182,6 +355,28 @@ pub fn ecdsa_verify(signature: &[u8], message: &[u8], public_key: &[u8]) -> bool
 #[cfg(test)]
 pub mod tests {
 	use super::*;
+	use alloc::{string::String, vec};
+	use cosmos_sdk_proto::cosmos::tx::{
+		signing::v1beta1::SignMode,
+		v1beta1::{mode_info::Single, TxBody},
+	};
+	use hex_literal::hex;
+	use pallet_cosmos_x_auth_signing::sign_mode_handler::SignModeHandler as DefaultSignModeHandler;
+	use sp_core::Pair;
+
+	fn secp256k1_key(key: &[u8]) -> Any {
+		Any::from_msg(&secp256k1::PubKey { key: key.to_vec() }).unwrap()
+	}
+
+	fn direct_mode() -> ModeInfo {
+		ModeInfo { sum: Some(Sum::Single(Single { mode: SignMode::Direct as i32 })) }
+	}
+
+	fn sign_direct(pair: &ecdsa::Pair, signer_data: &SignerData, tx: &Tx) -> Vec<u8> {
+		let sign_bytes =
+			DefaultSignModeHandler::get_sign_bytes(&direct_mode(), signer_data, tx).unwrap();
+		pair.sign_prehashed(&sha2_256(&sign_bytes)).0.to_vec()
+	}
 
 	#[test]
 	fn ecdsa_verify_test() {
@@ -193,21 +388,172 @@ pub mod tests {
 
 		assert!(ecdsa_verify(&sig, &message, &public_key));
 	}
+
+	#[test]
+	fn bit_array_test() {
+		let bitarray = CompactBitArray { extra_bits_stored: 3, elems: vec![0b1010_0000] };
+
+		assert_eq!(bit_array_count(&bitarray), 3);
+		assert!(bit_array_get(&bitarray, 0));
+		assert!(!bit_array_get(&bitarray, 1));
+		assert!(bit_array_get(&bitarray, 2));
+		assert!(!bit_array_get(&bitarray, 8));
+	}
+
+	#[test]
+	fn count_sub_keys_test() {
+		let sub_key = Any::from_msg(&secp256k1::PubKey { key: vec![2u8; 33] }).unwrap();
+		let multisig = Any::from_msg(&LegacyAminoPubKey {
+			threshold: 2,
+			public_keys: vec![sub_key.clone(), sub_key.clone(), sub_key.clone()],
+		})
+		.unwrap();
+
+		assert_eq!(ValidateSigCountDecorator::<()>::count_sub_keys(&sub_key), Ok(1));
+		assert_eq!(ValidateSigCountDecorator::<()>::count_sub_keys(&multisig), Ok(3));
+	}
+
+	#[test]
+	fn verify_multisig_test() {
+		let pairs =
+			["//Alice", "//Bob", "//Charlie"].map(|s| ecdsa::Pair::from_string(s, None).unwrap());
+		let public_key = LegacyAminoPubKey {
+			threshold: 2,
+			public_keys: pairs.iter().map(|pair| secp256k1_key(&pair.public().0)).collect(),
+		};
+		let signer_data = SignerData {
+			address: String::new(),
+			chain_id: "dev".into(),
+			account_number: 0,
+			sequence: 0,
+			pub_key: Any::from_msg(&public_key).unwrap(),
+		};
+		let tx = Tx {
+			body: Some(TxBody { memo: "multisig".into(), ..Default::default() }),
+			..Default::default()
+		};
+		let check = |bitarray: &CompactBitArray, signatures: &[Vec<u8>]| {
+			let mode_infos = vec![direct_mode(); signatures.len()];
+			verify_multisig::<DefaultSignModeHandler>(
+				&public_key,
+				&signer_data,
+				bitarray,
+				&mode_infos,
+				signatures,
+				&tx,
+			)
+		};
+
+		// The first and the last of the three sub-keys signed, meeting the threshold.
+		let signatures = [&pairs[0], &pairs[2]].map(|pair| sign_direct(pair, &signer_data, &tx));
+		let bitarray = CompactBitArray { extra_bits_stored: 3, elems: vec![0b1010_0000] };
+		assert_eq!(check(&bitarray, &signatures), Ok(()));
+
+		// The signatures must be by the sub-keys the bit array marks.
+		let bitarray = CompactBitArray { extra_bits_stored: 3, elems: vec![0b1100_0000] };
+		assert_eq!(check(&bitarray, &signatures), Err(InvalidTransaction::BadProof.into()));
+
+		// A single signature does not meet the threshold.
+		let bitarray = CompactBitArray { extra_bits_stored: 3, elems: vec![0b1000_0000] };
+		assert_eq!(check(&bitarray, &signatures[..1]), Err(InvalidTransaction::BadProof.into()));
+
+		// The bit array must have a bit for each sub-key, no more and no less.
+		for extra_bits_stored in [2, 4] {
+			let bitarray = CompactBitArray { extra_bits_stored, elems: vec![0b1010_0000] };
+			assert_eq!(check(&bitarray, &signatures), Err(InvalidTransaction::BadProof.into()));
+		}
+	}
+
+	#[test]
+	fn pub_key_address_test() {
+		let key = hex!("020a1091341fe5664bfa1782d5e04779689068c916b04cb365ec3153755684d9a1");
+		let (_hrp, address) =
+			acc_address_from_bech32("cosmos1qd69nuwj95gta4akjgyxtj9ujmz4w8edmqysqw").unwrap();
+		assert_eq!(pub_key_address(&secp256k1_key(&key)), Some(H160::from_slice(&address)));
+
+		// A 2-of-3 multisig of the key above and the secp256k1 points G and 2G.
+		let multisig = Any::from_msg(&LegacyAminoPubKey {
+			threshold: 2,
+			public_keys: vec![
+				secp256k1_key(&key),
+				secp256k1_key(&hex!(
+					"0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+				)),
+				secp256k1_key(&hex!(
+					"02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5"
+				)),
+			],
+		})
+		.unwrap();
+		assert_eq!(
+			legacy_amino_bytes(&multisig).unwrap(),
+			hex!(
+				"22c1f7e208021226eb5ae98721020a1091341fe5664bfa1782d5e04779689068c916b04cb365ec3153"
+				"755684d9a11226eb5ae987210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b"
+				"16f817981226eb5ae9872102c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c"
+				"709ee5"
+			)
+		);
+		assert_eq!(
+			pub_key_address(&multisig),
+			Some(H160(hex!("630b66adac092456c240f31a62745212a7beb0ab")))
+		);
+	}
+
+	#[test]
+	fn nested_multisig_test() {
+		let sub_key = secp256k1_key(&[2u8; 33]);
+		let inner = LegacyAminoPubKey { threshold: 1, public_keys: vec![sub_key.clone()] };
+		let nested = LegacyAminoPubKey {
+			threshold: 1,
+			public_keys: vec![sub_key, Any::from_msg(&inner).unwrap()],
+		};
+		let signer_data = SignerData {
+			address: String::new(),
+			chain_id: "dev".into(),
+			account_number: 0,
+			sequence: 0,
+			pub_key: Any::from_msg(&nested).unwrap(),
+		};
+		let bitarray = CompactBitArray { extra_bits_stored: 2, elems: vec![0b0100_0000] };
+
+		assert_eq!(
+			ValidateSigCountDecorator::<()>::count_sub_keys(&signer_data.pub_key),
+			Err(InvalidTransaction::BadSigner.into())
+		);
+		assert_eq!(pub_key_address(&signer_data.pub_key), None);
+		assert_eq!(
+			verify_multisig::<DefaultSignModeHandler>(
+				&nested,
+				&signer_data,
+				&bitarray,
+				&[direct_mode()],
+				&[Vec::new()],
+				&Tx::default(),
+			),
+			Err(InvalidTransaction::BadSigner.into())
+		);
+	}
 }
 
 pub struct ValidateSigCountDecorator<T>(core::marker::PhantomData<T>);
 
-impl<T> AnteDecorator for ValidateSigCountDecorator<T>
+impl<T, Context> AnteDecorator<Context> for ValidateSigCountDecorator<T>
 where
 	T: pallet_cosmos::Config,
 {
-	fn ante_handle(tx: &Tx, _simulate: bool) -> TransactionValidity {
+	fn ante_handle(_ctx: &mut Context, tx: &Tx, simulate: bool) -> TransactionValidity {
 		let mut sig_count = 0u64;
 
 		let auth_info = tx.auth_info.as_ref().ok_or(InvalidTransaction::BadSigner)?;
 		for SignerInfo { public_key, .. } in auth_info.signer_infos.iter() {
-			let public_key = public_key.as_ref().ok_or(InvalidTransaction::BadSigner)?;
-			sig_count = sig_count.saturating_add(Self::count_sub_keys(public_key)?);
+			let count = match public_key {
+				Some(public_key) => Self::count_sub_keys(public_key)?,
+				// A simulated transaction may omit the public key of a single signer.
+				None if simulate => 1,
+				None => return Err(InvalidTransaction::BadSigner.into()),
+			};
+			sig_count = sig_count.saturating_add(count);
 
 			if sig_count > T::TxSigLimit::get() {
 				return Err(InvalidTransaction::BadProof.into());
@@ -220,22 +566,151 @@ where
 
 impl<T> ValidateSigCountDecorator<T> {
 	fn count_sub_keys(pubkey: &Any) -> Result<u64, TransactionValidityError> {
-		// TODO: Support legacy multi signatures.
-		if LegacyAminoPubKey::decode(&mut &*pubkey.value).is_ok() {
-			Err(InvalidTransaction::BadProof.into())
-		} else {
+		any_match!(
+			pubkey, {
+				LegacyAminoPubKey => {
+					let pubkey = LegacyAminoPubKey::decode(&mut &*pubkey.value)
+						.map_err(|_| InvalidTransaction::BadSigner)?;
+					if pubkey.public_keys.iter().any(is_multisig) {
+						return Err(InvalidTransaction::BadSigner.into());
+					}
+
+					Ok(pubkey.public_keys.len() as u64)
+				}
+			},
 			Ok(1)
+		)
+	}
+}
+
+/// Charges the gas for verifying each signature, depending on the type of the signer's key.
+pub struct SigGasConsumeDecorator<T>(core::marker::PhantomData<T>);
+
+impl<T, Context> AnteDecorator<Context> for SigGasConsumeDecorator<T>
+where
+	T: pallet_cosmos::Config,
+	Context: context::traits::Context,
+{
+	fn ante_handle(ctx: &mut Context, tx: &Tx, simulate: bool) -> TransactionValidity {
+		let auth_info = tx.auth_info.as_ref().ok_or(InvalidTransaction::BadSigner)?;
+
+		for (signer_info, sig) in auth_info.signer_infos.iter().zip(tx.signatures.iter()) {
+			match signer_info.public_key.as_ref() {
+				Some(public_key) => {
+					let sign_mode =
+						signer_info.mode_info.as_ref().ok_or(InvalidTransaction::BadSigner)?;
+					Self::consume_sig_gas(ctx.gas_meter(), public_key, sign_mode, sig, simulate)?;
+				},
+				// A simulated transaction is charged as if a secp256k1 key signed it.
+				None if simulate => {
+					ctx.gas_meter()
+						.consume_gas(T::SigVerifyCostSecp256k1::get(), "ante verify: secp256k1")
+						.map_err(|_| InvalidTransaction::ExhaustsResources)?;
+				},
+				None => return Err(InvalidTransaction::BadSigner.into()),
+			}
 		}
+
+		Ok(ValidTransaction::default())
+	}
+}
+
+impl<T> SigGasConsumeDecorator<T>
+where
+	T: pallet_cosmos::Config,
+{
+	fn consume_sig_gas<G: GasMeter>(
+		gas_meter: &mut G,
+		public_key: &Any,
+		sign_mode: &ModeInfo,
+		signature: &[u8],
+		simulate: bool,
+	) -> Result<(), TransactionValidityError> {
+		any_match!(
+			public_key, {
+				secp256k1::PubKey => {
+					gas_meter
+						.consume_gas(T::SigVerifyCostSecp256k1::get(), "ante verify: secp256k1")
+						.map_err(|_| InvalidTransaction::ExhaustsResources)?;
+
+					Ok(())
+				},
+				LegacyAminoPubKey => {
+					let public_key =
+						LegacyAminoPubKey::decode(&mut &*public_key.value).map_err(|_| {
+							InvalidTransaction::BadSigner
+						})?;
+
+					// An unsigned multisig is simulated as if all of its sub-keys signed it.
+					if simulate && signature.is_empty() {
+						for sub_key in public_key.public_keys.iter() {
+							Self::consume_sig_gas(gas_meter, sub_key, sign_mode, signature, simulate)?;
+						}
+
+						return Ok(());
+					}
+
+					let Some(Sum::Multi(Multi { bitarray: Some(bitarray), mode_infos })) = &sign_mode.sum else {
+						return Err(InvalidTransaction::BadProof.into());
+					};
+					let signature = MultiSignature::decode(signature).map_err(|_| InvalidTransaction::BadProof)?;
+
+					let marked = public_key
+						.public_keys
+						.iter()
+						.enumerate()
+						.filter_map(|(i, sub_key)| bit_array_get(bitarray, i).then_some(sub_key));
+					for ((sub_key, mode_info), signature) in marked.zip(mode_infos).zip(signature.signatures.iter()) {
+						Self::consume_sig_gas(gas_meter, sub_key, mode_info, signature, simulate)?;
+					}
+
+					Ok(())
+				}
+			},
+			Err(InvalidTransaction::BadSigner.into())
+		)
+	}
+}
+
+/// Stores the public key of each signer that has none stored yet.
+pub struct SetPubKeyDecorator<T>(core::marker::PhantomData<T>);
+
+impl<T, Context> AnteDecorator<Context> for SetPubKeyDecorator<T>
+where
+	T: pallet_cosmos::Config,
+{
+	fn ante_handle(_ctx: &mut Context, tx: &Tx, _simulate: bool) -> TransactionValidity {
+		let signers = T::SigVerifiableTx::get_signers(tx).map_err(|_| InvalidTransaction::Call)?;
+		let auth_info = tx.auth_info.as_ref().ok_or(InvalidTransaction::BadSigner)?;
+
+		for (signer, signer_info) in signers.iter().zip(auth_info.signer_infos.iter()) {
+			let Some(public_key) = signer_info.public_key.as_ref() else {
+				continue;
+			};
+
+			let (_hrp, address_raw) =
+				acc_address_from_bech32(signer).map_err(|_| InvalidTransaction::BadSigner)?;
+			if address_raw.len() != 20 {
+				return Err(InvalidTransaction::BadSigner.into());
+			}
+
+			let address = H160::from_slice(&address_raw);
+			if T::AccountPubKeys::pub_key(&address).is_none() {
+				T::AccountPubKeys::set_pub_key(&address, public_key);
+			}
+		}
+
+		Ok(ValidTransaction::default())
 	}
 }
 
 pub struct IncrementSequenceDecorator<T>(core::marker::PhantomData<T>);
 
-impl<T> AnteDecorator for IncrementSequenceDecorator<T>
+impl<T, Context> AnteDecorator<Context> for IncrementSequenceDecorator<T>
 where
 	T: frame_system::Config + pallet_cosmos::Config,
 {
-	fn ante_handle(tx: &Tx, _simulate: bool) -> TransactionValidity {
+	fn ante_handle(_ctx: &mut Context, tx: &Tx, _simulate: bool) -> TransactionValidity {
 		let signers = T::SigVerifiableTx::get_signers(tx).map_err(|_| InvalidTransaction::Call)?;
 		for signer in signers.iter() {
 			let (_hrp, address_raw) =
@@ -244,7 +719,10 @@ where
 				return Err(InvalidTransaction::BadSigner.into());
 			}
 
-			let account = T::AddressMapping::into_account_id(H160::from_slice(&address_raw));
+			let address = H160::from_slice(&address_raw);
+			T::AccountNumbers::ensure_account_number(&address);
+
+			let account = T::AddressMapping::into_account_id(address);
 			frame_system::pallet::Pallet::<T>::inc_account_nonce(account);
 		}
 
@@ -0,0 +1,206 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::{string::String, vec::Vec};
+use cosmos_sdk_proto::{
+	cosmos::{
+		authz::v1beta1,
+		bank::v1beta1::{MsgSend, SendAuthorization as ProtoSendAuthorization},
+	},
+	prost::{Message, Name},
+	Any,
+};
+use pallet_cosmos_types::{
+	any_match,
+	coin::{coins_from, safe_sub, Coins},
+	errors::{CosmosError, RootError},
+};
+use pallet_cosmos_x_authz_types::errors::AuthzError;
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// Expirations are kept in seconds.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct Grant {
+	pub authorization: Authorization,
+	pub expiration: Option<u64>,
+}
+
+impl TryFrom<&v1beta1::Grant> for Grant {
+	type Error = CosmosError;
+
+	fn try_from(grant: &v1beta1::Grant) -> Result<Self, Self::Error> {
+		let authorization = grant.authorization.as_ref().ok_or(RootError::InvalidRequest)?;
+		let expiration = grant
+			.expiration
+			.as_ref()
+			.map(|expiration| u64::try_from(expiration.seconds))
+			.transpose()
+			.map_err(|_| AuthzError::InvalidExpirationTime)?;
+
+		Ok(Self { authorization: authorization.try_into()?, expiration })
+	}
+}
+
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub enum Authorization {
+	Generic(GenericAuthorization),
+	Send(SendAuthorization),
+}
+
+impl Authorization {
+	/// The type URL of the messages the authorization applies to.
+	pub fn msg_type_url(&self) -> String {
+		match self {
+			Self::Generic(authorization) => authorization.msg.clone(),
+			Self::Send(_) => MsgSend::type_url(),
+		}
+	}
+
+	/// Checks whether `msg` may be executed on behalf of the granter and updates the
+	/// authorization. Returns `true` when the authorization is used up and should be removed.
+	pub fn accept(&mut self, msg: &Any) -> Result<bool, CosmosError> {
+		match self {
+			Self::Generic(_) => Ok(false),
+			Self::Send(authorization) => authorization.accept(msg),
+		}
+	}
+}
+
+impl TryFrom<&Any> for Authorization {
+	type Error = CosmosError;
+
+	fn try_from(any: &Any) -> Result<Self, Self::Error> {
+		any_match!(
+			any, {
+				v1beta1::GenericAuthorization => v1beta1::GenericAuthorization::decode(&mut &*any.value)
+					.map(|authorization| Self::Generic(GenericAuthorization { msg: authorization.msg }))
+					.map_err(|_| RootError::UnpackAnyError.into()),
+				ProtoSendAuthorization => ProtoSendAuthorization::decode(&mut &*any.value)
+					.map_err(|_| RootError::UnpackAnyError.into())
+					.and_then(|authorization| (&authorization).try_into().map(Self::Send)),
+			},
+			Err(AuthzError::UnknownAuthorizationType.into())
+		)
+	}
+}
+
+/// Allows the grantee to execute any message of the given type.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct GenericAuthorization {
+	pub msg: String,
+}
+
+/// Allows the grantee to send up to `spend_limit` from the granter's account.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct SendAuthorization {
+	pub spend_limit: Coins,
+	/// Addresses the grantee may send to. Empty means any address.
+	pub allow_list: Vec<String>,
+}
+
+impl SendAuthorization {
+	fn accept(&mut self, msg: &Any) -> Result<bool, CosmosError> {
+		if msg.type_url != MsgSend::type_url() {
+			return Err(RootError::InvalidType.into());
+		}
+		let msg = MsgSend::decode(&mut &*msg.value).map_err(|_| RootError::UnpackAnyError)?;
+
+		if !self.allow_list.is_empty() && !self.allow_list.contains(&msg.to_address) {
+			return Err(RootError::Unauthorized.into());
+		}
+
+		self.spend_limit = safe_sub(&self.spend_limit, &coins_from(&msg.amount)?)
+			.ok_or(RootError::InsufficientFunds)?;
+
+		Ok(self.spend_limit.is_empty())
+	}
+}
+
+impl TryFrom<&ProtoSendAuthorization> for SendAuthorization {
+	type Error = CosmosError;
+
+	fn try_from(authorization: &ProtoSendAuthorization) -> Result<Self, Self::Error> {
+		let spend_limit = coins_from(&authorization.spend_limit)?;
+		if spend_limit.is_empty() {
+			return Err(RootError::InvalidCoins.into());
+		}
+
+		let mut allow_list = Vec::<String>::new();
+		for address in authorization.allow_list.iter() {
+			if allow_list.contains(address) {
+				return Err(RootError::InvalidAddress.into());
+			}
+			allow_list.push(address.clone());
+		}
+
+		Ok(Self { spend_limit, allow_list })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::{string::ToString, vec};
+	use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+
+	fn msg_send(to_address: &str, amount: u128) -> Any {
+		Any::from_msg(&MsgSend {
+			from_address: "cosmos1qd69nuwj95gta4akjgyxtj9ujmz4w8edmqysqw".into(),
+			to_address: to_address.into(),
+			amount: vec![Coin { denom: "acdt".into(), amount: amount.to_string() }],
+		})
+		.unwrap()
+	}
+
+	#[test]
+	fn generic_authorization_test() {
+		let mut authorization = Authorization::Generic(GenericAuthorization {
+			msg: "/cosmwasm.wasm.v1.MsgExecuteContract".into(),
+		});
+
+		assert_eq!(authorization.msg_type_url(), "/cosmwasm.wasm.v1.MsgExecuteContract");
+		assert_eq!(authorization.accept(&msg_send("cosmos1recipient", 10)), Ok(false));
+	}
+
+	#[test]
+	fn send_authorization_test() {
+		let recipient = "cosmos1n5jgx64zw38ss76mzuu4uc7jeyqw2vfjk60fdz";
+		let mut authorization = Authorization::Send(SendAuthorization {
+			spend_limit: [("acdt".to_string(), 100)].into_iter().collect(),
+			allow_list: vec![recipient.into()],
+		});
+
+		assert_eq!(authorization.msg_type_url(), MsgSend::type_url());
+		assert_eq!(authorization.accept(&msg_send(recipient, 40)), Ok(false));
+		assert_eq!(
+			authorization.accept(&msg_send(recipient, 70)),
+			Err(RootError::InsufficientFunds.into())
+		);
+		assert_eq!(
+			authorization.accept(&msg_send("cosmos1gmj2exag03ttgafprkdc3t880grma9nwefcd2w", 10)),
+			Err(RootError::Unauthorized.into())
+		);
+		assert_eq!(
+			authorization
+				.accept(&Any { type_url: "/cosmwasm.wasm.v1.MsgStoreCode".into(), value: vec![] }),
+			Err(RootError::InvalidType.into())
+		);
+		assert_eq!(authorization.accept(&msg_send(recipient, 60)), Ok(true));
+	}
+}
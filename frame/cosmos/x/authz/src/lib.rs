@@ -0,0 +1,79 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod authorizations;
+pub mod msgs;
+
+pub use pallet::*;
+
+use frame_support::traits::UnixTime;
+use pallet_cosmos::AddressMapping;
+use pallet_cosmos_types::{
+	address::acc_address_from_bech32,
+	errors::{CosmosError, RootError},
+};
+use sp_core::H160;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use crate::authorizations::Grant;
+	use alloc::string::String;
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_cosmos::Config {
+		/// Time used to check grant expirations.
+		type UnixTime: UnixTime;
+	}
+
+	/// Grants, keyed by granter, grantee and the type URL of the authorized message.
+	#[pallet::storage]
+	pub type Grants<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, T::AccountId>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+			NMapKey<Blake2_128Concat, String>,
+		),
+		Grant,
+	>;
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn account_id(address: &str) -> Result<T::AccountId, CosmosError> {
+		let (_hrp, address_raw) =
+			acc_address_from_bech32(address).map_err(|_| RootError::InvalidAddress)?;
+		if address_raw.len() != 20 {
+			return Err(RootError::InvalidAddress.into());
+		}
+
+		Ok(T::AddressMapping::into_account_id(H160::from_slice(&address_raw)))
+	}
+
+	pub fn now() -> u64 {
+		T::UnixTime::now().as_secs()
+	}
+}
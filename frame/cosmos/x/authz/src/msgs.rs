@@ -0,0 +1,217 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{authorizations::Grant, Grants};
+use alloc::vec;
+use core::marker::PhantomData;
+use cosmos_sdk_proto::{
+	cosmos::authz::v1beta1::{MsgExec, MsgGrant, MsgRevoke},
+	traits::Message,
+	Any,
+};
+use frame_support::traits::Get;
+use pallet_cosmos_types::{
+	context::{self, traits::Context as _},
+	errors::{CosmosError, RootError},
+	events::{traits::EventManager, CosmosEvent, EventAttribute},
+	gas::traits::GasMeter,
+	msgservice::{MsgHandler, MsgServiceRouter},
+};
+use pallet_cosmos_x_auth_signing::sign_verifiable_tx::traits::SigVerifiableTx;
+use pallet_cosmos_x_authz_types::{
+	errors::AuthzError,
+	events::{
+		ATTRIBUTE_KEY_GRANTEE, ATTRIBUTE_KEY_GRANTER, ATTRIBUTE_KEY_MSG_TYPE_URL, EVENT_TYPE_GRANT,
+		EVENT_TYPE_REVOKE,
+	},
+};
+
+pub struct MsgGrantHandler<T>(PhantomData<T>);
+
+impl<T> Default for MsgGrantHandler<T> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T, Context> MsgHandler<Context> for MsgGrantHandler<T>
+where
+	T: crate::Config,
+	Context: context::traits::Context,
+{
+	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
+		let MsgGrant { granter, grantee, grant } =
+			MsgGrant::decode(&mut &*msg.value).map_err(|_| RootError::UnpackAnyError)?;
+
+		if granter == grantee {
+			return Err(AuthzError::GranteeIsGranter.into());
+		}
+		let granter_account = crate::Pallet::<T>::account_id(&granter)?;
+		let grantee_account = crate::Pallet::<T>::account_id(&grantee)?;
+
+		let grant = Grant::try_from(grant.as_ref().ok_or(RootError::InvalidRequest)?)?;
+		if grant
+			.expiration
+			.is_some_and(|expiration| expiration < crate::Pallet::<T>::now())
+		{
+			return Err(AuthzError::InvalidExpirationTime.into());
+		}
+
+		let msg_type_url = grant.authorization.msg_type_url();
+		let authorized = Any { type_url: msg_type_url.clone(), value: vec![] };
+		if T::MsgServiceRouter::route::<Context>(&authorized).is_none() {
+			return Err(RootError::InvalidType.into());
+		}
+
+		Grants::<T>::insert((&granter_account, &grantee_account, &msg_type_url), grant);
+
+		ctx.gas_meter()
+			.consume_gas(T::DbWeight::get().writes(1).ref_time(), "WriteFlat")
+			.map_err(|_| RootError::OutOfGas)?;
+
+		ctx.event_manager().emit_event(CosmosEvent {
+			r#type: EVENT_TYPE_GRANT.into(),
+			attributes: vec![
+				EventAttribute {
+					key: ATTRIBUTE_KEY_MSG_TYPE_URL.into(),
+					value: msg_type_url.into(),
+				},
+				EventAttribute { key: ATTRIBUTE_KEY_GRANTER.into(), value: granter.into() },
+				EventAttribute { key: ATTRIBUTE_KEY_GRANTEE.into(), value: grantee.into() },
+			],
+		});
+
+		Ok(())
+	}
+}
+
+pub struct MsgRevokeHandler<T>(PhantomData<T>);
+
+impl<T> Default for MsgRevokeHandler<T> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T, Context> MsgHandler<Context> for MsgRevokeHandler<T>
+where
+	T: crate::Config,
+	Context: context::traits::Context,
+{
+	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
+		let MsgRevoke { granter, grantee, msg_type_url } =
+			MsgRevoke::decode(&mut &*msg.value).map_err(|_| RootError::UnpackAnyError)?;
+
+		let granter_account = crate::Pallet::<T>::account_id(&granter)?;
+		let grantee_account = crate::Pallet::<T>::account_id(&grantee)?;
+
+		ctx.gas_meter()
+			.consume_gas(T::DbWeight::get().reads(1).ref_time(), "ReadFlat")
+			.map_err(|_| RootError::OutOfGas)?;
+
+		let key = (&granter_account, &grantee_account, &msg_type_url);
+		if !Grants::<T>::contains_key(key) {
+			return Err(AuthzError::NoAuthorizationFound.into());
+		}
+
+		Grants::<T>::remove(key);
+
+		ctx.gas_meter()
+			.consume_gas(T::DbWeight::get().writes(1).ref_time(), "WriteFlat")
+			.map_err(|_| RootError::OutOfGas)?;
+
+		ctx.event_manager().emit_event(CosmosEvent {
+			r#type: EVENT_TYPE_REVOKE.into(),
+			attributes: vec![
+				EventAttribute {
+					key: ATTRIBUTE_KEY_MSG_TYPE_URL.into(),
+					value: msg_type_url.into(),
+				},
+				EventAttribute { key: ATTRIBUTE_KEY_GRANTER.into(), value: granter.into() },
+				EventAttribute { key: ATTRIBUTE_KEY_GRANTEE.into(), value: grantee.into() },
+			],
+		});
+
+		Ok(())
+	}
+}
+
+/// Executes the inner messages on behalf of their signers. Messages signed by anyone but the
+/// grantee need a grant, which is updated or removed as the authorization is used.
+pub struct MsgExecHandler<T>(PhantomData<T>);
+
+impl<T> Default for MsgExecHandler<T> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T, Context> MsgHandler<Context> for MsgExecHandler<T>
+where
+	T: crate::Config,
+	Context: context::traits::Context,
+{
+	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
+		let MsgExec { grantee, msgs } =
+			MsgExec::decode(&mut &*msg.value).map_err(|_| RootError::UnpackAnyError)?;
+
+		if msgs.is_empty() {
+			return Err(RootError::InvalidRequest.into());
+		}
+		let grantee_account = crate::Pallet::<T>::account_id(&grantee)?;
+
+		for msg in msgs.iter() {
+			let signers =
+				T::SigVerifiableTx::get_msg_signers(msg).map_err(|_| RootError::UnknownRequest)?;
+			let [granter] = signers.as_slice() else {
+				return Err(AuthzError::AuthorizationNumOfSigners.into());
+			};
+
+			let granter_account = crate::Pallet::<T>::account_id(granter)?;
+			if granter_account != grantee_account {
+				ctx.gas_meter()
+					.consume_gas(T::DbWeight::get().reads(1).ref_time(), "ReadFlat")
+					.map_err(|_| RootError::OutOfGas)?;
+
+				let key = (&granter_account, &grantee_account, &msg.type_url);
+				let mut grant = Grants::<T>::get(key).ok_or(AuthzError::NoAuthorizationFound)?;
+				if grant
+					.expiration
+					.is_some_and(|expiration| expiration < crate::Pallet::<T>::now())
+				{
+					return Err(AuthzError::AuthorizationExpired.into());
+				}
+
+				if grant.authorization.accept(msg)? {
+					Grants::<T>::remove(key);
+				} else {
+					Grants::<T>::insert(key, grant);
+				}
+
+				ctx.gas_meter()
+					.consume_gas(T::DbWeight::get().writes(1).ref_time(), "WriteFlat")
+					.map_err(|_| RootError::OutOfGas)?;
+			}
+
+			let handler = T::MsgServiceRouter::route(msg).ok_or(RootError::UnknownRequest)?;
+			handler.handle(msg, ctx)?;
+		}
+
+		Ok(())
+	}
+}
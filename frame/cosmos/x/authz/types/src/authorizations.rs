@@ -0,0 +1,97 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::{string::String, vec::Vec};
+use cosmos_sdk_proto::{
+	cosmos::{authz::v1beta1, bank::v1beta1::SendAuthorization as ProtoSendAuthorization},
+	prost::Message,
+	Any,
+};
+use pallet_cosmos_types::{any_match, coin::Coin};
+use pallet_cosmos_x_auth_migrations::legacytx::stdsign::format_timestamp;
+use serde::{Deserialize, Serialize};
+
+/// Amino JSON representation of an authorization.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum Authorization {
+	#[serde(rename = "cosmos-sdk/GenericAuthorization")]
+	Generic(GenericAuthorization),
+	#[serde(rename = "cosmos-sdk/SendAuthorization")]
+	Send(SendAuthorization),
+}
+
+impl TryFrom<&Any> for Authorization {
+	type Error = ();
+
+	fn try_from(any: &Any) -> Result<Self, Self::Error> {
+		any_match!(
+			any, {
+				v1beta1::GenericAuthorization => v1beta1::GenericAuthorization::decode(&mut &*any.value)
+					.map(|authorization| Self::Generic(GenericAuthorization { msg: authorization.msg }))
+					.map_err(|_| ()),
+				ProtoSendAuthorization => ProtoSendAuthorization::decode(&mut &*any.value)
+					.map(|authorization| Self::Send((&authorization).into()))
+					.map_err(|_| ()),
+			},
+			Err(())
+		)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenericAuthorization {
+	pub msg: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SendAuthorization {
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub allow_list: Vec<String>,
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub spend_limit: Vec<Coin>,
+}
+
+impl From<&ProtoSendAuthorization> for SendAuthorization {
+	fn from(authorization: &ProtoSendAuthorization) -> Self {
+		Self {
+			allow_list: authorization.allow_list.clone(),
+			spend_limit: authorization.spend_limit.iter().map(Into::into).collect(),
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Grant {
+	pub authorization: Authorization,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub expiration: Option<String>,
+}
+
+impl TryFrom<&v1beta1::Grant> for Grant {
+	type Error = ();
+
+	fn try_from(grant: &v1beta1::Grant) -> Result<Self, Self::Error> {
+		Ok(Self {
+			authorization: grant.authorization.as_ref().ok_or(())?.try_into()?,
+			expiration: grant
+				.expiration
+				.as_ref()
+				.map(|expiration| format_timestamp(expiration.seconds, expiration.nanos)),
+		})
+	}
+}
@@ -0,0 +1,55 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pallet_cosmos_types::errors::CosmosError;
+
+pub const AUTHZ_CODESPACE: u8 = 3;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum AuthzError {
+	NoAuthorizationFound = 2,
+	InvalidExpirationTime = 3,
+	UnknownAuthorizationType = 4,
+	NoGrantKeyFound = 5,
+	AuthorizationExpired = 6,
+	GranteeIsGranter = 7,
+	AuthorizationNumOfSigners = 9,
+}
+
+impl From<AuthzError> for CosmosError {
+	fn from(error: AuthzError) -> Self {
+		CosmosError { codespace: AUTHZ_CODESPACE, code: error as u8 }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{AuthzError, CosmosError};
+	use crate::errors::AUTHZ_CODESPACE;
+
+	#[test]
+	fn authz_error_test() {
+		let error: CosmosError = AuthzError::AuthorizationExpired.into();
+		assert_eq!(
+			error,
+			CosmosError {
+				codespace: AUTHZ_CODESPACE,
+				code: AuthzError::AuthorizationExpired as u8
+			}
+		);
+	}
+}
@@ -0,0 +1,23 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub const EVENT_TYPE_GRANT: &str = "cosmos.authz.v1beta1.EventGrant";
+pub const EVENT_TYPE_REVOKE: &str = "cosmos.authz.v1beta1.EventRevoke";
+
+pub const ATTRIBUTE_KEY_MSG_TYPE_URL: &str = "msg_type_url";
+pub const ATTRIBUTE_KEY_GRANTER: &str = "granter";
+pub const ATTRIBUTE_KEY_GRANTEE: &str = "grantee";
@@ -0,0 +1,50 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::authorizations::Grant;
+use alloc::{string::String, vec, vec::Vec};
+use cosmos_sdk_proto::{cosmos::authz, prost::Message, Any};
+use pallet_cosmos_types::tx_msgs::Msg;
+use pallet_cosmos_x_auth_migrations::legacytx::stdsign::LegacyMsg;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MsgGrant {
+	pub grant: Grant,
+	pub grantee: String,
+	pub granter: String,
+}
+
+impl TryFrom<&Any> for MsgGrant {
+	type Error = ();
+
+	fn try_from(any: &Any) -> Result<Self, Self::Error> {
+		let msg = authz::v1beta1::MsgGrant::decode(&mut &*any.value).map_err(|_| ())?;
+		let grant = msg.grant.as_ref().ok_or(())?;
+		Ok(Self { grant: grant.try_into()?, grantee: msg.grantee, granter: msg.granter })
+	}
+}
+
+impl LegacyMsg for MsgGrant {
+	const AMINO_NAME: &'static str = "cosmos-sdk/MsgGrant";
+}
+
+impl Msg for MsgGrant {
+	fn get_signers(self) -> Vec<String> {
+		vec![self.granter.clone()]
+	}
+}
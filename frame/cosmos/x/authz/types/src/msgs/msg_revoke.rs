@@ -0,0 +1,48 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::{string::String, vec, vec::Vec};
+use cosmos_sdk_proto::{cosmos::authz, prost::Message, Any};
+use pallet_cosmos_types::tx_msgs::Msg;
+use pallet_cosmos_x_auth_migrations::legacytx::stdsign::LegacyMsg;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MsgRevoke {
+	pub grantee: String,
+	pub granter: String,
+	pub msg_type_url: String,
+}
+
+impl TryFrom<&Any> for MsgRevoke {
+	type Error = ();
+
+	fn try_from(any: &Any) -> Result<Self, Self::Error> {
+		let msg = authz::v1beta1::MsgRevoke::decode(&mut &*any.value).map_err(|_| ())?;
+		Ok(Self { grantee: msg.grantee, granter: msg.granter, msg_type_url: msg.msg_type_url })
+	}
+}
+
+impl LegacyMsg for MsgRevoke {
+	const AMINO_NAME: &'static str = "cosmos-sdk/MsgRevoke";
+}
+
+impl Msg for MsgRevoke {
+	fn get_signers(self) -> Vec<String> {
+		vec![self.granter.clone()]
+	}
+}
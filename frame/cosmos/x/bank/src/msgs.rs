@@ -16,18 +16,25 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use alloc::vec;
-use cosmos_sdk_proto::{cosmos::bank::v1beta1::MsgSend, traits::Message, Any};
+use alloc::{vec, vec::Vec};
+use cosmos_sdk_proto::{
+	cosmos::{
+		bank::v1beta1::{MsgMultiSend, MsgSend},
+		base::v1beta1::Coin,
+	},
+	traits::Message,
+	Any,
+};
 use frame_support::{
 	pallet_prelude::*,
 	traits::{fungibles::Mutate, tokens::Preservation, Currency, ExistenceRequirement},
 };
 use pallet_assets::WeightInfo as _;
 use pallet_balances::WeightInfo as _;
-use pallet_cosmos::AddressMapping;
+use pallet_cosmos::{AccountNumbers, AddressMapping};
 use pallet_cosmos_types::{
 	address::acc_address_from_bech32,
-	coin::amount_to_string,
+	coin::{amount_to_string, coins_from, Coins},
 	context,
 	errors::{CosmosError, RootError},
 	events::{
@@ -36,10 +43,106 @@ use pallet_cosmos_types::{
 	},
 	gas::traits::GasMeter,
 };
-use pallet_cosmos_x_bank_types::events::{ATTRIBUTE_KEY_RECIPIENT, EVENT_TYPE_TRANSFER};
+use pallet_cosmos_x_bank_types::{
+	errors::BankError,
+	events::{ATTRIBUTE_KEY_RECIPIENT, EVENT_TYPE_TRANSFER},
+};
 use sp_core::H160;
 use sp_runtime::{traits::Convert, SaturatedConversion};
 
+fn address_from_bech32(address: &str) -> Result<H160, CosmosError> {
+	let (_hrp, address_raw) =
+		acc_address_from_bech32(address).map_err(|_| RootError::InvalidAddress)?;
+	if address_raw.len() != 20 {
+		return Err(RootError::InvalidAddress.into());
+	}
+
+	Ok(H160::from_slice(&address_raw))
+}
+
+fn send_coins<T, Context>(
+	ctx: &mut Context,
+	from_address: &str,
+	from_account: &T::AccountId,
+	to_address: &str,
+	to_account: &T::AccountId,
+	amount: &[Coin],
+) -> Result<(), CosmosError>
+where
+	T: pallet_cosmos::Config,
+	Context: context::traits::Context,
+{
+	ctx.gas_meter()
+		.consume_gas(T::DbWeight::get().reads(1 + amount.len() as u64).ref_time(), "ReadFlat")
+		.map_err(|_| RootError::OutOfGas)?;
+
+	if pallet_cosmos::Pallet::<T>::is_blocked_addr(to_account) {
+		return Err(RootError::Unauthorized.into());
+	}
+	if amount
+		.iter()
+		.any(|amt| !pallet_cosmos::Pallet::<T>::is_send_enabled(&amt.denom))
+	{
+		return Err(BankError::SendDisabled.into());
+	}
+
+	for amt in amount.iter() {
+		let transfer_amount = amt.amount.parse::<u128>().map_err(|_| RootError::InvalidCoins)?;
+
+		if T::NativeDenom::get() == amt.denom {
+			T::NativeAsset::transfer(
+				from_account,
+				to_account,
+				transfer_amount.saturated_into(),
+				ExistenceRequirement::KeepAlive,
+			)
+			.map_err(|_| RootError::InsufficientFunds)?;
+
+			ctx.gas_meter()
+				.consume_gas(
+					pallet_balances::weights::SubstrateWeight::<T>::transfer_keep_alive()
+						.ref_time(),
+					"transfer_keep_alive",
+				)
+				.map_err(|_| RootError::OutOfGas)?;
+		} else {
+			let asset_id =
+				T::AssetToDenom::convert(amt.denom.clone()).map_err(|_| RootError::InvalidCoins)?;
+			T::Assets::transfer(
+				asset_id,
+				from_account,
+				to_account,
+				transfer_amount.saturated_into(),
+				Preservation::Preserve,
+			)
+			.map_err(|_| RootError::InsufficientFunds)?;
+
+			ctx.gas_meter()
+				.consume_gas(
+					pallet_assets::weights::SubstrateWeight::<T>::transfer_keep_alive().ref_time(),
+					"transfer_keep_alive",
+				)
+				.map_err(|_| RootError::OutOfGas)?;
+		}
+	}
+
+	ctx.event_manager().emit_events(vec![
+		CosmosEvent::coin_spent(from_address, amount),
+		CosmosEvent::coin_received(to_address, amount),
+	]);
+
+	Ok(())
+}
+
+fn add_coins(total: &mut Coins, coins: &[Coin]) -> Result<(), CosmosError> {
+	for (denom, amount) in coins_from(coins)?.into_iter() {
+		let sum = total.entry(denom).or_default();
+		*sum = sum.checked_add(amount).ok_or(RootError::InvalidCoins)?;
+	}
+
+	Ok(())
+}
+
 pub struct MsgSendHandler<T>(PhantomData<T>);
 
 impl<T> Default for MsgSendHandler<T> {
@@ -57,65 +160,25 @@ where
 		let MsgSend { from_address, to_address, amount } =
 			MsgSend::decode(&mut &*msg.value).map_err(|_| RootError::UnpackAnyError)?;
 
-		let (_hrp, from_address_raw) =
-			acc_address_from_bech32(&from_address).map_err(|_| RootError::InvalidAddress)?;
-		let (_hrp, to_address_raw) =
-			acc_address_from_bech32(&to_address).map_err(|_| RootError::InvalidAddress)?;
+		let from_address_raw = address_from_bech32(&from_address)?;
+		let to_address_raw = address_from_bech32(&to_address)?;
 
-		if from_address_raw.len() != 20 {
-			return Err(RootError::InvalidAddress.into());
-		}
-		if to_address_raw.len() != 20 {
-			return Err(RootError::InvalidAddress.into());
-		}
-		let from_account = T::AddressMapping::into_account_id(H160::from_slice(&from_address_raw));
-		let to_account = T::AddressMapping::into_account_id(H160::from_slice(&to_address_raw));
+		let from_account = T::AddressMapping::into_account_id(from_address_raw);
+		let to_account = T::AddressMapping::into_account_id(to_address_raw);
+		T::AccountNumbers::ensure_account_number(&to_address_raw);
 
 		ctx.gas_meter()
-			.consume_gas(T::DbWeight::get().reads(2).ref_time(), "")
+			.consume_gas(T::DbWeight::get().reads(2).ref_time(), "ReadFlat")
 			.map_err(|_| RootError::OutOfGas)?;
 
-		for amt in amount.iter() {
-			let transfer_amount =
-				amt.amount.parse::<u128>().map_err(|_| RootError::InvalidCoins)?;
-
-			if T::NativeDenom::get() == amt.denom {
-				T::NativeAsset::transfer(
-					&from_account,
-					&to_account,
-					transfer_amount.saturated_into(),
-					ExistenceRequirement::KeepAlive,
-				)
-				.map_err(|_| RootError::InsufficientFunds)?;
-
-				ctx.gas_meter()
-					.consume_gas(
-						pallet_balances::weights::SubstrateWeight::<T>::transfer_keep_alive()
-							.ref_time(),
-						"",
-					)
-					.map_err(|_| RootError::OutOfGas)?;
-			} else {
-				let asset_id = T::AssetToDenom::convert(amt.denom.clone())
-					.map_err(|_| RootError::InvalidCoins)?;
-				T::Assets::transfer(
-					asset_id,
-					&from_account,
-					&to_account,
-					transfer_amount.saturated_into(),
-					Preservation::Preserve,
-				)
-				.map_err(|_| RootError::InsufficientFunds)?;
-
-				ctx.gas_meter()
-					.consume_gas(
-						pallet_assets::weights::SubstrateWeight::<T>::transfer_keep_alive()
-							.ref_time(),
-						"",
-					)
-					.map_err(|_| RootError::OutOfGas)?;
-			}
-		}
+		send_coins::<T, Context>(
+			ctx,
+			&from_address,
+			&from_account,
+			&to_address,
+			&to_account,
+			&amount,
+		)?;
 
 		let msg_event = CosmosEvent {
 			r#type: EVENT_TYPE_TRANSFER.into(),
@@ -134,3 +197,92 @@ where
 		Ok(())
 	}
 }
+
+pub struct MsgMultiSendHandler<T>(PhantomData<T>);
+
+impl<T> Default for MsgMultiSendHandler<T> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T, Context> pallet_cosmos_types::msgservice::MsgHandler<Context> for MsgMultiSendHandler<T>
+where
+	T: pallet_cosmos::Config,
+	Context: context::traits::Context,
+{
+	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
+		let MsgMultiSend { inputs, outputs } =
+			MsgMultiSend::decode(&mut &*msg.value).map_err(|_| RootError::UnpackAnyError)?;
+
+		// Only a single input is allowed, as in cosmos-sdk v0.47 and later.
+		let input = match inputs.as_slice() {
+			[] => return Err(BankError::NoInputs.into()),
+			[input] => input,
+			_ => return Err(BankError::MultipleSenders.into()),
+		};
+		if outputs.is_empty() {
+			return Err(BankError::NoOutputs.into());
+		}
+
+		let mut total_in = Coins::new();
+		add_coins(&mut total_in, &input.coins)?;
+		let mut total_out = Coins::new();
+		for output in outputs.iter() {
+			add_coins(&mut total_out, &output.coins)?;
+		}
+		if total_in != total_out {
+			return Err(BankError::InputOutputMismatch.into());
+		}
+
+		let from_address_raw = address_from_bech32(&input.address)?;
+		let from_account = T::AddressMapping::into_account_id(from_address_raw);
+
+		let recipients = outputs
+			.iter()
+			.map(|output| address_from_bech32(&output.address))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		ctx.gas_meter()
+			.consume_gas(
+				T::DbWeight::get().reads(1 + recipients.len() as u64).ref_time(),
+				"ReadFlat",
+			)
+			.map_err(|_| RootError::OutOfGas)?;
+
+		for (output, to_address_raw) in outputs.iter().zip(recipients.into_iter()) {
+			let to_account = T::AddressMapping::into_account_id(to_address_raw);
+			T::AccountNumbers::ensure_account_number(&to_address_raw);
+
+			send_coins::<T, Context>(
+				ctx,
+				&input.address,
+				&from_account,
+				&output.address,
+				&to_account,
+				&output.coins,
+			)?;
+
+			let transfer_event = CosmosEvent {
+				r#type: EVENT_TYPE_TRANSFER.into(),
+				attributes: vec![
+					EventAttribute {
+						key: ATTRIBUTE_KEY_SENDER.into(),
+						value: input.address.clone().into(),
+					},
+					EventAttribute {
+						key: ATTRIBUTE_KEY_RECIPIENT.into(),
+						value: output.address.clone().into(),
+					},
+					EventAttribute {
+						key: ATTRIBUTE_KEY_AMOUNT.into(),
+						value: amount_to_string(&output.coins).into(),
+					},
+				],
+			};
+			ctx.event_manager().emit_event(transfer_event);
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,337 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use alloc::{
+	string::{String, ToString},
+	vec::Vec,
+};
+use core::marker::PhantomData;
+use cosmos_sdk_proto::{
+	cosmos::bank::v1beta1::{
+		QueryAllBalancesRequest, QueryAllBalancesResponse, QueryBalanceRequest,
+		QueryBalanceResponse, QueryDenomMetadataRequest, QueryDenomMetadataResponse,
+		QuerySpendableBalancesRequest, QuerySpendableBalancesResponse, QuerySupplyOfRequest,
+		QuerySupplyOfResponse, QueryTotalSupplyRequest, QueryTotalSupplyResponse,
+	},
+	traits::Message,
+};
+use frame_support::{
+	traits::{
+		fungibles::Inspect,
+		tokens::{Fortitude, Preservation},
+		Currency,
+	},
+	BoundedVec,
+};
+use pallet_cosmos::AddressMapping;
+use pallet_cosmos_types::{
+	address::acc_address_from_bech32,
+	coin::Coin,
+	query::{paginate, PageRequest, PageResponse, QueryError, QueryHandler},
+};
+use pallet_cosmos_x_bank_types::metadata::{DenomUnit, Metadata};
+use sp_core::{Get, H160};
+use sp_runtime::{traits::Convert, SaturatedConversion};
+
+fn account_id<T: pallet_cosmos::Config>(address: &str) -> Result<T::AccountId, QueryError> {
+	let (_hrp, address_raw) =
+		acc_address_from_bech32(address).map_err(|_| QueryError::InvalidAddress)?;
+	if address_raw.len() != 20 {
+		return Err(QueryError::InvalidAddress);
+	}
+
+	Ok(T::AddressMapping::into_account_id(H160::from_slice(&address_raw)))
+}
+
+/// All known denominations with their asset ids, sorted by denomination.
+fn denoms<T: pallet_cosmos::Config>() -> Vec<(String, T::AssetId)> {
+	let mut denoms = pallet_cosmos::AssetDenomRouter::<T>::iter()
+		.filter(|(asset_id, _)| *asset_id != T::NativeAssetId::get())
+		.filter_map(|(asset_id, denom)| {
+			String::from_utf8(denom.into_inner()).ok().map(|denom| (denom, asset_id))
+		})
+		.collect::<Vec<_>>();
+	denoms.push((T::NativeDenom::get().to_string(), T::NativeAssetId::get()));
+	denoms.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+	denoms
+}
+
+fn balance_of<T: pallet_cosmos::Config>(who: &T::AccountId, asset_id: T::AssetId) -> u128 {
+	if asset_id == T::NativeAssetId::get() {
+		T::NativeAsset::free_balance(who).saturated_into()
+	} else {
+		T::Assets::balance(asset_id, who).into()
+	}
+}
+
+/// The balance that can be moved without killing the account.
+fn spendable_balance_of<T: pallet_cosmos::Config>(
+	who: &T::AccountId,
+	asset_id: T::AssetId,
+) -> u128 {
+	if asset_id == T::NativeAssetId::get() {
+		T::NativeAsset::free_balance(who)
+			.saturating_sub(T::NativeAsset::minimum_balance())
+			.saturated_into()
+	} else {
+		T::Assets::reducible_balance(asset_id, who, Preservation::Preserve, Fortitude::Polite)
+			.into()
+	}
+}
+
+fn supply_of_asset<T: pallet_cosmos::Config>(asset_id: T::AssetId) -> u128 {
+	if asset_id == T::NativeAssetId::get() {
+		T::NativeAsset::total_issuance().saturated_into()
+	} else {
+		T::Assets::total_issuance(asset_id).into()
+	}
+}
+
+fn nonzero_coins<T, F>(
+	f: F,
+	pagination: Option<PageRequest>,
+) -> Result<(Vec<Coin>, PageResponse), QueryError>
+where
+	T: pallet_cosmos::Config,
+	F: Fn(T::AssetId) -> u128,
+{
+	let coins = denoms::<T>()
+		.into_iter()
+		.filter_map(|(denom, asset_id)| {
+			let amount = f(asset_id);
+			(amount > 0).then(|| (denom.clone(), Coin { amount: amount.to_string(), denom }))
+		})
+		.collect();
+
+	paginate(coins, pagination)
+}
+
+/// The balance of `address` in `denom`, zero for unknown denominations.
+pub fn balance<T: pallet_cosmos::Config>(address: &str, denom: &str) -> Result<Coin, QueryError> {
+	let who = account_id::<T>(address)?;
+	let amount = T::AssetToDenom::convert(denom.to_string())
+		.map(|asset_id| balance_of::<T>(&who, asset_id))
+		.unwrap_or_default();
+
+	Ok(Coin { amount: amount.to_string(), denom: denom.to_string() })
+}
+
+/// The nonzero balances of `address`, sorted by denomination.
+pub fn all_balances<T: pallet_cosmos::Config>(
+	address: &str,
+	pagination: Option<PageRequest>,
+) -> Result<(Vec<Coin>, PageResponse), QueryError> {
+	let who = account_id::<T>(address)?;
+	nonzero_coins::<T, _>(|asset_id| balance_of::<T>(&who, asset_id), pagination)
+}
+
+/// The nonzero balances of `address` that it can spend, sorted by denomination.
+pub fn spendable_balances<T: pallet_cosmos::Config>(
+	address: &str,
+	pagination: Option<PageRequest>,
+) -> Result<(Vec<Coin>, PageResponse), QueryError> {
+	let who = account_id::<T>(address)?;
+	nonzero_coins::<T, _>(|asset_id| spendable_balance_of::<T>(&who, asset_id), pagination)
+}
+
+/// The nonzero supply of every denomination, sorted by denomination.
+pub fn total_supply<T: pallet_cosmos::Config>(
+	pagination: Option<PageRequest>,
+) -> Result<(Vec<Coin>, PageResponse), QueryError> {
+	nonzero_coins::<T, _>(supply_of_asset::<T>, pagination)
+}
+
+/// The supply of `denom`, zero for unknown denominations.
+pub fn supply_of<T: pallet_cosmos::Config>(denom: &str) -> Coin {
+	let amount = T::AssetToDenom::convert(denom.to_string())
+		.map(supply_of_asset::<T>)
+		.unwrap_or_default();
+
+	Coin { amount: amount.to_string(), denom: denom.to_string() }
+}
+
+/// The metadata of `denom`, derived from the asset behind it unless it has been registered.
+pub fn denom_metadata<T: pallet_cosmos::Config>(denom: &str) -> Result<Metadata, QueryError> {
+	let asset_id = T::AssetToDenom::convert(denom.to_string()).map_err(|_| QueryError::NotFound)?;
+
+	if let Some(metadata) = BoundedVec::<u8, T::MaxDenomLimit>::try_from(denom.as_bytes().to_vec())
+		.ok()
+		.and_then(pallet_cosmos::DenomMetadata::<T>::get)
+	{
+		return Ok(metadata);
+	}
+
+	if asset_id == T::NativeAssetId::get() {
+		Ok(Metadata {
+			denom_units: alloc::vec![DenomUnit { denom: denom.to_string(), ..Default::default() }],
+			base: denom.to_string(),
+			display: denom.to_string(),
+			name: denom.to_string(),
+			symbol: denom.to_string(),
+			..Default::default()
+		})
+	} else {
+		Ok(pallet_cosmos::Pallet::<T>::asset_denom_metadata(denom.to_string(), asset_id))
+	}
+}
+
+fn page_request(
+	pagination: Option<cosmos_sdk_proto::cosmos::base::query::v1beta1::PageRequest>,
+) -> Result<Option<PageRequest>, QueryError> {
+	pagination.map(PageRequest::try_from).transpose()
+}
+
+pub struct QueryBalanceHandler<T>(PhantomData<T>);
+
+impl<T> Default for QueryBalanceHandler<T> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T> QueryHandler for QueryBalanceHandler<T>
+where
+	T: pallet_cosmos::Config,
+{
+	fn handle(&self, request: &[u8]) -> Result<Vec<u8>, QueryError> {
+		let QueryBalanceRequest { address, denom } =
+			QueryBalanceRequest::decode(request).map_err(|_| QueryError::InvalidRequest)?;
+		let balance = balance::<T>(&address, &denom)?;
+
+		Ok(QueryBalanceResponse { balance: Some(balance.into()) }.encode_to_vec())
+	}
+}
+
+pub struct QueryAllBalancesHandler<T>(PhantomData<T>);
+
+impl<T> Default for QueryAllBalancesHandler<T> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T> QueryHandler for QueryAllBalancesHandler<T>
+where
+	T: pallet_cosmos::Config,
+{
+	fn handle(&self, request: &[u8]) -> Result<Vec<u8>, QueryError> {
+		let request =
+			QueryAllBalancesRequest::decode(request).map_err(|_| QueryError::InvalidRequest)?;
+		let (balances, pagination) =
+			all_balances::<T>(&request.address, page_request(request.pagination)?)?;
+
+		Ok(QueryAllBalancesResponse {
+			balances: balances.into_iter().map(Into::into).collect(),
+			pagination: Some(pagination.into()),
+		}
+		.encode_to_vec())
+	}
+}
+
+pub struct QuerySpendableBalancesHandler<T>(PhantomData<T>);
+
+impl<T> Default for QuerySpendableBalancesHandler<T> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T> QueryHandler for QuerySpendableBalancesHandler<T>
+where
+	T: pallet_cosmos::Config,
+{
+	fn handle(&self, request: &[u8]) -> Result<Vec<u8>, QueryError> {
+		let request = QuerySpendableBalancesRequest::decode(request)
+			.map_err(|_| QueryError::InvalidRequest)?;
+		let (balances, pagination) =
+			spendable_balances::<T>(&request.address, page_request(request.pagination)?)?;
+
+		Ok(QuerySpendableBalancesResponse {
+			balances: balances.into_iter().map(Into::into).collect(),
+			pagination: Some(pagination.into()),
+		}
+		.encode_to_vec())
+	}
+}
+
+pub struct QueryTotalSupplyHandler<T>(PhantomData<T>);
+
+impl<T> Default for QueryTotalSupplyHandler<T> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T> QueryHandler for QueryTotalSupplyHandler<T>
+where
+	T: pallet_cosmos::Config,
+{
+	fn handle(&self, request: &[u8]) -> Result<Vec<u8>, QueryError> {
+		let QueryTotalSupplyRequest { pagination } =
+			QueryTotalSupplyRequest::decode(request).map_err(|_| QueryError::InvalidRequest)?;
+		let (supply, pagination) = total_supply::<T>(page_request(pagination)?)?;
+
+		Ok(QueryTotalSupplyResponse {
+			supply: supply.into_iter().map(Into::into).collect(),
+			pagination: Some(pagination.into()),
+		}
+		.encode_to_vec())
+	}
+}
+
+pub struct QuerySupplyOfHandler<T>(PhantomData<T>);
+
+impl<T> Default for QuerySupplyOfHandler<T> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T> QueryHandler for QuerySupplyOfHandler<T>
+where
+	T: pallet_cosmos::Config,
+{
+	fn handle(&self, request: &[u8]) -> Result<Vec<u8>, QueryError> {
+		let QuerySupplyOfRequest { denom } =
+			QuerySupplyOfRequest::decode(request).map_err(|_| QueryError::InvalidRequest)?;
+
+		Ok(QuerySupplyOfResponse { amount: Some(supply_of::<T>(&denom).into()) }.encode_to_vec())
+	}
+}
+
+pub struct QueryDenomMetadataHandler<T>(PhantomData<T>);
+
+impl<T> Default for QueryDenomMetadataHandler<T> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T> QueryHandler for QueryDenomMetadataHandler<T>
+where
+	T: pallet_cosmos::Config,
+{
+	fn handle(&self, request: &[u8]) -> Result<Vec<u8>, QueryError> {
+		let QueryDenomMetadataRequest { denom } =
+			QueryDenomMetadataRequest::decode(request).map_err(|_| QueryError::InvalidRequest)?;
+		let metadata = denom_metadata::<T>(&denom)?;
+
+		Ok(QueryDenomMetadataResponse { metadata: Some(metadata.into()) }.encode_to_vec())
+	}
+}
@@ -0,0 +1,50 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pallet_cosmos_types::errors::CosmosError;
+
+pub const BANK_CODESPACE: u8 = 4;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum BankError {
+	NoInputs = 2,
+	NoOutputs = 3,
+	InputOutputMismatch = 4,
+	SendDisabled = 5,
+	MultipleSenders = 9,
+}
+
+impl From<BankError> for CosmosError {
+	fn from(error: BankError) -> Self {
+		CosmosError { codespace: BANK_CODESPACE, code: error as u8 }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{BankError, CosmosError};
+	use crate::errors::BANK_CODESPACE;
+
+	#[test]
+	fn bank_error_test() {
+		let error: CosmosError = BankError::InputOutputMismatch.into();
+		assert_eq!(
+			error,
+			CosmosError { codespace: BANK_CODESPACE, code: BankError::InputOutputMismatch as u8 }
+		);
+	}
+}
@@ -19,5 +19,7 @@
 
 extern crate alloc;
 
+pub mod errors;
 pub mod events;
+pub mod metadata;
 pub mod msgs;
@@ -0,0 +1,180 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::{
+	collections::BTreeSet,
+	string::{String, ToString},
+	vec,
+	vec::Vec,
+};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+
+/// A unit of a denomination, worth `10^exponent` of its base unit.
+#[derive(
+	Clone, Debug, Default, Eq, PartialEq, Decode, Encode, TypeInfo, Serialize, Deserialize,
+)]
+pub struct DenomUnit {
+	pub denom: String,
+	pub exponent: u32,
+	pub aliases: Vec<String>,
+}
+
+/// Client-facing information about a denomination.
+#[derive(
+	Clone, Debug, Default, Eq, PartialEq, Decode, Encode, TypeInfo, Serialize, Deserialize,
+)]
+pub struct Metadata {
+	pub description: String,
+	pub denom_units: Vec<DenomUnit>,
+	pub base: String,
+	pub display: String,
+	pub name: String,
+	pub symbol: String,
+	pub uri: String,
+	pub uri_hash: String,
+}
+
+/// Metric prefixes of base denominations and the exponents they stand for, as in `uatom`.
+const METRIC_PREFIXES: [(char, u8); 6] =
+	[('m', 3), ('u', 6), ('n', 9), ('p', 12), ('f', 15), ('a', 18)];
+
+impl Metadata {
+	/// Metadata of an asset whose base unit is `base` and whose display unit is worth
+	/// `10^decimals` of it.
+	///
+	/// The display unit drops the metric prefix of `base` matching `decimals`, as in
+	/// `uatom` → `atom`, or is `base` in upper case otherwise.
+	pub fn from_asset(base: String, name: String, symbol: String, decimals: u8) -> Self {
+		let mut denom_units = vec![DenomUnit { denom: base.clone(), ..Default::default() }];
+
+		let display = METRIC_PREFIXES
+			.iter()
+			.find(|(_, exponent)| *exponent == decimals)
+			.and_then(|(prefix, _)| base.strip_prefix(*prefix))
+			.filter(|display| !display.is_empty())
+			.map(ToString::to_string)
+			.unwrap_or_else(|| base.to_uppercase());
+		let display = if decimals > 0 && display != base {
+			denom_units.push(DenomUnit {
+				denom: display.clone(),
+				exponent: decimals.into(),
+				..Default::default()
+			});
+			display
+		} else {
+			base.clone()
+		};
+
+		Self { denom_units, base, display, name, symbol, ..Default::default() }
+	}
+
+	/// Checks the metadata the way cosmos-sdk does: the base unit comes first with exponent zero,
+	/// exponents increase, denominations and aliases are unique and the display unit is one of
+	/// the units.
+	pub fn is_valid(&self) -> bool {
+		if self.name.trim().is_empty() || self.symbol.trim().is_empty() {
+			return false;
+		}
+		if self.base.is_empty() || self.display.is_empty() {
+			return false;
+		}
+		match self.denom_units.first() {
+			Some(first) if first.denom == self.base && first.exponent == 0 => {},
+			_ => return false,
+		}
+
+		let mut seen = BTreeSet::new();
+		for (i, unit) in self.denom_units.iter().enumerate() {
+			if i > 0 && unit.exponent <= self.denom_units[i - 1].exponent {
+				return false;
+			}
+			if !seen.insert(&unit.denom) {
+				return false;
+			}
+			for alias in unit.aliases.iter() {
+				if alias.trim().is_empty() || !seen.insert(alias) {
+					return false;
+				}
+			}
+		}
+
+		self.denom_units.iter().any(|unit| unit.denom == self.display)
+	}
+}
+
+impl From<DenomUnit> for cosmos_sdk_proto::cosmos::bank::v1beta1::DenomUnit {
+	fn from(unit: DenomUnit) -> Self {
+		Self { denom: unit.denom, exponent: unit.exponent, aliases: unit.aliases }
+	}
+}
+
+impl From<Metadata> for cosmos_sdk_proto::cosmos::bank::v1beta1::Metadata {
+	fn from(metadata: Metadata) -> Self {
+		Self {
+			description: metadata.description,
+			denom_units: metadata.denom_units.into_iter().map(Into::into).collect(),
+			base: metadata.base,
+			display: metadata.display,
+			name: metadata.name,
+			symbol: metadata.symbol,
+			uri: metadata.uri,
+			uri_hash: metadata.uri_hash,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn metadata_from_asset_test() {
+		let metadata = Metadata::from_asset("uatom".into(), "Atom".into(), "ATOM".into(), 6);
+		assert_eq!(metadata.display, "atom");
+		assert_eq!(
+			metadata.denom_units[1],
+			DenomUnit { denom: "atom".into(), exponent: 6, aliases: vec![] }
+		);
+		assert!(metadata.is_valid());
+
+		let metadata = Metadata::from_asset("acdt".into(), "CDT".into(), "acdt".into(), 0);
+		assert_eq!(metadata.display, "acdt");
+		assert_eq!(metadata.denom_units.len(), 1);
+		assert!(metadata.is_valid());
+
+		let metadata = Metadata::from_asset("cdt".into(), "CDT".into(), "cdt".into(), 18);
+		assert_eq!(metadata.display, "CDT");
+		assert!(metadata.is_valid());
+	}
+
+	#[test]
+	fn metadata_validate_test() {
+		let mut metadata = Metadata::from_asset("uatom".into(), "Atom".into(), "ATOM".into(), 6);
+		metadata.display = "matom".into();
+		assert!(!metadata.is_valid());
+
+		let mut metadata = Metadata::from_asset("uatom".into(), "Atom".into(), "ATOM".into(), 6);
+		metadata.denom_units[1].exponent = 0;
+		assert!(!metadata.is_valid());
+
+		let mut metadata = Metadata::from_asset("uatom".into(), "Atom".into(), "ATOM".into(), 6);
+		metadata.denom_units[1].aliases = vec!["uatom".into()];
+		assert!(!metadata.is_valid());
+	}
+}
@@ -15,4 +15,5 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod msg_multi_send;
 pub mod msg_send;
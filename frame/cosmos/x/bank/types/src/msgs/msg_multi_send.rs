@@ -0,0 +1,83 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::{string::String, vec::Vec};
+use cosmos_sdk_proto::{cosmos::bank::v1beta1, prost::Message, Any};
+use pallet_cosmos_types::{coin::Coin, tx_msgs::Msg};
+use pallet_cosmos_x_auth_migrations::legacytx::stdsign::LegacyMsg;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Input {
+	pub address: String,
+	pub coins: Vec<Coin>,
+}
+
+impl From<&v1beta1::Input> for Input {
+	fn from(input: &v1beta1::Input) -> Self {
+		Self { address: input.address.clone(), coins: input.coins.iter().map(Into::into).collect() }
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Output {
+	pub address: String,
+	pub coins: Vec<Coin>,
+}
+
+impl From<&v1beta1::Output> for Output {
+	fn from(output: &v1beta1::Output) -> Self {
+		Self {
+			address: output.address.clone(),
+			coins: output.coins.iter().map(Into::into).collect(),
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MsgMultiSend {
+	pub inputs: Vec<Input>,
+	pub outputs: Vec<Output>,
+}
+
+impl TryFrom<&Any> for MsgMultiSend {
+	type Error = ();
+
+	fn try_from(any: &Any) -> Result<Self, Self::Error> {
+		let msg = v1beta1::MsgMultiSend::decode(&mut &*any.value).map_err(|_| ())?;
+		Ok(Self {
+			inputs: msg.inputs.iter().map(Into::into).collect(),
+			outputs: msg.outputs.iter().map(Into::into).collect(),
+		})
+	}
+}
+
+impl Msg for MsgMultiSend {
+	fn get_signers(self) -> Vec<String> {
+		let mut signers = Vec::<String>::new();
+		for input in self.inputs.into_iter() {
+			if !signers.contains(&input.address) {
+				signers.push(input.address);
+			}
+		}
+		signers
+	}
+}
+
+impl LegacyMsg for MsgMultiSend {
+	const AMINO_NAME: &'static str = "cosmos-sdk/MsgMultiSend";
+}
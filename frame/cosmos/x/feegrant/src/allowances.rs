@@ -0,0 +1,337 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+use cosmos_sdk_proto::{cosmos::feegrant::v1beta1, prost::Message, Any};
+use pallet_cosmos_types::{
+	any_match,
+	coin::{coins_from, safe_sub, Coins},
+	errors::{CosmosError, RootError},
+};
+use pallet_cosmos_x_feegrant_types::errors::FeegrantError;
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// Timestamps and durations are kept in seconds.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub enum FeeAllowance {
+	Basic(BasicAllowance),
+	Periodic(PeriodicAllowance),
+	AllowedMsg(AllowedMsgAllowance),
+}
+
+impl FeeAllowance {
+	/// Checks whether `fee` may be paid for `msgs` at `now` and deducts it from the allowance.
+	/// Returns `true` when the allowance is used up and should be removed.
+	pub fn accept(&mut self, fee: &Coins, msgs: &[Any], now: u64) -> Result<bool, FeegrantError> {
+		match self {
+			Self::Basic(allowance) => allowance.accept(fee, now),
+			Self::Periodic(allowance) => allowance.accept(fee, now),
+			Self::AllowedMsg(allowance) => allowance.accept(fee, msgs, now),
+		}
+	}
+
+	pub fn expiration(&self) -> Option<u64> {
+		match self {
+			Self::Basic(allowance) => allowance.expiration,
+			Self::Periodic(allowance) => allowance.basic.expiration,
+			Self::AllowedMsg(allowance) => allowance.allowance.expiration(),
+		}
+	}
+
+	/// Starts the first period of a periodic allowance granted at `now`.
+	pub fn update_period_reset(&mut self, now: u64) {
+		match self {
+			Self::Basic(_) => {},
+			Self::Periodic(allowance) =>
+				if allowance.period_reset == 0 {
+					allowance.period_reset = now.saturating_add(allowance.period);
+				},
+			Self::AllowedMsg(allowance) => allowance.allowance.update_period_reset(now),
+		}
+	}
+}
+
+impl TryFrom<&Any> for FeeAllowance {
+	type Error = CosmosError;
+
+	fn try_from(any: &Any) -> Result<Self, Self::Error> {
+		any_match!(
+			any, {
+				v1beta1::BasicAllowance => v1beta1::BasicAllowance::decode(&mut &*any.value)
+					.map_err(|_| RootError::UnpackAnyError.into())
+					.and_then(|allowance| (&allowance).try_into().map(Self::Basic)),
+				v1beta1::PeriodicAllowance => v1beta1::PeriodicAllowance::decode(&mut &*any.value)
+					.map_err(|_| RootError::UnpackAnyError.into())
+					.and_then(|allowance| (&allowance).try_into().map(Self::Periodic)),
+				v1beta1::AllowedMsgAllowance => v1beta1::AllowedMsgAllowance::decode(&mut &*any.value)
+					.map_err(|_| RootError::UnpackAnyError.into())
+					.and_then(|allowance| (&allowance).try_into().map(Self::AllowedMsg)),
+			},
+			Err(RootError::UnpackAnyError.into())
+		)
+	}
+}
+
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct BasicAllowance {
+	/// The maximum amount the grantee may spend. Empty means no limit.
+	pub spend_limit: Coins,
+	pub expiration: Option<u64>,
+}
+
+impl BasicAllowance {
+	fn accept(&mut self, fee: &Coins, now: u64) -> Result<bool, FeegrantError> {
+		if self.expiration.is_some_and(|expiration| expiration < now) {
+			return Err(FeegrantError::FeeLimitExpired);
+		}
+
+		if !self.spend_limit.is_empty() {
+			self.spend_limit =
+				safe_sub(&self.spend_limit, fee).ok_or(FeegrantError::FeeLimitExceeded)?;
+
+			return Ok(self.spend_limit.is_empty());
+		}
+
+		Ok(false)
+	}
+}
+
+impl TryFrom<&v1beta1::BasicAllowance> for BasicAllowance {
+	type Error = CosmosError;
+
+	fn try_from(allowance: &v1beta1::BasicAllowance) -> Result<Self, Self::Error> {
+		let expiration = allowance
+			.expiration
+			.as_ref()
+			.map(|expiration| u64::try_from(expiration.seconds))
+			.transpose()
+			.map_err(|_| RootError::InvalidRequest)?;
+
+		Ok(Self { spend_limit: coins_from(&allowance.spend_limit)?, expiration })
+	}
+}
+
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct PeriodicAllowance {
+	pub basic: BasicAllowance,
+	pub period: u64,
+	pub period_spend_limit: Coins,
+	pub period_can_spend: Coins,
+	pub period_reset: u64,
+}
+
+impl PeriodicAllowance {
+	fn accept(&mut self, fee: &Coins, now: u64) -> Result<bool, FeegrantError> {
+		if self.basic.expiration.is_some_and(|expiration| expiration < now) {
+			return Err(FeegrantError::FeeLimitExpired);
+		}
+
+		self.try_reset_period(now);
+
+		self.period_can_spend =
+			safe_sub(&self.period_can_spend, fee).ok_or(FeegrantError::FeeLimitExceeded)?;
+
+		if !self.basic.spend_limit.is_empty() {
+			self.basic.spend_limit =
+				safe_sub(&self.basic.spend_limit, fee).ok_or(FeegrantError::FeeLimitExceeded)?;
+
+			return Ok(self.basic.spend_limit.is_empty());
+		}
+
+		Ok(false)
+	}
+
+	fn try_reset_period(&mut self, now: u64) {
+		if now < self.period_reset {
+			return;
+		}
+
+		self.period_can_spend = if self.basic.spend_limit.is_empty() {
+			self.period_spend_limit.clone()
+		} else {
+			self.period_spend_limit
+				.iter()
+				.filter_map(|(denom, amount)| {
+					let left = self.basic.spend_limit.get(denom)?;
+					Some((denom.clone(), *amount.min(left)))
+				})
+				.collect()
+		};
+
+		self.period_reset = self.period_reset.saturating_add(self.period);
+		if now > self.period_reset {
+			self.period_reset = now.saturating_add(self.period);
+		}
+	}
+}
+
+impl TryFrom<&v1beta1::PeriodicAllowance> for PeriodicAllowance {
+	type Error = CosmosError;
+
+	fn try_from(allowance: &v1beta1::PeriodicAllowance) -> Result<Self, Self::Error> {
+		let basic = allowance.basic.as_ref().ok_or(RootError::InvalidRequest)?;
+		let basic = BasicAllowance::try_from(basic)?;
+
+		let period_spend_limit = coins_from(&allowance.period_spend_limit)?;
+		if period_spend_limit.is_empty() {
+			return Err(RootError::InvalidCoins.into());
+		}
+		if !basic.spend_limit.is_empty() &&
+			!period_spend_limit.keys().all(|denom| basic.spend_limit.contains_key(denom))
+		{
+			return Err(RootError::InvalidCoins.into());
+		}
+
+		let period = allowance
+			.period
+			.as_ref()
+			.and_then(|period| u64::try_from(period.seconds).ok())
+			.ok_or(FeegrantError::InvalidDuration)?;
+		let period_reset = allowance
+			.period_reset
+			.as_ref()
+			.map_or(Ok(0), |period_reset| u64::try_from(period_reset.seconds))
+			.map_err(|_| RootError::InvalidRequest)?;
+
+		Ok(Self {
+			basic,
+			period,
+			period_spend_limit,
+			period_can_spend: coins_from(&allowance.period_can_spend)?,
+			period_reset,
+		})
+	}
+}
+
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct AllowedMsgAllowance {
+	pub allowance: Box<FeeAllowance>,
+	/// Type URLs of the messages the allowance may pay for.
+	pub allowed_messages: Vec<String>,
+}
+
+impl AllowedMsgAllowance {
+	fn accept(&mut self, fee: &Coins, msgs: &[Any], now: u64) -> Result<bool, FeegrantError> {
+		if !msgs.iter().all(|msg| self.allowed_messages.contains(&msg.type_url)) {
+			return Err(FeegrantError::MessageNotAllowed);
+		}
+
+		self.allowance.accept(fee, msgs, now)
+	}
+}
+
+impl TryFrom<&v1beta1::AllowedMsgAllowance> for AllowedMsgAllowance {
+	type Error = CosmosError;
+
+	fn try_from(allowance: &v1beta1::AllowedMsgAllowance) -> Result<Self, Self::Error> {
+		if allowance.allowed_messages.is_empty() {
+			return Err(FeegrantError::NoMessages.into());
+		}
+
+		let inner = allowance.allowance.as_ref().ok_or(RootError::InvalidRequest)?;
+		let inner = FeeAllowance::try_from(inner)?;
+		if matches!(inner, FeeAllowance::AllowedMsg(_)) {
+			return Err(RootError::InvalidRequest.into());
+		}
+
+		Ok(Self {
+			allowance: Box::new(inner),
+			allowed_messages: allowance.allowed_messages.clone(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::vec;
+
+	fn coins(coins: &[(&str, u128)]) -> Coins {
+		coins.iter().map(|(denom, amount)| (denom.to_string(), *amount)).collect()
+	}
+
+	#[test]
+	fn basic_allowance_test() {
+		let mut allowance = FeeAllowance::Basic(BasicAllowance {
+			spend_limit: coins(&[("acdt", 100)]),
+			expiration: Some(1_000),
+		});
+
+		assert_eq!(allowance.accept(&coins(&[("acdt", 40)]), &[], 500), Ok(false));
+		assert_eq!(
+			allowance.accept(&coins(&[("acdt", 70)]), &[], 500),
+			Err(FeegrantError::FeeLimitExceeded)
+		);
+		assert_eq!(
+			allowance.accept(&coins(&[("uatom", 1)]), &[], 500),
+			Err(FeegrantError::FeeLimitExceeded)
+		);
+		assert_eq!(
+			allowance.accept(&coins(&[("acdt", 10)]), &[], 1_001),
+			Err(FeegrantError::FeeLimitExpired)
+		);
+		assert_eq!(allowance.accept(&coins(&[("acdt", 60)]), &[], 1_000), Ok(true));
+	}
+
+	#[test]
+	fn periodic_allowance_test() {
+		let mut allowance = FeeAllowance::Periodic(PeriodicAllowance {
+			basic: BasicAllowance { spend_limit: coins(&[("acdt", 100)]), expiration: None },
+			period: 10,
+			period_spend_limit: coins(&[("acdt", 30)]),
+			period_can_spend: Coins::new(),
+			period_reset: 0,
+		});
+		allowance.update_period_reset(0);
+
+		assert_eq!(
+			allowance.accept(&coins(&[("acdt", 10)]), &[], 5),
+			Err(FeegrantError::FeeLimitExceeded)
+		);
+		assert_eq!(allowance.accept(&coins(&[("acdt", 30)]), &[], 10), Ok(false));
+		assert_eq!(
+			allowance.accept(&coins(&[("acdt", 1)]), &[], 15),
+			Err(FeegrantError::FeeLimitExceeded)
+		);
+		assert_eq!(allowance.accept(&coins(&[("acdt", 30)]), &[], 20), Ok(false));
+		assert_eq!(allowance.accept(&coins(&[("acdt", 30)]), &[], 100), Ok(false));
+		assert_eq!(allowance.accept(&coins(&[("acdt", 10)]), &[], 200), Ok(true));
+	}
+
+	#[test]
+	fn allowed_msg_allowance_test() {
+		let mut allowance = FeeAllowance::AllowedMsg(AllowedMsgAllowance {
+			allowance: Box::new(FeeAllowance::Basic(BasicAllowance {
+				spend_limit: Coins::new(),
+				expiration: None,
+			})),
+			allowed_messages: vec!["/cosmos.bank.v1beta1.MsgSend".into()],
+		});
+
+		let msg_send = Any { type_url: "/cosmos.bank.v1beta1.MsgSend".into(), value: vec![] };
+		let msg_store_code =
+			Any { type_url: "/cosmwasm.wasm.v1.MsgStoreCode".into(), value: vec![] };
+
+		assert_eq!(allowance.accept(&coins(&[("acdt", 10)]), &[msg_send.clone()], 0), Ok(false));
+		assert_eq!(
+			allowance.accept(&coins(&[("acdt", 10)]), &[msg_send, msg_store_code], 0),
+			Err(FeegrantError::MessageNotAllowed)
+		);
+	}
+}
@@ -0,0 +1,118 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::comparison_chain, clippy::large_enum_variant)]
+
+extern crate alloc;
+
+pub mod allowances;
+pub mod msgs;
+
+pub use pallet::*;
+
+use crate::allowances::FeeAllowance;
+use alloc::vec;
+use cosmos_sdk_proto::{cosmos::base::v1beta1::Coin, Any};
+use frame_support::traits::UnixTime;
+use pallet_cosmos::AddressMapping;
+use pallet_cosmos_types::{
+	address::acc_address_from_bech32,
+	coin::coins_from,
+	errors::{CosmosError, RootError},
+	events::{CosmosEvent, EventAttribute},
+};
+use pallet_cosmos_x_feegrant_types::{
+	errors::FeegrantError,
+	events::{ATTRIBUTE_KEY_GRANTEE, ATTRIBUTE_KEY_GRANTER, EVENT_TYPE_USE_FEEGRANT},
+};
+use sp_core::H160;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_cosmos::Config {
+		/// Time used to check allowance expirations and periods.
+		type UnixTime: UnixTime;
+	}
+
+	/// Fee allowances, keyed by granter and grantee.
+	#[pallet::storage]
+	pub type Allowances<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		FeeAllowance,
+	>;
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn account_id(address: &str) -> Result<T::AccountId, CosmosError> {
+		let (_hrp, address_raw) =
+			acc_address_from_bech32(address).map_err(|_| RootError::InvalidAddress)?;
+		if address_raw.len() != 20 {
+			return Err(RootError::InvalidAddress.into());
+		}
+
+		Ok(T::AddressMapping::into_account_id(H160::from_slice(&address_raw)))
+	}
+
+	pub fn now() -> u64 {
+		T::UnixTime::now().as_secs()
+	}
+}
+
+impl<T: Config> pallet_cosmos::FeeGrant for Pallet<T> {
+	fn use_granted_fees(
+		granter: &str,
+		grantee: &str,
+		fee: &[Coin],
+		msgs: &[Any],
+	) -> Result<(), CosmosError> {
+		let granter_account = Self::account_id(granter)?;
+		let grantee_account = Self::account_id(grantee)?;
+
+		let mut allowance = Allowances::<T>::get(&granter_account, &grantee_account)
+			.ok_or(FeegrantError::NoAllowance)?;
+		let remove = allowance.accept(&coins_from(fee)?, msgs, Self::now())?;
+
+		if remove {
+			Allowances::<T>::remove(&granter_account, &grantee_account);
+		} else {
+			Allowances::<T>::insert(&granter_account, &grantee_account, allowance);
+		}
+
+		pallet_cosmos::Pallet::<T>::deposit_ante_events(vec![CosmosEvent {
+			r#type: EVENT_TYPE_USE_FEEGRANT.into(),
+			attributes: vec![
+				EventAttribute { key: ATTRIBUTE_KEY_GRANTER.into(), value: granter.into() },
+				EventAttribute { key: ATTRIBUTE_KEY_GRANTEE.into(), value: grantee.into() },
+			],
+		}]);
+
+		Ok(())
+	}
+}
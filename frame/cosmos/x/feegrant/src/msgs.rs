@@ -0,0 +1,146 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{allowances::FeeAllowance, Allowances};
+use alloc::vec;
+use core::marker::PhantomData;
+use cosmos_sdk_proto::{
+	cosmos::feegrant::v1beta1::{MsgGrantAllowance, MsgRevokeAllowance},
+	traits::Message,
+	Any,
+};
+use frame_support::traits::Get;
+use pallet_cosmos_types::{
+	context,
+	errors::{CosmosError, RootError},
+	events::{traits::EventManager, CosmosEvent, EventAttribute},
+	gas::traits::GasMeter,
+};
+use pallet_cosmos_x_feegrant_types::{
+	errors::FeegrantError,
+	events::{
+		ATTRIBUTE_KEY_GRANTEE, ATTRIBUTE_KEY_GRANTER, EVENT_TYPE_REVOKE_FEEGRANT,
+		EVENT_TYPE_SET_FEEGRANT,
+	},
+};
+
+pub struct MsgGrantAllowanceHandler<T>(PhantomData<T>);
+
+impl<T> Default for MsgGrantAllowanceHandler<T> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T, Context> pallet_cosmos_types::msgservice::MsgHandler<Context>
+	for MsgGrantAllowanceHandler<T>
+where
+	T: crate::Config,
+	Context: context::traits::Context,
+{
+	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
+		let MsgGrantAllowance { granter, grantee, allowance } =
+			MsgGrantAllowance::decode(&mut &*msg.value).map_err(|_| RootError::UnpackAnyError)?;
+
+		if granter == grantee {
+			return Err(RootError::InvalidAddress.into());
+		}
+		let granter_account = crate::Pallet::<T>::account_id(&granter)?;
+		let grantee_account = crate::Pallet::<T>::account_id(&grantee)?;
+
+		let mut allowance =
+			FeeAllowance::try_from(allowance.as_ref().ok_or(RootError::InvalidRequest)?)?;
+
+		ctx.gas_meter()
+			.consume_gas(T::DbWeight::get().reads(1).ref_time(), "ReadFlat")
+			.map_err(|_| RootError::OutOfGas)?;
+
+		if Allowances::<T>::contains_key(&granter_account, &grantee_account) {
+			return Err(RootError::InvalidRequest.into());
+		}
+
+		let now = crate::Pallet::<T>::now();
+		if allowance.expiration().is_some_and(|expiration| expiration < now) {
+			return Err(FeegrantError::FeeLimitExpired.into());
+		}
+		allowance.update_period_reset(now);
+
+		Allowances::<T>::insert(&granter_account, &grantee_account, allowance);
+
+		ctx.gas_meter()
+			.consume_gas(T::DbWeight::get().writes(1).ref_time(), "WriteFlat")
+			.map_err(|_| RootError::OutOfGas)?;
+
+		ctx.event_manager().emit_event(CosmosEvent {
+			r#type: EVENT_TYPE_SET_FEEGRANT.into(),
+			attributes: vec![
+				EventAttribute { key: ATTRIBUTE_KEY_GRANTER.into(), value: granter.into() },
+				EventAttribute { key: ATTRIBUTE_KEY_GRANTEE.into(), value: grantee.into() },
+			],
+		});
+
+		Ok(())
+	}
+}
+
+pub struct MsgRevokeAllowanceHandler<T>(PhantomData<T>);
+
+impl<T> Default for MsgRevokeAllowanceHandler<T> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T, Context> pallet_cosmos_types::msgservice::MsgHandler<Context>
+	for MsgRevokeAllowanceHandler<T>
+where
+	T: crate::Config,
+	Context: context::traits::Context,
+{
+	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
+		let MsgRevokeAllowance { granter, grantee } =
+			MsgRevokeAllowance::decode(&mut &*msg.value).map_err(|_| RootError::UnpackAnyError)?;
+
+		let granter_account = crate::Pallet::<T>::account_id(&granter)?;
+		let grantee_account = crate::Pallet::<T>::account_id(&grantee)?;
+
+		ctx.gas_meter()
+			.consume_gas(T::DbWeight::get().reads(1).ref_time(), "ReadFlat")
+			.map_err(|_| RootError::OutOfGas)?;
+
+		if !Allowances::<T>::contains_key(&granter_account, &grantee_account) {
+			return Err(FeegrantError::NoAllowance.into());
+		}
+
+		Allowances::<T>::remove(&granter_account, &grantee_account);
+
+		ctx.gas_meter()
+			.consume_gas(T::DbWeight::get().writes(1).ref_time(), "WriteFlat")
+			.map_err(|_| RootError::OutOfGas)?;
+
+		ctx.event_manager().emit_event(CosmosEvent {
+			r#type: EVENT_TYPE_REVOKE_FEEGRANT.into(),
+			attributes: vec![
+				EventAttribute { key: ATTRIBUTE_KEY_GRANTER.into(), value: granter.into() },
+				EventAttribute { key: ATTRIBUTE_KEY_GRANTEE.into(), value: grantee.into() },
+			],
+		});
+
+		Ok(())
+	}
+}
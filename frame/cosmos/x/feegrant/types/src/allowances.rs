@@ -0,0 +1,125 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+use cosmos_sdk_proto::{cosmos::feegrant::v1beta1, prost::Message, Any};
+use pallet_cosmos_types::{any_match, coin::Coin};
+use pallet_cosmos_x_auth_migrations::legacytx::stdsign::{format_timestamp, ZERO_TIME_SECONDS};
+use serde::{Deserialize, Serialize};
+
+/// Amino JSON representation of a fee allowance.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum FeeAllowance {
+	#[serde(rename = "cosmos-sdk/BasicAllowance")]
+	Basic(BasicAllowance),
+	#[serde(rename = "cosmos-sdk/PeriodicAllowance")]
+	Periodic(PeriodicAllowance),
+	#[serde(rename = "cosmos-sdk/AllowedMsgAllowance")]
+	AllowedMsg(AllowedMsgAllowance),
+}
+
+impl TryFrom<&Any> for FeeAllowance {
+	type Error = ();
+
+	fn try_from(any: &Any) -> Result<Self, Self::Error> {
+		any_match!(
+			any, {
+				v1beta1::BasicAllowance => v1beta1::BasicAllowance::decode(&mut &*any.value)
+					.map(|allowance| Self::Basic((&allowance).into()))
+					.map_err(|_| ()),
+				v1beta1::PeriodicAllowance => v1beta1::PeriodicAllowance::decode(&mut &*any.value)
+					.map(|allowance| Self::Periodic((&allowance).into()))
+					.map_err(|_| ()),
+				v1beta1::AllowedMsgAllowance => v1beta1::AllowedMsgAllowance::decode(&mut &*any.value)
+					.map_err(|_| ())
+					.and_then(|allowance| (&allowance).try_into().map(Self::AllowedMsg)),
+			},
+			Err(())
+		)
+	}
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BasicAllowance {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub expiration: Option<String>,
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub spend_limit: Vec<Coin>,
+}
+
+impl From<&v1beta1::BasicAllowance> for BasicAllowance {
+	fn from(allowance: &v1beta1::BasicAllowance) -> Self {
+		Self {
+			expiration: allowance
+				.expiration
+				.as_ref()
+				.map(|expiration| format_timestamp(expiration.seconds, expiration.nanos)),
+			spend_limit: allowance.spend_limit.iter().map(Into::into).collect(),
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeriodicAllowance {
+	pub basic: BasicAllowance,
+	/// The period in nanoseconds.
+	pub period: String,
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub period_can_spend: Vec<Coin>,
+	pub period_reset: String,
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub period_spend_limit: Vec<Coin>,
+}
+
+impl From<&v1beta1::PeriodicAllowance> for PeriodicAllowance {
+	fn from(allowance: &v1beta1::PeriodicAllowance) -> Self {
+		let period = allowance.period.as_ref().map_or(0, |period| {
+			i128::from(period.seconds) * 1_000_000_000 + i128::from(period.nanos)
+		});
+		let (seconds, nanos) = allowance
+			.period_reset
+			.as_ref()
+			.map_or((ZERO_TIME_SECONDS, 0), |reset| (reset.seconds, reset.nanos));
+
+		Self {
+			basic: allowance.basic.as_ref().map(Into::into).unwrap_or_default(),
+			period: format!("{}", period),
+			period_can_spend: allowance.period_can_spend.iter().map(Into::into).collect(),
+			period_reset: format_timestamp(seconds, nanos),
+			period_spend_limit: allowance.period_spend_limit.iter().map(Into::into).collect(),
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllowedMsgAllowance {
+	pub allowance: Box<FeeAllowance>,
+	pub allowed_messages: Vec<String>,
+}
+
+impl TryFrom<&v1beta1::AllowedMsgAllowance> for AllowedMsgAllowance {
+	type Error = ();
+
+	fn try_from(allowance: &v1beta1::AllowedMsgAllowance) -> Result<Self, Self::Error> {
+		let inner = allowance.allowance.as_ref().ok_or(())?;
+		Ok(Self {
+			allowance: Box::new(inner.try_into()?),
+			allowed_messages: allowance.allowed_messages.clone(),
+		})
+	}
+}
@@ -0,0 +1,54 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pallet_cosmos_types::errors::CosmosError;
+
+pub const FEEGRANT_CODESPACE: u8 = 2;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum FeegrantError {
+	FeeLimitExceeded = 2,
+	FeeLimitExpired = 3,
+	InvalidDuration = 4,
+	NoAllowance = 5,
+	NoMessages = 6,
+	MessageNotAllowed = 7,
+}
+
+impl From<FeegrantError> for CosmosError {
+	fn from(error: FeegrantError) -> Self {
+		CosmosError { codespace: FEEGRANT_CODESPACE, code: error as u8 }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{CosmosError, FeegrantError};
+	use crate::errors::FEEGRANT_CODESPACE;
+
+	#[test]
+	fn feegrant_error_test() {
+		let error: CosmosError = FeegrantError::FeeLimitExceeded.into();
+		assert_eq!(
+			error,
+			CosmosError {
+				codespace: FEEGRANT_CODESPACE,
+				code: FeegrantError::FeeLimitExceeded as u8
+			}
+		);
+	}
+}
@@ -0,0 +1,23 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub const EVENT_TYPE_SET_FEEGRANT: &str = "set_feegrant";
+pub const EVENT_TYPE_REVOKE_FEEGRANT: &str = "revoke_feegrant";
+pub const EVENT_TYPE_USE_FEEGRANT: &str = "use_feegrant";
+
+pub const ATTRIBUTE_KEY_GRANTER: &str = "granter";
+pub const ATTRIBUTE_KEY_GRANTEE: &str = "grantee";
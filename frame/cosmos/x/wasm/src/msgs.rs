@@ -48,7 +48,7 @@ use pallet_cosmos_x_wasm_types::{
 	},
 };
 use pallet_cosmwasm::{
-	runtimes::vm::InitialStorageMutability,
+	runtimes::vm::{CosmwasmVMShared, InitialStorageMutability},
 	types::{
 		CodeIdentifier, ContractCodeOf, ContractLabelOf, ContractMessageOf, ContractSaltOf, FundsOf,
 	},
@@ -125,7 +125,6 @@ where
 	T::AccountId: EcdsaExt,
 	Context: context::traits::Context,
 {
-	// TODO: Consume gas
 	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
 		// TODO: Ignore fix_msg
 		let MsgInstantiateContract2 { sender, admin, code_id, label, msg, funds, salt, fix_msg: _ } =
@@ -162,7 +161,7 @@ where
 		let funds = convert_funds::<T>(&funds)?;
 		let message: ContractMessageOf<T> = msg.try_into().map_err(|_| RootError::TxDecodeError)?;
 
-		let contract = pallet_cosmwasm::Pallet::<T>::do_instantiate(
+		let result = pallet_cosmwasm::Pallet::<T>::do_instantiate(
 			&mut shared,
 			who,
 			code_identifier,
@@ -171,9 +170,14 @@ where
 			label,
 			funds,
 			message,
-		)
-		.map_err(|_| WasmError::InstantiateFailed)?;
+		);
+		ctx.gas_meter()
+			.consume_gas(gas.saturating_sub(shared.gas.remaining()), "wasm execution")
+			.map_err(|_| RootError::OutOfGas)?;
+		let contract = result.map_err(|_| WasmError::InstantiateFailed)?;
 		let contract = T::AccountToAddr::convert(contract);
+		emit_transfer_events(ctx, &shared);
+		emit_contract_events(ctx, &shared);
 
 		// TODO: Same events emitted pallet_cosmos and pallet_cosmwasm
 		let msg_event = CosmosEvent {
@@ -207,7 +211,6 @@ where
 	T::AccountId: EcdsaExt,
 	Context: context::traits::Context,
 {
-	// TODO: Consume gas
 	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
 		let MsgExecuteContract { sender, contract, msg, funds } =
 			MsgExecuteContract::decode(&mut &*msg.value).map_err(|_| RootError::TxDecodeError)?;
@@ -232,14 +235,19 @@ where
 		let funds: FundsOf<T> = convert_funds::<T>(&funds)?;
 		let message: ContractMessageOf<T> = msg.try_into().map_err(|_| RootError::TxDecodeError)?;
 
-		pallet_cosmwasm::Pallet::<T>::do_execute(
+		let result = pallet_cosmwasm::Pallet::<T>::do_execute(
 			&mut shared,
 			who,
 			contract_account,
 			funds,
 			message,
-		)
-		.map_err(|_| WasmError::ExecuteFailed)?;
+		);
+		ctx.gas_meter()
+			.consume_gas(gas.saturating_sub(shared.gas.remaining()), "wasm execution")
+			.map_err(|_| RootError::OutOfGas)?;
+		result.map_err(|_| WasmError::ExecuteFailed)?;
+		emit_transfer_events(ctx, &shared);
+		emit_contract_events(ctx, &shared);
 
 		// TODO: Same events emitted pallet_cosmos and pallet_cosmwasm
 		let msg_event = CosmosEvent {
@@ -293,14 +301,19 @@ where
 		let new_code_identifier = CodeIdentifier::CodeId(code_id);
 		let message: ContractMessageOf<T> = msg.try_into().map_err(|_| RootError::TxDecodeError)?;
 
-		pallet_cosmwasm::Pallet::<T>::do_migrate(
+		let result = pallet_cosmwasm::Pallet::<T>::do_migrate(
 			&mut shared,
 			who,
 			contract_account,
 			new_code_identifier,
 			message,
-		)
-		.map_err(|_| WasmError::MigrationFailed)?;
+		);
+		ctx.gas_meter()
+			.consume_gas(gas.saturating_sub(shared.gas.remaining()), "wasm execution")
+			.map_err(|_| RootError::OutOfGas)?;
+		result.map_err(|_| WasmError::MigrationFailed)?;
+		emit_transfer_events(ctx, &shared);
+		emit_contract_events(ctx, &shared);
 
 		// TODO: Same events emitted pallet_cosmos and pallet_cosmwasm
 		let msg_event = CosmosEvent {
@@ -363,13 +376,16 @@ where
 		let contract_account =
 			T::AccountToAddr::convert(contract.clone()).map_err(|_| RootError::TxDecodeError)?;
 
-		pallet_cosmwasm::Pallet::<T>::do_update_admin(
+		let result = pallet_cosmwasm::Pallet::<T>::do_update_admin(
 			&mut shared,
 			who,
 			contract_account,
 			new_admin_account,
-		)
-		.map_err(|_| WasmError::MigrationFailed)?;
+		);
+		ctx.gas_meter()
+			.consume_gas(gas.saturating_sub(shared.gas.remaining()), "wasm execution")
+			.map_err(|_| RootError::OutOfGas)?;
+		result.map_err(|_| WasmError::MigrationFailed)?;
 
 		// TODO: Same events emitted pallet_cosmos and pallet_cosmwasm
 		let msg_event = CosmosEvent {
@@ -386,6 +402,45 @@ where
 	}
 }
 
+/// Emits the bank events of the funds moved by the contracts.
+fn emit_transfer_events<Context>(ctx: &mut Context, shared: &CosmwasmVMShared)
+where
+	Context: context::traits::Context,
+{
+	for (from, to, funds) in shared.transfers.iter() {
+		let amount = funds
+			.iter()
+			.map(|coin| Coin { denom: coin.denom.clone(), amount: coin.amount.to_string() })
+			.collect::<Vec<_>>();
+
+		ctx.event_manager().emit_events(vec![
+			CosmosEvent::coin_spent(from, &amount),
+			CosmosEvent::coin_received(to, &amount),
+		]);
+	}
+}
+
+/// Emits the events of the contracts, which already follow the `wasm` and `wasm-*` conventions
+/// of wasmd.
+fn emit_contract_events<Context>(ctx: &mut Context, shared: &CosmwasmVMShared)
+where
+	Context: context::traits::Context,
+{
+	for event in shared.events.iter() {
+		ctx.event_manager().emit_event(CosmosEvent {
+			r#type: event.ty.clone().into(),
+			attributes: event
+				.attributes
+				.iter()
+				.map(|attribute| EventAttribute {
+					key: attribute.key.clone().into(),
+					value: attribute.value.clone().into(),
+				})
+				.collect(),
+		});
+	}
+}
+
 fn convert_funds<T: pallet_cosmwasm::Config>(coins: &[Coin]) -> Result<FundsOf<T>, CosmosError> {
 	// TODO: Handle native asset
 	let mut funds = FundsOf::<T>::default();
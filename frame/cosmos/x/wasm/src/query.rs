@@ -0,0 +1,62 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use cosmos_sdk_proto::{
+	cosmwasm::wasm::v1::{QuerySmartContractStateRequest, QuerySmartContractStateResponse},
+	prost::Message,
+};
+use cosmwasm_std::{Empty, QueryRequest, WasmQuery};
+use pallet_cosmos_types::query::{QueryError, QueryHandler};
+use sp_runtime::traits::Convert;
+
+/// The gas limit of smart queries, the same the sidecar passes to `cosmwasm_query`.
+const SMART_QUERY_GAS_LIMIT: u64 = 10_000_000_000;
+
+pub struct QuerySmartContractStateHandler<T>(PhantomData<T>);
+
+impl<T> Default for QuerySmartContractStateHandler<T> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T> QueryHandler for QuerySmartContractStateHandler<T>
+where
+	T: pallet_cosmwasm::Config,
+{
+	fn handle(&self, request: &[u8]) -> Result<Vec<u8>, QueryError> {
+		let QuerySmartContractStateRequest { address, query_data } =
+			QuerySmartContractStateRequest::decode(request)
+				.map_err(|_| QueryError::InvalidRequest)?;
+		let contract = <T::AccountToAddr as Convert<_, Result<_, ()>>>::convert(address.clone())
+			.map_err(|_| QueryError::InvalidAddress)?;
+
+		// The query is run as a system query on behalf of the contract itself.
+		let query_request = serde_json::to_vec(&QueryRequest::<Empty>::Wasm(WasmQuery::Smart {
+			contract_addr: address,
+			msg: query_data.into(),
+		}))
+		.map_err(|_| QueryError::InvalidRequest)?;
+		let data = pallet_cosmwasm::query::<T>(contract, SMART_QUERY_GAS_LIMIT, query_request)
+			.map_err(|_| QueryError::InvalidRequest)?;
+
+		Ok(QuerySmartContractStateResponse { data: data.into() }.encode_to_vec())
+	}
+}
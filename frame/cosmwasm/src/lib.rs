@@ -77,8 +77,8 @@ use alloc::{
 use composable_support::abstractions::utils::increment::Increment;
 use cosmwasm_std::{
 	Addr, Attribute as CosmwasmEventAttribute, Binary as CosmwasmBinary, BlockInfo,
-	CodeInfoResponse, Coin, ContractInfo as CosmwasmContractInfo, ContractInfoResponse, Env,
-	Event as CosmwasmEvent, MessageInfo, Timestamp, TransactionInfo,
+	CodeInfoResponse, Coin, ContractInfo as CosmwasmContractInfo, ContractInfoResponse,
+	DenomMetadata, Env, Event as CosmwasmEvent, MessageInfo, Timestamp, TransactionInfo,
 };
 use cosmwasm_vm::{
 	executor::{cosmwasm_call, QueryCall, QueryResponse},
@@ -97,7 +97,7 @@ use frame_support::{
 	traits::{
 		fungibles::{Inspect as FungiblesInspect, Mutate as FungiblesMutate},
 		tokens::Preservation,
-		Currency, ExistenceRequirement, Get, ReservableCurrency, UnixTime,
+		Contains, Currency, ExistenceRequirement, Get, ReservableCurrency, UnixTime,
 	},
 	ReversibleStorageHasher, StorageHasher,
 };
@@ -126,13 +126,13 @@ pub mod pallet {
 		traits::{
 			fungibles::{Inspect as FungiblesInspect, Mutate as FungiblesMutate},
 			tokens::{AssetId, Balance},
-			BuildGenesisConfig, Currency, Get, ReservableCurrency, UnixTime,
+			BuildGenesisConfig, Contains, Currency, Get, ReservableCurrency, UnixTime,
 		},
 		transactional, PalletId, Twox64Concat,
 	};
 	use frame_system::{ensure_signed, pallet_prelude::OriginFor};
 	use sp_core::crypto::UncheckedFrom;
-	use sp_runtime::traits::{Convert, MaybeDisplay};
+	use sp_runtime::traits::{Convert, MaybeDisplay, SaturatedConversion};
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -165,6 +165,8 @@ pub mod pallet {
 		TransferFailed,
 		LabelTooBig,
 		UnknownDenom,
+		SendDisabled,
+		BlockedAddress,
 		StackOverflow,
 		NotEnoughFundsForUpload,
 		NonceOverflow,
@@ -307,6 +309,15 @@ pub mod pallet {
 		type AssetToDenom: Convert<AssetIdOf<Self>, String>
 			+ Convert<String, Result<AssetIdOf<Self>, ()>>;
 
+		/// A way to look up the metadata of a cosmwasm `Denom`.
+		type DenomToMetadata: Convert<String, Option<DenomMetadata>>;
+
+		/// Denoms that may be sent with `BankMsg::Send`.
+		type SendEnabled: Contains<String>;
+
+		/// Accounts that may not receive funds from `BankMsg::Send`.
+		type BlockedAddrs: Contains<AccountIdOf<Self>>;
+
 		/// Interface used to pay when uploading code.
 		type NativeAsset: ReservableCurrency<AccountIdOf<Self>, Balance = BalanceOf<Self>>
 			+ Currency<AccountIdOf<Self>>;
@@ -381,17 +392,40 @@ pub mod pallet {
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub contracts: Vec<(T::AccountIdExtended, ContractCodeOf<T>)>,
+		/// Codes stored under the given code ids, such as those exported by another chain. Their
+		/// creators are charged the upload deposit.
+		pub codes: Vec<(CosmwasmCodeId, T::AccountIdExtended, ContractCodeOf<T>)>,
+		/// Contracts of the `codes`, created without calling their `instantiate` entrypoint.
+		pub instances: Vec<GenesisContractOf<T>>,
 	}
 
 	impl<T: Config> Default for GenesisConfig<T> {
 		fn default() -> Self {
-			Self { contracts: Default::default() }
+			Self {
+				contracts: Default::default(),
+				codes: Default::default(),
+				instances: Default::default(),
+			}
 		}
 	}
 
 	#[pallet::genesis_build]
 	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
 		fn build(&self) {
+			for (code_id, who, code) in self.codes.clone() {
+				// Reserved as by `do_upload`, since it is returned once the code is unused.
+				let deposit = code.len().saturating_mul(T::CodeStorageByteDeposit::get() as _);
+				T::NativeAsset::reserve(&who, deposit.saturated_into())
+					.expect("creators of codes in genesis can pay their deposits");
+				<Pallet<T>>::do_store_code(&who, code_id, code)
+					.expect("codes in genesis are valid");
+			}
+			if let Some(code_id) = self.codes.iter().map(|(code_id, _, _)| *code_id).max() {
+				CurrentCodeId::<T>::put(code_id);
+			}
+			for contract in &self.instances {
+				<Pallet<T>>::do_create_contract(contract).expect("instances in genesis are valid");
+			}
 			for (who, code) in self.contracts.clone() {
 				<Pallet<T>>::do_upload(&who, code).expect("contracts in genesis are valid");
 			}
@@ -663,6 +697,9 @@ impl<T: Config> Pallet<T> {
 			depth: 0,
 			gas: Gas::new(T::MAX_FRAMES, gas),
 			cache: CosmwasmVMCache { code: Default::default() },
+			transfers: Default::default(),
+			transfer_checkpoints: Default::default(),
+			events: Default::default(),
 		}
 	}
 
@@ -691,6 +728,7 @@ impl<T: Config> Pallet<T> {
 
 		Self::sub_level_dispatch(shared, sender, contract.clone(), cosmwasm_funds, call).map(
 			|(data, events)| {
+				shared.events.extend(events.iter().cloned());
 				for CosmwasmEvent { ty, attributes, .. } in events {
 					Self::deposit_event(Event::<T>::Emitted {
 						contract: contract.clone(),
@@ -946,10 +984,22 @@ impl<T: Config> Pallet<T> {
 		let deposit = code.len().saturating_mul(T::CodeStorageByteDeposit::get() as _);
 		T::NativeAsset::reserve(who, deposit.saturated_into())
 			.map_err(|_| Error::<T>::NotEnoughFundsForUpload)?;
+		let code_id = CurrentCodeId::<T>::increment().map_err(|_| Error::<T>::IncrementFailed)?;
+		Self::do_store_code(who, code_id, code)?;
+		Self::deposit_event(Event::<T>::Uploaded { code_hash, code_id });
+		Ok((H256::from(code_hash), code_id))
+	}
+
+	/// Instruments `code` and stores it under `code_id`, as created by `who`.
+	pub(crate) fn do_store_code(
+		who: &AccountIdOf<T>,
+		code_id: CosmwasmCodeId,
+		code: ContractCodeOf<T>,
+	) -> Result<(), Error<T>> {
+		let code_hash = sp_io::hashing::sha2_256(&code);
 		let module = Self::do_load_module(&code)?;
 		let ibc_capable = Self::do_check_ibc_capability(&module);
 		let instrumented_code = Self::do_instrument_code(module)?;
-		let code_id = CurrentCodeId::<T>::increment().map_err(|_| Error::<T>::IncrementFailed)?;
 		CodeHashToId::<T>::insert(code_hash, code_id);
 		PristineCode::<T>::insert(code_id, code);
 		InstrumentedCode::<T>::insert(code_id, instrumented_code);
@@ -963,8 +1013,63 @@ impl<T: Config> Pallet<T> {
 				refcount: 0,
 			},
 		);
-		Self::deposit_event(Event::<T>::Uploaded { code_hash, code_id });
-		Ok((H256::from(code_hash), code_id))
+		Ok(())
+	}
+
+	/// Makes `to` the creator of the codes `from` created, returning the deposit reserved for
+	/// them, which is for the caller to move along.
+	pub fn hand_over_codes(
+		from: &AccountIdOf<T>,
+		to: &AccountIdOf<T>,
+	) -> Result<BalanceOf<T>, Error<T>> {
+		let code_ids = CodeIdToInfo::<T>::iter()
+			.filter(|(_, code_info)| code_info.creator == *from)
+			.map(|(code_id, _)| code_id)
+			.collect::<Vec<_>>();
+		let mut deposit: usize = 0;
+		for code_id in code_ids {
+			let code = PristineCode::<T>::try_get(code_id).map_err(|_| Error::<T>::CodeNotFound)?;
+			deposit = deposit
+				.saturating_add(code.len().saturating_mul(T::CodeStorageByteDeposit::get() as _));
+			CodeIdToInfo::<T>::mutate(code_id, |entry| {
+				if let Some(code_info) = entry {
+					code_info.creator = to.clone();
+				}
+			});
+		}
+		Ok(deposit.saturated_into())
+	}
+
+	/// Creates `contract` with the given storage entries, as if it had been instantiated.
+	pub(crate) fn do_create_contract(contract: &GenesisContractOf<T>) -> Result<(), Error<T>> {
+		ensure!(
+			Self::contract_exists(&contract.address).is_err(),
+			Error::<T>::ContractAlreadyExists
+		);
+		CodeIdToInfo::<T>::try_mutate(contract.code_id, |entry| -> Result<(), Error<T>> {
+			let code_info = entry.as_mut().ok_or(Error::<T>::CodeNotFound)?;
+			code_info.refcount =
+				code_info.refcount.checked_add(1).ok_or(Error::<T>::RefcountOverflow)?;
+			Ok(())
+		})?;
+		let nonce = CurrentNonce::<T>::increment().map_err(|_| Error::<T>::NonceOverflow)?;
+		let trie_id = Self::derive_contract_trie_id(&contract.address, nonce);
+		for (key, value) in &contract.storage {
+			Self::with_db_entry(&trie_id, key, |child_trie, entry| {
+				storage::child::put_raw(&child_trie, &entry, value)
+			});
+		}
+		ContractToInfo::<T>::insert(
+			&contract.address,
+			ContractInfoOf::<T> {
+				instantiator: contract.instantiator.clone(),
+				code_id: contract.code_id,
+				trie_id,
+				admin: contract.admin.clone(),
+				label: contract.label.clone(),
+			},
+		);
+		Ok(())
 	}
 
 	#[allow(clippy::too_many_arguments)]
@@ -1295,6 +1400,24 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	pub(crate) fn do_denom_metadata(denom: String) -> Result<DenomMetadata, Error<T>> {
+		T::DenomToMetadata::convert(denom).ok_or(Error::<T>::UnknownDenom)
+	}
+
+	/// Execute a `BankMsg::Send` from a contract, honoring the bank send restrictions.
+	pub(crate) fn do_bank_send(
+		from: &AccountIdOf<T>,
+		to: &AccountIdOf<T>,
+		funds: &[Coin],
+	) -> Result<(), Error<T>> {
+		ensure!(!T::BlockedAddrs::contains(to), Error::<T>::BlockedAddress);
+		ensure!(
+			funds.iter().all(|Coin { denom, .. }| T::SendEnabled::contains(denom)),
+			Error::<T>::SendDisabled
+		);
+		Self::do_transfer(from, to, funds, Preservation::Expendable)
+	}
+
 	/// Execute a transfer of funds between two accounts.
 	pub(crate) fn do_transfer(
 		from: &AccountIdOf<T>,
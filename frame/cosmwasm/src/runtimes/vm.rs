@@ -2,7 +2,9 @@ use super::abstraction::{CanonicalCosmwasmAccount, CosmwasmAccount, Gas};
 use crate::{runtimes::abstraction::GasOutcome, types::*, weights::WeightInfo, Config, Pallet};
 use alloc::{borrow::ToOwned, collections::btree_map::BTreeMap, string::String, vec::Vec};
 use core::marker::{Send, Sync};
-use cosmwasm_std::{CodeInfoResponse, Coin, ContractInfoResponse, Empty, Env, MessageInfo};
+use cosmwasm_std::{
+	CodeInfoResponse, Coin, ContractInfoResponse, DenomMetadata, Empty, Env, Event, MessageInfo,
+};
 use cosmwasm_vm::{
 	executor::ExecutorError,
 	has::Has,
@@ -163,6 +165,12 @@ pub struct CosmwasmVMShared {
 	pub gas: Gas,
 	/// Shared cache.
 	pub cache: CosmwasmVMCache,
+	/// Funds moved by the contracts, as `(from, to, funds)` with cosmwasm addresses.
+	pub transfers: Vec<(String, String, Vec<Coin>)>,
+	/// Length of `transfers` when each of the currently open transactions began.
+	pub transfer_checkpoints: Vec<usize>,
+	/// Events emitted by the contracts in successful top level calls.
+	pub events: Vec<Event>,
 }
 
 impl CosmwasmVMShared {
@@ -175,6 +183,9 @@ impl CosmwasmVMShared {
 			depth: 0,
 			gas: Gas::new(max_frames, initial_value),
 			cache: CosmwasmVMCache::default(),
+			transfers: Vec::new(),
+			transfer_checkpoints: Vec::new(),
+			events: Vec::new(),
 		}
 	}
 
@@ -192,6 +203,12 @@ impl CosmwasmVMShared {
 	pub fn pop_readonly(&mut self) {
 		self.storage_readonly_depth -= 1;
 	}
+	/// Record a successful transfer of `funds`, if any.
+	pub fn push_transfer(&mut self, from: String, to: String, funds: &[Coin]) {
+		if !funds.is_empty() {
+			self.transfers.push((from, to, funds.to_vec()));
+		}
+	}
 }
 
 /// Cosmwasm VM instance data.
@@ -410,7 +427,9 @@ impl<'a, T: Config + Send + Sync> VMBase for CosmwasmVM<'a, T> {
 	fn transfer(&mut self, to: &Self::Address, funds: &[Coin]) -> Result<(), Self::Error> {
 		log::debug!(target: "runtime::contracts", "transfer: {:#?}", funds);
 		let from = self.contract_address.as_ref();
-		Pallet::<T>::do_transfer(from, to.as_ref(), funds, Preservation::Expendable)?;
+		Pallet::<T>::do_bank_send(from, to.as_ref(), funds)?;
+		self.shared
+			.push_transfer(self.contract_address.clone().into(), to.clone().into(), funds);
 		Ok(())
 	}
 
@@ -435,6 +454,11 @@ impl<'a, T: Config + Send + Sync> VMBase for CosmwasmVM<'a, T> {
 		Ok(Coin { denom, amount: amount.into() })
 	}
 
+	fn denom_metadata(&mut self, denom: String) -> Result<DenomMetadata, Self::Error> {
+		log::debug!(target: "runtime::contracts", "denom_metadata: {}", denom);
+		Pallet::<T>::do_denom_metadata(denom).map_err(Into::into)
+	}
+
 	fn query_contract_info(
 		&mut self,
 		address: Self::Address,
@@ -652,6 +676,7 @@ impl<'a, T: Config + Send + Sync> VMBase for CosmwasmVM<'a, T> {
 		funds: &[Coin],
 	) -> Result<(), Self::Error> {
 		Pallet::<T>::do_transfer(from.as_ref(), to.as_ref(), funds, Preservation::Expendable)?;
+		self.shared.push_transfer(from.clone().into(), to.clone().into(), funds);
 		Ok(())
 	}
 }
@@ -660,14 +685,19 @@ impl<'a, T: Config> Transactional for CosmwasmVM<'a, T> {
 	type Error = CosmwasmVMError<T>;
 	fn transaction_begin(&mut self) -> Result<(), Self::Error> {
 		sp_io::storage::start_transaction();
+		self.shared.transfer_checkpoints.push(self.shared.transfers.len());
 		Ok(())
 	}
 	fn transaction_commit(&mut self) -> Result<(), Self::Error> {
 		sp_io::storage::commit_transaction();
+		self.shared.transfer_checkpoints.pop();
 		Ok(())
 	}
 	fn transaction_rollback(&mut self) -> Result<(), Self::Error> {
 		sp_io::storage::rollback_transaction();
+		if let Some(len) = self.shared.transfer_checkpoints.pop() {
+			self.shared.transfers.truncate(len);
+		}
 		Ok(())
 	}
 }
@@ -1,8 +1,10 @@
 use crate::{runtimes::vm::CosmwasmVM, Config};
+use alloc::vec::Vec;
 use codec::{Decode, Encode, MaxEncodedLen};
 use cosmwasm_vm::system::CosmwasmCodeId;
 use frame_support::{BoundedBTreeMap, BoundedVec};
 use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
 
 pub type DefaultCosmwasmVM<'a, T> = CosmwasmVM<'a, T>;
 pub type KeepAlive = bool;
@@ -25,6 +27,7 @@ pub type AssetIdOf<T> = <T as Config>::AssetId;
 pub type BalanceOf<T> = <T as Config>::Balance;
 pub type ContractInfoOf<T> = ContractInfo<AccountIdOf<T>, ContractLabelOf<T>, ContractTrieIdOf<T>>;
 pub type CodeInfoOf<T> = CodeInfo<AccountIdOf<T>>;
+pub type GenesisContractOf<T> = GenesisContract<AccountIdOf<T>, ContractLabelOf<T>>;
 
 #[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, TypeInfo, Debug)]
 pub enum EntryPoint {
@@ -85,3 +88,15 @@ pub struct ContractInfo<AccountId, Label, TrieId> {
 	/// Contract label defined by the instantiator.
 	pub label: Label,
 }
+
+/// A contract created at genesis from an existing code, along with the entries of its storage.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenesisContract<AccountId, Label> {
+	pub address: AccountId,
+	pub code_id: CosmwasmCodeId,
+	pub instantiator: AccountId,
+	pub admin: Option<AccountId>,
+	pub label: Label,
+	pub storage: Vec<(Vec<u8>, Vec<u8>)>,
+}
@@ -20,8 +20,20 @@
 #![warn(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use alloc::vec::Vec;
 use sp_runtime_interface::runtime_interface;
 
+#[cfg(feature = "std")]
+static MIN_GAS_PRICES: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
+/// Set the node-local minimum gas prices applied to the transaction pool.
+#[cfg(feature = "std")]
+pub fn set_min_gas_prices(min_gas_prices: String) {
+	let _ = MIN_GAS_PRICES.set(min_gas_prices);
+}
+
 /// Interface for working with crypto-related, handling Cosmos transactions and messages.
 #[runtime_interface]
 pub trait Cosmos {
@@ -29,4 +41,9 @@ pub trait Cosmos {
 	fn secp256k1_ecdsa_verify(sig: &[u8], msg: &[u8], pub_key: &[u8]) -> bool {
 		hp_crypto::secp256k1_ecdsa_verify(sig, msg, pub_key)
 	}
+
+	/// Get the node-local minimum gas prices, if configured.
+	fn min_gas_prices() -> Option<Vec<u8>> {
+		MIN_GAS_PRICES.get().map(|min_gas_prices| min_gas_prices.as_bytes().to_vec())
+	}
 }
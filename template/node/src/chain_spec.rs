@@ -16,7 +16,11 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use horizon_template_runtime::{AccountId, RuntimeGenesisConfig, Signature, WASM_BINARY};
+mod cosmos;
+
+use horizon_template_runtime::{
+	opaque::SessionKeys, AccountId, RuntimeGenesisConfig, Signature, WASM_BINARY,
+};
 use sc_chain_spec::NoExtension;
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -48,9 +52,30 @@ where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
-/// Generate an Aura authority key.
-pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
-	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
+/// Generate the account of an authority along with its Aura and GRANDPA keys.
+pub fn authority_keys_from_seed(s: &str) -> (AccountId, AuraId, GrandpaId) {
+	(
+		get_account_id_from_seed::<ecdsa::Public>(s),
+		get_from_seed::<AuraId>(s),
+		get_from_seed::<GrandpaId>(s),
+	)
+}
+
+/// The session config that registers the keys of `initial_authorities` to their accounts,
+/// which are paid their share of the fees of the blocks they author.
+fn session_genesis(initial_authorities: &[(AccountId, AuraId, GrandpaId)]) -> serde_json::Value {
+	serde_json::json!({
+		"keys": initial_authorities
+			.iter()
+			.map(|(account, aura, grandpa)| {
+				(
+					account.clone(),
+					account.clone(),
+					SessionKeys { aura: aura.clone(), grandpa: grandpa.clone() },
+				)
+			})
+			.collect::<Vec<_>>(),
+	})
 }
 
 pub fn development_config() -> Result<ChainSpec, String> {
@@ -101,9 +126,32 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 		.build())
 }
 
+/// A chain spec whose genesis recreates the balances, accounts and contracts in the
+/// `genesis.json` exported by a Cosmos SDK chain at `path`. The spec comes with the development
+/// authority and sudo key, which are to be replaced before launching a network with it.
+pub fn cosmos_genesis_config(path: &str) -> Result<ChainSpec, String> {
+	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+	let file = std::fs::File::open(path).map_err(|e| format!("Error opening {}: {}", path, e))?;
+	let genesis: cosmos::Genesis = serde_json::from_reader(std::io::BufReader::new(file))
+		.map_err(|e| format!("Error parsing {}: {}", path, e))?;
+
+	let initial_authorities = vec![authority_keys_from_seed("Alice")];
+	let root_key = get_account_id_from_seed::<ecdsa::Public>("Alice");
+	let mut genesis_config = cosmos::genesis_config_patch(&genesis, root_key.clone())?;
+	genesis_config["session"] = session_genesis(&initial_authorities);
+	genesis_config["sudo"] = serde_json::json!({ "key": Some(root_key) });
+
+	Ok(ChainSpec::builder(wasm_binary, None)
+		.with_name(&genesis.chain_id)
+		.with_id(&genesis.chain_id)
+		.with_chain_type(ChainType::Live)
+		.with_genesis_config_patch(genesis_config)
+		.build())
+}
+
 /// Configure initial storage state for FRAME modules.
 fn testnet_genesis(
-	initial_authorities: Vec<(AuraId, GrandpaId)>,
+	initial_authorities: Vec<(AccountId, AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
 	_enable_println: bool,
@@ -123,12 +171,8 @@ fn testnet_genesis(
 		"cosmosAccounts": {
 			"accounts": endowed_accounts.to_vec(),
 		},
-		"aura": {
-			"authorities": initial_authorities.iter().map(|x| (x.0.clone())).collect::<Vec<_>>(),
-		},
-		"grandpa": {
-			"authorities": initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect::<Vec<_>>(),
-		},
+		// Aura and GRANDPA authorities are set from the session keys.
+		"session": session_genesis(&initial_authorities),
 		"sudo": {
 			// Assign network admin rights.
 			"key": Some(root_key),
@@ -0,0 +1,501 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Genesis state recreated from the `genesis.json` exported by a Cosmos SDK chain.
+
+use base64ct::{Base64, Encoding};
+use horizon_template_runtime::{
+	compat::cosmos::HashedAddressMapping, AccountId, ExistentialDeposit, Runtime,
+};
+use pallet_cosmwasm::types::{ContractCodeOf, ContractLabelOf, GenesisContractOf};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sp_core::{crypto::UncheckedFrom, Get, H160, H256};
+use sp_runtime::traits::BlakeTwo256;
+use std::{collections::BTreeMap, str::FromStr};
+
+#[derive(Deserialize)]
+pub struct Genesis {
+	pub chain_id: String,
+	app_state: AppState,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct AppState {
+	auth: AuthState,
+	bank: BankState,
+	wasm: WasmState,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct AuthState {
+	/// Accounts of any type, each of which is or wraps a `BaseAccount`.
+	accounts: Vec<Value>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct BankState {
+	balances: Vec<Balance>,
+	supply: Vec<Coin>,
+	denom_metadata: Vec<Metadata>,
+}
+
+#[derive(Deserialize)]
+struct Balance {
+	address: String,
+	coins: Vec<Coin>,
+}
+
+#[derive(Deserialize)]
+struct Coin {
+	denom: String,
+	amount: String,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Metadata {
+	name: String,
+	base: String,
+	display: String,
+	denom_units: Vec<DenomUnit>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct DenomUnit {
+	denom: String,
+	exponent: u8,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct WasmState {
+	codes: Vec<Code>,
+	contracts: Vec<Contract>,
+}
+
+#[derive(Deserialize)]
+struct Code {
+	code_id: String,
+	code_info: CodeInfo,
+	/// Base64-encoded wasm bytecode.
+	code_bytes: String,
+}
+
+#[derive(Deserialize)]
+struct CodeInfo {
+	creator: String,
+}
+
+#[derive(Deserialize)]
+struct Contract {
+	contract_address: String,
+	contract_info: ContractInfo,
+	#[serde(default)]
+	contract_state: Vec<Model>,
+}
+
+#[derive(Deserialize)]
+struct ContractInfo {
+	code_id: String,
+	creator: String,
+	#[serde(default)]
+	admin: String,
+	#[serde(default)]
+	label: String,
+}
+
+/// A contract storage entry, with a hex-encoded key and a base64-encoded value.
+#[derive(Deserialize)]
+struct Model {
+	key: String,
+	value: String,
+}
+
+fn parse<T: FromStr>(value: &str, what: &str) -> Result<T, String> {
+	value.parse().map_err(|_| format!("Invalid {}: {}", what, value))
+}
+
+fn decode_address(address: &str) -> Result<Vec<u8>, String> {
+	bech32::decode(address)
+		.map(|(_hrp, data)| data)
+		.map_err(|e| format!("Invalid address {}: {}", address, e))
+}
+
+/// The account that holds the assets of a user address until it is connected to a key, or
+/// the account of a contract address.
+fn account_id(address: &str) -> Result<AccountId, String> {
+	let data = decode_address(address)?;
+	match data.len() {
+		20 => Ok(HashedAddressMapping::<Runtime, BlakeTwo256>::interim_account_id(
+			H160::from_slice(&data),
+		)),
+		32 => Ok(AccountId::unchecked_from(H256::from_slice(&data))),
+		_ => Err(format!("Address {} is neither 20 nor 32 bytes long", address)),
+	}
+}
+
+/// The `BaseAccount` an account of any type wraps.
+fn base_account(account: &Value) -> Option<&Value> {
+	if account.get("address").is_some() {
+		return Some(account);
+	}
+	["base_account", "base_vesting_account"]
+		.iter()
+		.find_map(|key| account.get(key).and_then(base_account))
+}
+
+/// Address and account number of each user account. Accounts of contracts, whose addresses
+/// do not fit the key of account numbers, are left out.
+fn account_numbers(auth: &AuthState) -> Result<Vec<(H160, u64)>, String> {
+	let mut account_numbers = Vec::new();
+	for account in &auth.accounts {
+		let account = base_account(account).ok_or("Account without an address")?;
+		let address = account["address"].as_str().ok_or("Account without an address")?;
+		let data = decode_address(address)?;
+		if data.len() != 20 {
+			continue;
+		}
+		let number = match &account["account_number"] {
+			Value::String(number) => parse(number, "account number")?,
+			Value::Number(number) => number.as_u64().ok_or("Invalid account number")?,
+			_ => return Err(format!("Account {} without an account number", address)),
+		};
+		account_numbers.push((H160::from_slice(&data), number));
+	}
+
+	Ok(account_numbers)
+}
+
+/// The genesis config patch of the balances, accounts and contracts of `genesis`, with the
+/// denominations other than the native one issued as assets owned by `asset_owner`.
+pub fn genesis_config_patch(genesis: &Genesis, asset_owner: AccountId) -> Result<Value, String> {
+	let AppState { auth, bank, wasm } = &genesis.app_state;
+	let native_denom = <Runtime as pallet_cosmos::Config>::NativeDenom::get();
+	let string_limit = <Runtime as pallet_assets::Config>::StringLimit::get() as usize;
+
+	let mut balances = Vec::new();
+	let mut asset_accounts = BTreeMap::<&str, Vec<(AccountId, u128)>>::new();
+	let mut totals = BTreeMap::<&str, u128>::new();
+	for balance in &bank.balances {
+		let who = account_id(&balance.address)?;
+		for coin in &balance.coins {
+			let amount: u128 = parse(&coin.amount, "amount")?;
+			let total = totals.entry(coin.denom.as_str()).or_default();
+			*total = total.checked_add(amount).ok_or("Total balance overflow")?;
+
+			if coin.denom == native_denom {
+				if amount < ExistentialDeposit::get() {
+					return Err(format!(
+						"{} holds {}{}, less than the existential deposit",
+						balance.address, amount, coin.denom
+					));
+				}
+				balances.push((who.clone(), amount));
+			} else {
+				asset_accounts
+					.entry(coin.denom.as_str())
+					.or_default()
+					.push((who.clone(), amount));
+			}
+		}
+	}
+	for coin in &bank.supply {
+		if totals.get(coin.denom.as_str()).copied().unwrap_or_default() !=
+			parse::<u128>(&coin.amount, "amount")?
+		{
+			return Err(format!("Supply of {} does not match its balances", coin.denom));
+		}
+	}
+
+	let metadata: BTreeMap<&str, &Metadata> = bank
+		.denom_metadata
+		.iter()
+		.map(|metadata| (metadata.base.as_str(), metadata))
+		.collect();
+	let (mut assets, mut assets_metadata, mut assets_accounts, mut denoms) =
+		(Vec::new(), Vec::new(), Vec::new(), Vec::new());
+	for (asset_id, (denom, accounts)) in asset_accounts.into_iter().enumerate() {
+		let asset_id = asset_id as u128;
+		if denom.len() > string_limit {
+			return Err(format!("Denom {} is longer than {} bytes", denom, string_limit));
+		}
+		let (name, decimals) = metadata.get(denom).map_or((denom, 0), |metadata| {
+			let decimals = metadata
+				.denom_units
+				.iter()
+				.find(|unit| unit.denom == metadata.display)
+				.map_or(0, |unit| unit.exponent);
+			let name = if metadata.name.is_empty() || metadata.name.len() > string_limit {
+				denom
+			} else {
+				metadata.name.as_str()
+			};
+			(name, decimals)
+		});
+
+		assets.push((asset_id, asset_owner.clone(), true, 1u128));
+		assets_metadata.push((asset_id, name.as_bytes(), denom.as_bytes(), decimals));
+		assets_accounts.extend(accounts.into_iter().map(|(who, amount)| (asset_id, who, amount)));
+		denoms.push((denom.as_bytes(), asset_id));
+	}
+
+	let deposit_per_byte =
+		<Runtime as pallet_cosmwasm::Config>::CodeStorageByteDeposit::get() as u128;
+	let mut codes = Vec::new();
+	let mut deposits = BTreeMap::<&str, u128>::new();
+	for code in &wasm.codes {
+		let bytes = Base64::decode_vec(&code.code_bytes)
+			.map_err(|_| format!("Invalid bytecode of code {}", code.code_id))?;
+		let deposit = deposits.entry(code.code_info.creator.as_str()).or_default();
+		*deposit = (bytes.len() as u128)
+			.checked_mul(deposit_per_byte)
+			.and_then(|amount| deposit.checked_add(amount))
+			.ok_or("Code deposit overflow")?;
+		codes.push((
+			parse::<u64>(&code.code_id, "code id")?,
+			account_id(&code.code_info.creator)?,
+			ContractCodeOf::<Runtime>::try_from(bytes)
+				.map_err(|_| format!("Code {} is too large", code.code_id))?,
+		));
+	}
+	// Creators are charged the upload deposit of their codes, which is reserved on the same
+	// account as their balance and handed over along with it once they connect a key.
+	for (creator, deposit) in deposits {
+		let who = account_id(creator)?;
+		let balance: u128 = balances
+			.iter()
+			.filter(|(holder, _)| *holder == who)
+			.map(|(_, amount)| amount)
+			.sum();
+		// Reserving the deposit must leave the existential deposit free.
+		if balance < deposit.saturating_add(ExistentialDeposit::get()) {
+			return Err(format!(
+				"{} holds {}{}, less than the deposit of {} for its codes and the existential \
+				 deposit",
+				creator, balance, native_denom, deposit
+			));
+		}
+	}
+
+	let mut instances = Vec::new();
+	for contract in &wasm.contracts {
+		let address = &contract.contract_address;
+		if decode_address(address)?.len() != 32 {
+			return Err(format!("Contract address {} is not 32 bytes long", address));
+		}
+		let info = &contract.contract_info;
+		let storage = contract
+			.contract_state
+			.iter()
+			.map(|model| {
+				Ok((
+					hex::decode(&model.key).map_err(|_| format!("Invalid key in {}", address))?,
+					Base64::decode_vec(&model.value)
+						.map_err(|_| format!("Invalid value in {}", address))?,
+				))
+			})
+			.collect::<Result<Vec<_>, String>>()?;
+
+		instances.push(GenesisContractOf::<Runtime> {
+			address: account_id(address)?,
+			code_id: parse(&info.code_id, "code id")?,
+			instantiator: account_id(&info.creator)?,
+			admin: (!info.admin.is_empty()).then(|| account_id(&info.admin)).transpose()?,
+			label: ContractLabelOf::<Runtime>::try_from(info.label.as_bytes().to_vec())
+				.map_err(|_| format!("Label of {} is too long", address))?,
+			storage,
+		});
+	}
+
+	Ok(json!({
+		"assets": {
+			"assets": assets,
+			"metadata": assets_metadata,
+			"accounts": assets_accounts,
+		},
+		"balances": {
+			"balances": balances,
+		},
+		"cosmos": {
+			"assets": denoms,
+		},
+		"cosmosAccounts": {
+			"accountNumbers": account_numbers(auth)?,
+		},
+		"cosmwasm": {
+			"codes": codes,
+			"instances": instances,
+		},
+	}))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{genesis_config_patch, Genesis};
+	use bech32::{Bech32, Hrp};
+	use horizon_template_runtime::{AccountId, Runtime};
+	use pallet_cosmos::AddressMapping;
+	use serde_json::{json, Value};
+	use sp_core::{crypto::UncheckedFrom, H160, H256};
+
+	fn address(byte: u8) -> String {
+		bech32::encode::<Bech32>(Hrp::parse("cosmos").unwrap(), &[byte; 20]).unwrap()
+	}
+
+	fn patch(app_state: Value) -> Result<Value, String> {
+		let genesis: Genesis =
+			serde_json::from_value(json!({ "chain_id": "cosmoshub-4", "app_state": app_state }))
+				.unwrap();
+		genesis_config_patch(&genesis, AccountId::unchecked_from(H256::zero()))
+	}
+
+	fn app_state(alice_number: Value, supply: &str) -> Value {
+		json!({
+			"auth": {
+				"accounts": [
+					{
+						"@type": "/cosmos.auth.v1beta1.BaseAccount",
+						"address": address(1),
+						"account_number": alice_number,
+					},
+					{
+						"@type": "/cosmos.vesting.v1beta1.ContinuousVestingAccount",
+						"base_vesting_account": {
+							"base_account": { "address": address(2), "account_number": 0 },
+						},
+					},
+				],
+			},
+			"bank": {
+				"balances": [
+					{
+						"address": address(1),
+						"coins": [
+							{ "denom": "acdt", "amount": "1000" },
+							{ "denom": "uatom", "amount": "10" },
+						],
+					},
+					{ "address": address(2), "coins": [{ "denom": "acdt", "amount": "600" }] },
+				],
+				"supply": [
+					{ "denom": "acdt", "amount": supply },
+					{ "denom": "uatom", "amount": "10" },
+				],
+				"denom_metadata": [
+					{
+						"name": "Cosmos Hub Atom",
+						"base": "uatom",
+						"display": "atom",
+						"denom_units": [
+							{ "denom": "uatom", "exponent": 0 },
+							{ "denom": "atom", "exponent": 6 },
+						],
+					},
+				],
+			},
+		})
+	}
+
+	#[test]
+	fn genesis_config_patch_test() {
+		let patch = patch(app_state(json!("5"), "1600")).unwrap();
+
+		assert_eq!(
+			patch["cosmosAccounts"]["accountNumbers"],
+			json!([
+				[format!("0x{}", hex::encode([1u8; 20])), 5],
+				[format!("0x{}", hex::encode([2u8; 20])), 0],
+			])
+		);
+		assert_eq!(patch["balances"]["balances"].as_array().unwrap().len(), 2);
+		assert_eq!(patch["cosmos"]["assets"], json!([[b"uatom", 0]]));
+		assert_eq!(patch["assets"]["metadata"], json!([[0, b"Cosmos Hub Atom", b"uatom", 6]]));
+		assert_eq!(patch["assets"]["accounts"][0][2], json!(10));
+	}
+
+	#[test]
+	fn genesis_config_patch_error_test() {
+		let error = patch(app_state(Value::Null, "1600")).unwrap_err();
+		assert_eq!(error, format!("Account {} without an account number", address(1)));
+
+		let error = patch(app_state(json!("5"), "1601")).unwrap_err();
+		assert_eq!(error, "Supply of acdt does not match its balances");
+
+		let mut state = app_state(json!("5"), "1100");
+		state["bank"]["balances"][1]["coins"][0]["amount"] = json!("100");
+		let error = patch(state).unwrap_err();
+		assert!(error.ends_with("less than the existential deposit"));
+
+		// The creator cannot pay the deposit of the 8-byte code.
+		let mut state = app_state(json!("5"), "1600");
+		state["wasm"] = json!({
+			"codes": [
+				{
+					"code_id": "1",
+					"code_info": { "creator": address(2) },
+					"code_bytes": "AGFzbQEAAAA=",
+				},
+			],
+		});
+		let error = patch(state.clone()).unwrap_err();
+		assert_eq!(
+			error,
+			format!(
+				"{} holds 600acdt, less than the deposit of 8000000 for its codes and the \
+				 existential deposit",
+				address(2)
+			)
+		);
+
+		// The deposit alone leaves nothing free.
+		state["bank"]["balances"][1]["coins"][0]["amount"] = json!("8000000");
+		state["bank"]["supply"][0]["amount"] = json!("8001000");
+		assert!(patch(state).unwrap_err().ends_with("and the existential deposit"));
+	}
+
+	#[test]
+	fn genesis_config_patch_interim_account_test() {
+		let mut state = app_state(json!("5"), "8001100");
+		state["bank"]["balances"][0]["coins"][0]["amount"] = json!("8000500");
+		state["wasm"] = json!({
+			"codes": [
+				{
+					"code_id": "1",
+					"code_info": { "creator": address(1) },
+					"code_bytes": "AGFzbQEAAAA=",
+				},
+			],
+		});
+		let patch = patch(state).unwrap();
+
+		// Until the address connects a key, its coins and code deposits are held by the account
+		// its first tx moves them from.
+		let interim = sp_io::TestExternalities::default().execute_with(|| {
+			<Runtime as pallet_cosmos::Config>::AddressMapping::into_account_id(H160([1; 20]))
+		});
+		let interim = serde_json::to_value(interim).unwrap();
+		assert_eq!(patch["balances"]["balances"][0], json!([interim, 8000500]));
+		assert_eq!(patch["assets"]["accounts"], json!([[0, interim, 10]]));
+		assert_eq!(patch["cosmwasm"]["codes"][0][1], interim);
+	}
+}
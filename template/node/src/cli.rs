@@ -17,6 +17,7 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use sc_cli::RunCmd;
+use std::net::SocketAddr;
 
 #[derive(Debug, clap::Parser)]
 pub struct Cli {
@@ -25,6 +26,21 @@ pub struct Cli {
 
 	#[clap(flatten)]
 	pub run: RunCmd,
+
+	/// The minimum gas prices a transaction must pay to enter the local pool
+	/// (e.g. "0.0025uatom,0.1stake").
+	#[arg(long)]
+	pub min_gas_prices: Option<String>,
+
+	/// Serve the CometBFT JSON-RPC methods that Cosmos clients use at this address
+	/// (e.g. "127.0.0.1:26657").
+	#[arg(long)]
+	pub cometbft_rpc_addr: Option<SocketAddr>,
+
+	/// Do not index the results of Cosmos txs, which the tx and block results RPC methods serve.
+	/// Offchain indexing is then left to `--enable-offchain-indexing`.
+	#[arg(long)]
+	pub no_cosmos_tx_index: bool,
 }
 
 #[derive(Debug, clap::Subcommand)]
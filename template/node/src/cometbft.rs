@@ -0,0 +1,651 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A JSON-RPC server with the methods of CometBFT v0.38 that Cosmos clients such as CosmJS and
+//! Keplr rely on, so that they can connect to the node without a sidecar.
+
+use base64ct::{Base64, Encoding};
+use cosmos_rpc::{
+	cosmos::{Cosmos, CosmosApiServer},
+	internal_error, not_found_error, request_error,
+};
+use cosmos_runtime_api::{
+	BroadcastTxCommitResponse, BroadcastTxResponse, CosmosError, CosmosRuntimeApi, QueryError,
+	RootError, TxResult, TxSearchResponse,
+};
+use horizon_template_runtime::{opaque::Block, RuntimeCall, UncheckedExtrinsic};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	server::Server,
+	Methods,
+};
+use pallet_cosmos_types::{errors::ROOT_CODESPACE, events::CosmosEvent};
+use pallet_cosmos_x_authz_types::errors::AUTHZ_CODESPACE;
+use pallet_cosmos_x_bank_types::errors::BANK_CODESPACE;
+use pallet_cosmos_x_feegrant_types::errors::FEEGRANT_CODESPACE;
+use pallet_cosmos_x_wasm_types::errors::WASM_CODESPACE;
+use parity_scale_codec::{Decode, Encode};
+use sc_client_api::{Backend, BlockBackend, BlockchainEvents, StorageProvider};
+use sc_transaction_pool_api::TransactionPool;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sp_api::{Core, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_consensus_grandpa::GrandpaApi;
+use sp_core::{
+	hashing::twox_128, offchain::OffchainStorage, sha2_256, storage::StorageKey, Bytes, H256,
+};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::{marker::PhantomData, net::SocketAddr, sync::Arc};
+
+/// The CometBFT version reported to clients, which choose the response formats by it.
+const COMETBFT_VERSION: &str = "0.38.0";
+/// Stands for the hashes and addresses that have no counterpart in Substrate blocks.
+const EMPTY_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+const EMPTY_ADDRESS: &str = "0000000000000000000000000000000000000000";
+
+/// Results per page of `tx_search` and `validators`, unless requested otherwise.
+const DEFAULT_PER_PAGE: u64 = 30;
+const MAX_PER_PAGE: u64 = 100;
+
+/// An integer parameter, which CometBFT accepts both as a JSON number and as a string.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Int {
+	Number(u64),
+	String(String),
+}
+
+impl Int {
+	fn get(self) -> RpcResult<u64> {
+		match self {
+			Int::Number(n) => Ok(n),
+			Int::String(s) =>
+				s.parse().map_err(|_| request_error(format!("invalid integer {}", s))),
+		}
+	}
+}
+
+fn int(param: Option<Int>) -> RpcResult<Option<u64>> {
+	param.map(Int::get).transpose()
+}
+
+#[rpc(server)]
+#[async_trait]
+pub trait CometbftApi {
+	#[method(name = "status")]
+	async fn status(&self) -> RpcResult<Value>;
+
+	#[method(name = "abci_info")]
+	async fn abci_info(&self) -> RpcResult<Value>;
+
+	/// Answers the hex-encoded request `data` to the gRPC query method at `path`.
+	#[method(name = "abci_query", param_kind = map)]
+	async fn abci_query(&self, path: String, data: String, height: Option<Int>)
+		-> RpcResult<Value>;
+
+	/// Submits the base64-encoded `tx` without waiting for it to be checked.
+	#[method(name = "broadcast_tx_async", param_kind = map)]
+	async fn broadcast_tx_async(&self, tx: String) -> RpcResult<Value>;
+
+	#[method(name = "broadcast_tx_sync", param_kind = map)]
+	async fn broadcast_tx_sync(&self, tx: String) -> RpcResult<Value>;
+
+	#[method(name = "broadcast_tx_commit", param_kind = map)]
+	async fn broadcast_tx_commit(&self, tx: String) -> RpcResult<Value>;
+
+	/// The tx with the base64-encoded SHA-256 `hash`.
+	#[method(name = "tx", param_kind = map)]
+	async fn tx(&self, hash: String) -> RpcResult<Value>;
+
+	#[method(name = "tx_search", param_kind = map)]
+	async fn tx_search(
+		&self,
+		query: String,
+		page: Option<Int>,
+		per_page: Option<Int>,
+		order_by: Option<String>,
+	) -> RpcResult<Value>;
+
+	#[method(name = "block", param_kind = map)]
+	async fn block(&self, height: Option<Int>) -> RpcResult<Value>;
+
+	#[method(name = "block_results", param_kind = map)]
+	async fn block_results(&self, height: Option<Int>) -> RpcResult<Value>;
+
+	/// The GRANDPA authorities, whose ed25519 keys CometBFT clients can decode.
+	#[method(name = "validators", param_kind = map)]
+	async fn validators(
+		&self,
+		height: Option<Int>,
+		page: Option<Int>,
+		per_page: Option<Int>,
+	) -> RpcResult<Value>;
+}
+
+/// The name a Cosmos app registers the codespace under, which is empty on success.
+fn codespace(codespace: u8, code: u8) -> &'static str {
+	match (codespace, code) {
+		(_, 0) => "",
+		(ROOT_CODESPACE, _) => "sdk",
+		(WASM_CODESPACE, _) => "wasm",
+		(FEEGRANT_CODESPACE, _) => "feegrant",
+		(AUTHZ_CODESPACE, _) => "authz",
+		(BANK_CODESPACE, _) => "bank",
+		_ => "undefined",
+	}
+}
+
+fn events(events: &[CosmosEvent]) -> Value {
+	events
+		.iter()
+		.map(|event| {
+			let attributes: Vec<Value> = event
+				.attributes
+				.iter()
+				.map(|attribute| {
+					json!({
+						"key": String::from_utf8_lossy(&attribute.key),
+						"value": String::from_utf8_lossy(&attribute.value),
+						"index": true,
+					})
+				})
+				.collect();
+			json!({ "type": String::from_utf8_lossy(&event.r#type), "attributes": attributes })
+		})
+		.collect()
+}
+
+fn exec_tx_result(tx_result: &TxResult) -> Value {
+	json!({
+		"code": tx_result.code,
+		"data": "",
+		"log": tx_result.log,
+		"info": "",
+		"gas_wanted": tx_result.gas_wanted.to_string(),
+		"gas_used": tx_result.gas_used.to_string(),
+		"events": events(&tx_result.events),
+		"codespace": codespace(tx_result.codespace, tx_result.code),
+	})
+}
+
+fn check_tx_result(check_tx: &BroadcastTxResponse) -> Value {
+	json!({
+		"code": check_tx.code,
+		"data": "",
+		"log": check_tx.log,
+		"info": "",
+		"gas_wanted": "0",
+		"gas_used": "0",
+		"events": [],
+		"codespace": codespace(check_tx.codespace, check_tx.code),
+	})
+}
+
+fn block_id(hash: &H256) -> Value {
+	json!({ "hash": hex::encode_upper(hash), "parts": { "total": 1, "hash": hex::encode_upper(hash) } })
+}
+
+fn decode_tx(tx: &str) -> RpcResult<Bytes> {
+	Base64::decode_vec(tx)
+		.map(Bytes)
+		.map_err(|_| request_error("tx must be base64-encoded"))
+}
+
+/// Formats milliseconds since the Unix epoch as an RFC 3339 UTC time.
+fn rfc3339(millis: u64) -> String {
+	let (days, secs) = (millis / 86_400_000, millis / 1000 % 86_400);
+
+	// Converts days since the epoch to a civil date, counting years from March.
+	let z = days + 719_468;
+	let era = z / 146_097;
+	let doe = z - era * 146_097;
+	let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+	let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+	let mp = (5 * doy + 2) / 153;
+	let day = doy - (153 * mp + 2) / 5 + 1;
+	let month = if mp < 10 { mp + 3 } else { mp - 9 };
+	let year = yoe + era * 400 + u64::from(month <= 2);
+
+	format!(
+		"{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+		year,
+		month,
+		day,
+		secs / 3600,
+		secs / 60 % 60,
+		secs % 60,
+		millis % 1000
+	)
+}
+
+pub struct Cometbft<C, P, S, BE> {
+	client: Arc<C>,
+	cosmos: Cosmos<C, P, S>,
+	/// The address the server listens on.
+	listen_addr: SocketAddr,
+	/// The name of the node.
+	moniker: String,
+	_backend: PhantomData<BE>,
+}
+
+impl<C, P, S, BE> Cometbft<C, P, S, BE>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockBackend<Block>,
+	C: StorageProvider<Block, BE>,
+	C::Api: CosmosRuntimeApi<Block>,
+	S: OffchainStorage,
+	BE: Backend<Block>,
+{
+	pub fn new(
+		client: Arc<C>,
+		pool: Arc<P>,
+		offchain_storage: Option<S>,
+		listen_addr: SocketAddr,
+		moniker: String,
+	) -> Self {
+		Self {
+			client: client.clone(),
+			cosmos: Cosmos::new(client, pool, offchain_storage),
+			listen_addr,
+			moniker,
+			_backend: PhantomData,
+		}
+	}
+
+	/// The height and hash of the best block if `height` is missing or zero, or else of the
+	/// block at `height`.
+	fn block_hash(&self, height: Option<u64>) -> RpcResult<(u64, H256)> {
+		let info = self.client.info();
+		let best_number = u64::from(info.best_number);
+
+		match height {
+			None | Some(0) => Ok((best_number, info.best_hash)),
+			Some(height) => u32::try_from(height)
+				.ok()
+				.filter(|_| height <= best_number)
+				.map(|number| self.client.hash(number))
+				.transpose()
+				.map_err(internal_error)?
+				.flatten()
+				.map(|hash| (height, hash))
+				.ok_or_else(|| {
+					request_error(format!(
+						"height {} must be less than or equal to the current blockchain height {}",
+						height, best_number
+					))
+				}),
+		}
+	}
+
+	fn header(&self, hash: H256) -> RpcResult<<Block as BlockT>::Header> {
+		self.client
+			.header(hash)
+			.map_err(internal_error)?
+			.ok_or_else(|| not_found_error(format!("block {:?} not found", hash)))
+	}
+
+	/// The time the block was produced at, as set by its timestamp inherent.
+	fn block_time(&self, hash: H256) -> RpcResult<String> {
+		let key = StorageKey([twox_128(b"Timestamp"), twox_128(b"Now")].concat());
+		let now = self
+			.client
+			.storage(hash, &key)
+			.map_err(internal_error)?
+			.map(|data| u64::decode(&mut &*data.0).map_err(internal_error))
+			.transpose()?
+			.unwrap_or_default();
+
+		Ok(rfc3339(now))
+	}
+
+	/// The Cosmos txs in the block, along with their indices among its extrinsics.
+	fn block_txs(&self, hash: H256) -> RpcResult<Vec<(u32, Vec<u8>)>> {
+		let body = self
+			.client
+			.block_body(hash)
+			.map_err(internal_error)?
+			.ok_or_else(|| not_found_error(format!("block {:?} not found", hash)))?;
+
+		Ok(body
+			.iter()
+			.enumerate()
+			.filter_map(|(index, extrinsic)| {
+				match UncheckedExtrinsic::decode(&mut &*extrinsic.encode()).ok()?.0.function {
+					RuntimeCall::Cosmos(pallet_cosmos::Call::transact { tx_bytes }) =>
+						Some((index as u32, tx_bytes)),
+					_ => None,
+				}
+			})
+			.collect())
+	}
+
+	fn tx_json(&self, tx_result: TxResult) -> RpcResult<Value> {
+		let (_, hash) = self.block_hash(Some(tx_result.height))?;
+		let (index, tx) = self
+			.block_txs(hash)?
+			.into_iter()
+			.enumerate()
+			.find(|(_, (index, _))| *index == tx_result.index)
+			.map(|(index, (_, tx))| (index, tx))
+			.ok_or_else(|| internal_error("Tx is missing from its block"))?;
+
+		Ok(json!({
+			"hash": hex::encode_upper(tx_result.hash),
+			"height": tx_result.height.to_string(),
+			"index": index,
+			"tx_result": exec_tx_result(&tx_result),
+			"tx": Base64::encode_string(&tx),
+		}))
+	}
+}
+
+#[async_trait]
+impl<C, P, S, BE> CometbftApiServer for Cometbft<C, P, S, BE>
+where
+	C: Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockBackend<Block>,
+	C: BlockchainEvents<Block> + StorageProvider<Block, BE> + 'static,
+	C::Api: Core<Block> + CosmosRuntimeApi<Block> + GrandpaApi<Block>,
+	P: TransactionPool<Block = Block> + 'static,
+	S: OffchainStorage + 'static,
+	BE: Backend<Block> + 'static,
+{
+	async fn status(&self) -> RpcResult<Value> {
+		let info = self.client.info();
+		let chain_id =
+			self.client.runtime_api().chain_id(info.best_hash).map_err(internal_error)?;
+		let latest = self.header(info.best_hash)?;
+		let earliest = self.header(info.genesis_hash)?;
+
+		Ok(json!({
+			"node_info": {
+				"protocol_version": { "p2p": "8", "block": "11", "app": "0" },
+				"id": EMPTY_ADDRESS,
+				"listen_addr": self.listen_addr.to_string(),
+				"network": chain_id,
+				"version": COMETBFT_VERSION,
+				"channels": "",
+				"moniker": self.moniker,
+				"other": { "tx_index": "on", "rpc_address": format!("tcp://{}", self.listen_addr) },
+			},
+			"sync_info": {
+				"latest_block_hash": hex::encode_upper(info.best_hash),
+				"latest_app_hash": hex::encode_upper(latest.state_root()),
+				"latest_block_height": info.best_number.to_string(),
+				"latest_block_time": self.block_time(info.best_hash)?,
+				"earliest_block_hash": hex::encode_upper(info.genesis_hash),
+				"earliest_app_hash": hex::encode_upper(earliest.state_root()),
+				"earliest_block_height": "0",
+				"earliest_block_time": self.block_time(info.genesis_hash)?,
+				"catching_up": false,
+			},
+			"validator_info": {
+				"address": EMPTY_ADDRESS,
+				"pub_key": {
+					"type": "tendermint/PubKeyEd25519",
+					"value": Base64::encode_string(&[0u8; 32]),
+				},
+				"voting_power": "0",
+			},
+		}))
+	}
+
+	async fn abci_info(&self) -> RpcResult<Value> {
+		let info = self.client.info();
+		let version = self.client.runtime_api().version(info.best_hash).map_err(internal_error)?;
+		let header = self.header(info.best_hash)?;
+
+		Ok(json!({
+			"response": {
+				"data": version.spec_name.to_string(),
+				"version": version.spec_version.to_string(),
+				"app_version": "0",
+				"last_block_height": info.best_number.to_string(),
+				"last_block_app_hash": Base64::encode_string(header.state_root().as_ref()),
+			},
+		}))
+	}
+
+	async fn abci_query(
+		&self,
+		path: String,
+		data: String,
+		height: Option<Int>,
+	) -> RpcResult<Value> {
+		let data = hex::decode(data).map_err(|_| request_error("data must be hex-encoded"))?;
+		let (height, hash) = self.block_hash(int(height)?)?;
+
+		let (error, log, value) = match self
+			.client
+			.runtime_api()
+			.abci_query(hash, path.clone(), data)
+			.map_err(internal_error)?
+		{
+			Ok(value) => (None, String::new(), value),
+			Err(QueryError::InvalidAddress) =>
+				(Some(RootError::InvalidAddress), "invalid address".into(), Vec::new()),
+			Err(QueryError::InvalidRequest) =>
+				(Some(RootError::InvalidRequest), "invalid request".into(), Vec::new()),
+			Err(QueryError::NotFound) =>
+				(Some(RootError::NotFound), "not found".into(), Vec::new()),
+			Err(QueryError::UnknownRequest) => (
+				Some(RootError::UnknownRequest),
+				format!("unknown query path {}", path),
+				Vec::new(),
+			),
+		};
+		let CosmosError { codespace: space, code } =
+			error.map_or(CosmosError { codespace: ROOT_CODESPACE, code: 0 }, Into::into);
+
+		Ok(json!({
+			"response": {
+				"code": code,
+				"log": log,
+				"info": "",
+				"index": "0",
+				"key": null,
+				"value": Base64::encode_string(&value),
+				"proofOps": null,
+				"height": height.to_string(),
+				"codespace": codespace(space, code),
+			},
+		}))
+	}
+
+	async fn broadcast_tx_async(&self, tx: String) -> RpcResult<Value> {
+		let hash = self.cosmos.broadcast_tx(decode_tx(&tx)?).await?;
+
+		Ok(json!({
+			"code": 0,
+			"data": "",
+			"log": "",
+			"codespace": "",
+			"hash": hex::encode_upper(hash),
+		}))
+	}
+
+	async fn broadcast_tx_sync(&self, tx: String) -> RpcResult<Value> {
+		let response = self.cosmos.broadcast_tx_sync(decode_tx(&tx)?).await?;
+
+		Ok(json!({
+			"code": response.code,
+			"data": "",
+			"log": response.log,
+			"codespace": codespace(response.codespace, response.code),
+			"hash": hex::encode_upper(response.hash),
+		}))
+	}
+
+	async fn broadcast_tx_commit(&self, tx: String) -> RpcResult<Value> {
+		let BroadcastTxCommitResponse { check_tx, tx_result } =
+			self.cosmos.broadcast_tx_commit(decode_tx(&tx)?).await?;
+
+		Ok(json!({
+			"check_tx": check_tx_result(&check_tx),
+			"tx_result": tx_result.as_ref().map_or_else(
+				|| json!({ "code": 0, "gas_wanted": "0", "gas_used": "0", "events": [] }),
+				exec_tx_result,
+			),
+			"hash": hex::encode_upper(check_tx.hash),
+			"height": tx_result.map_or(0, |tx_result| tx_result.height).to_string(),
+		}))
+	}
+
+	async fn tx(&self, hash: String) -> RpcResult<Value> {
+		let hash = Base64::decode_vec(&hash)
+			.ok()
+			.and_then(|hash| <[u8; 32]>::try_from(hash).ok())
+			.ok_or_else(|| request_error("hash must be a base64-encoded SHA-256 hash"))?;
+
+		let tx_result = self.cosmos.get_tx(H256(hash)).await?;
+		self.tx_json(tx_result)
+	}
+
+	async fn tx_search(
+		&self,
+		query: String,
+		page: Option<Int>,
+		per_page: Option<Int>,
+		order_by: Option<String>,
+	) -> RpcResult<Value> {
+		let page = int(page)?.map(|page| page.min(u32::MAX.into()) as u32);
+		let per_page = int(per_page)?.map(|per_page| per_page.min(MAX_PER_PAGE) as u32);
+		let TxSearchResponse { txs, total_count } =
+			self.cosmos.tx_search(query, page, per_page, order_by).await?;
+
+		let txs = txs
+			.into_iter()
+			.map(|tx_result| self.tx_json(tx_result))
+			.collect::<RpcResult<Vec<_>>>()?;
+
+		Ok(json!({ "txs": txs, "total_count": total_count.to_string() }))
+	}
+
+	async fn block(&self, height: Option<Int>) -> RpcResult<Value> {
+		let (height, hash) = self.block_hash(int(height)?)?;
+		let header = self.header(hash)?;
+		let chain_id = self.client.runtime_api().chain_id(hash).map_err(internal_error)?;
+		let txs: Vec<String> =
+			self.block_txs(hash)?.iter().map(|(_, tx)| Base64::encode_string(tx)).collect();
+
+		Ok(json!({
+			"block_id": block_id(&hash),
+			"block": {
+				"header": {
+					"version": { "block": "11", "app": "0" },
+					"chain_id": chain_id,
+					"height": height.to_string(),
+					"time": self.block_time(hash)?,
+					"last_block_id": block_id(header.parent_hash()),
+					"last_commit_hash": EMPTY_HASH,
+					"data_hash": hex::encode_upper(header.extrinsics_root()),
+					"validators_hash": EMPTY_HASH,
+					"next_validators_hash": EMPTY_HASH,
+					"consensus_hash": EMPTY_HASH,
+					"app_hash": hex::encode_upper(header.state_root()),
+					"last_results_hash": EMPTY_HASH,
+					"evidence_hash": EMPTY_HASH,
+					"proposer_address": EMPTY_ADDRESS,
+				},
+				"data": { "txs": txs },
+				"evidence": { "evidence": [] },
+				"last_commit": {
+					"height": height.saturating_sub(1).to_string(),
+					"round": 0,
+					"block_id": block_id(header.parent_hash()),
+					"signatures": [],
+				},
+			},
+		}))
+	}
+
+	async fn block_results(&self, height: Option<Int>) -> RpcResult<Value> {
+		let (height, hash) = self.block_hash(int(height)?)?;
+		let header = self.header(hash)?;
+
+		let txs_results: Vec<Value> = self
+			.cosmos
+			.block_tx_results::<Block>(hash)?
+			.iter()
+			.map(exec_tx_result)
+			.collect();
+
+		Ok(json!({
+			"height": height.to_string(),
+			"txs_results": txs_results,
+			"finalize_block_events": [],
+			"validator_updates": [],
+			"consensus_param_updates": null,
+			"app_hash": Base64::encode_string(header.state_root().as_ref()),
+		}))
+	}
+
+	async fn validators(
+		&self,
+		height: Option<Int>,
+		page: Option<Int>,
+		per_page: Option<Int>,
+	) -> RpcResult<Value> {
+		let (height, hash) = self.block_hash(int(height)?)?;
+		let authorities =
+			self.client.runtime_api().grandpa_authorities(hash).map_err(internal_error)?;
+
+		let total = authorities.len() as u64;
+		let per_page = int(per_page)?.unwrap_or(DEFAULT_PER_PAGE).clamp(1, MAX_PER_PAGE);
+		let pages = total.div_ceil(per_page).max(1);
+		let page = int(page)?.unwrap_or(1);
+		if page == 0 || page > pages {
+			return Err(request_error(format!("page should be within [1, {}] range", pages)));
+		}
+
+		let validators: Vec<Value> = authorities
+			.iter()
+			.skip(((page - 1) * per_page) as usize)
+			.take(per_page as usize)
+			.map(|(id, weight)| {
+				let pub_key: &[u8] = id.as_ref();
+				json!({
+					"address": hex::encode_upper(&sha2_256(pub_key)[..20]),
+					"pub_key": {
+						"type": "tendermint/PubKeyEd25519",
+						"value": Base64::encode_string(pub_key),
+					},
+					"voting_power": weight.to_string(),
+					"proposer_priority": "0",
+				})
+			})
+			.collect();
+
+		Ok(json!({
+			"block_height": height.to_string(),
+			"count": validators.len().to_string(),
+			"total": total.to_string(),
+			"validators": validators,
+		}))
+	}
+}
+
+/// Serves `methods` at `addr` until the server stops.
+pub async fn serve(addr: SocketAddr, methods: impl Into<Methods>) -> std::io::Result<()> {
+	let server = Server::builder().build(addr).await?;
+	server.start(methods).stopped().await;
+
+	Ok(())
+}
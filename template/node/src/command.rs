@@ -54,6 +54,9 @@ impl SubstrateCli for Cli {
 		Ok(match id {
 			"dev" => Box::new(chain_spec::development_config()?),
 			"" | "local" => Box::new(chain_spec::local_testnet_config()?),
+			// A Cosmos SDK `genesis.json`, as in `--chain cosmos:genesis.json`.
+			id if id.starts_with("cosmos:") =>
+				Box::new(chain_spec::cosmos_genesis_config(&id["cosmos:".len()..])?),
 			path =>
 				Box::new(chain_spec::ChainSpec::from_json_file(std::path::PathBuf::from(path))?),
 		})
@@ -121,9 +124,21 @@ pub fn run() -> sc_cli::Result<()> {
 			runner.sync_run(|config| cmd.run::<Block>(&config))
 		},
 		None => {
+			if let Some(min_gas_prices) = &cli.min_gas_prices {
+				pallet_cosmos_types::coin::parse_dec_coins(min_gas_prices)
+					.map_err(|e| format!("Invalid --min-gas-prices: {:?}", e))?;
+				hp_io::set_min_gas_prices(min_gas_prices.clone());
+			}
+
+			let cometbft_rpc_addr = cli.cometbft_rpc_addr;
+			let cosmos_tx_index = !cli.no_cosmos_tx_index;
 			let runner = cli.create_runner(&cli.run)?;
-			runner.run_node_until_exit(|config| async move {
-				service::new_full(config).map_err(sc_cli::Error::Service)
+			runner.run_node_until_exit(|mut config| async move {
+				// cosmos_getTx serves tx results from the offchain index.
+				if cosmos_tx_index {
+					config.offchain_worker.indexing_enabled = true;
+				}
+				service::new_full(config, cometbft_rpc_addr).map_err(sc_cli::Error::Service)
 			})
 		},
 	}
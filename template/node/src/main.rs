@@ -21,6 +21,7 @@
 
 mod chain_spec;
 mod cli;
+mod cometbft;
 mod command;
 mod rpc;
 mod service;
@@ -26,7 +26,7 @@ use sc_service::{error::Error as ServiceError, Configuration, TaskManager, WarpS
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
-use std::{sync::Arc, time::Duration};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 
 pub(crate) type FullClient = sc_service::TFullClient<
 	Block,
@@ -145,7 +145,10 @@ pub fn new_partial(
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
+pub fn new_full(
+	config: Configuration,
+	cometbft_rpc_addr: Option<SocketAddr>,
+) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -207,6 +210,18 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 		);
 	}
 
+	// The runtime only writes Cosmos tx results offchain when indexing is enabled.
+	let offchain_storage =
+		if config.offchain_worker.indexing_enabled { backend.offchain_storage() } else { None };
+	if let Some(offchain_storage) = offchain_storage.clone() {
+		let client = client.clone();
+		task_manager.spawn_handle().spawn_blocking("cosmos-tx-index", None, async move {
+			if let Err(e) = cosmos_rpc::index::run(client, offchain_storage).await {
+				log::error!("Failed to index Cosmos txs: {}", e);
+			}
+		});
+	}
+
 	let role = config.role.clone();
 	let force_authoring = config.force_authoring;
 	let backoff_authoring_blocks: Option<()> = None;
@@ -217,14 +232,36 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
+		let offchain_storage = offchain_storage.clone();
 
 		Box::new(move |deny_unsafe, _| {
-			let deps =
-				crate::rpc::FullDeps { client: client.clone(), pool: pool.clone(), deny_unsafe };
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				deny_unsafe,
+				offchain_storage: offchain_storage.clone(),
+			};
 			crate::rpc::create_full(deps).map_err(Into::into)
 		})
 	};
 
+	if let Some(addr) = cometbft_rpc_addr {
+		use crate::cometbft::{Cometbft, CometbftApiServer};
+
+		let cometbft = Cometbft::<_, _, _, FullBackend>::new(
+			client.clone(),
+			transaction_pool.clone(),
+			offchain_storage,
+			addr,
+			name.clone(),
+		);
+		task_manager.spawn_handle().spawn("cometbft-rpc", None, async move {
+			if let Err(e) = crate::cometbft::serve(addr, cometbft.into_rpc()).await {
+				log::error!("Failed to serve CometBFT RPC at {}: {}", addr, e);
+			}
+		});
+	}
+
 	let _rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
 		network: network.clone(),
 		client: client.clone(),
@@ -20,11 +20,15 @@ use crate::AccountId;
 use alloc::{string::String, vec::Vec};
 use bech32::{Bech32, Hrp};
 use core::marker::PhantomData;
+use cosmos_sdk_proto::{prost::Message, Any};
 use hp_account::CosmosSigner;
 use hp_crypto::EcdsaExt;
-use pallet_cosmos::AddressMapping;
+use pallet_cosmos::{AccountNumbers as _, AddressMapping};
 use sp_core::{crypto::UncheckedFrom, Get, H160, H256};
-use sp_runtime::traits::Convert;
+use sp_runtime::{
+	traits::{Convert, LookupError, StaticLookup},
+	MultiAddress,
+};
 
 pub struct AccountToAddr<T>(PhantomData<T>);
 
@@ -63,7 +67,12 @@ where
 	fn convert(address: Vec<u8>) -> Result<AccountId, ()> {
 		// Cosmos address length is 20, contract address is 32.
 		let account = match address.len() {
-			20 => T::AddressMapping::into_account_id(H160::from_slice(&address)),
+			20 => {
+				// Contracts send funds to the addresses they resolve, which may be new.
+				let address = H160::from_slice(&address);
+				T::AccountNumbers::ensure_account_number(&address);
+				T::AddressMapping::into_account_id(address)
+			},
 			32 => AccountId::unchecked_from(H256::from_slice(&address)),
 			_ => return Err(()),
 		};
@@ -71,3 +80,277 @@ where
 		Ok(account)
 	}
 }
+
+/// Looks up accounts by id or by 20-byte Cosmos address, so that calls such as balance
+/// transfers can fund an address that is not connected to a key yet. Addresses are numbered
+/// as they are looked up, since they may be about to receive funds.
+pub struct AccountLookup<T>(PhantomData<T>);
+
+impl<T> StaticLookup for AccountLookup<T>
+where
+	T: pallet_cosmos::Config<AccountId = AccountId>,
+{
+	type Source = MultiAddress<AccountId, ()>;
+	type Target = AccountId;
+
+	fn lookup(source: Self::Source) -> Result<Self::Target, LookupError> {
+		match source {
+			MultiAddress::Id(who) => Ok(who),
+			MultiAddress::Address20(address) => {
+				let address = H160::from(address);
+				T::AccountNumbers::ensure_account_number(&address);
+				Ok(T::AddressMapping::into_account_id(address))
+			},
+			_ => Err(LookupError),
+		}
+	}
+
+	fn unlookup(target: Self::Target) -> Self::Source {
+		MultiAddress::Id(target)
+	}
+}
+
+pub struct AccountNumbers<T>(PhantomData<T>);
+
+impl<T> pallet_cosmos::AccountNumbers for AccountNumbers<T>
+where
+	T: pallet_cosmos_accounts::Config,
+{
+	fn account_number(address: &H160) -> Option<u64> {
+		pallet_cosmos_accounts::Pallet::<T>::account_number(address)
+	}
+
+	fn ensure_account_number(address: &H160) {
+		pallet_cosmos_accounts::Pallet::<T>::ensure_account_number(address);
+	}
+}
+
+pub struct AccountPubKeys<T>(PhantomData<T>);
+
+impl<T> pallet_cosmos::AccountPubKeys for AccountPubKeys<T>
+where
+	T: pallet_cosmos_accounts::Config,
+{
+	fn pub_key(address: &H160) -> Option<Any> {
+		pallet_cosmos_accounts::Pallet::<T>::pub_key(address)
+			.and_then(|pub_key| Any::decode(&mut &*pub_key).ok())
+	}
+
+	fn set_pub_key(address: &H160, pub_key: &Any) {
+		pallet_cosmos_accounts::Pallet::<T>::set_pub_key(address, pub_key.encode_to_vec());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		compat::cosmos::HashedAddressMapping, Assets, Balances, Block, CodeStorageByteDeposit,
+		ExistentialDeposit, Runtime, RuntimeCall, RuntimeOrigin, System,
+	};
+	use cosmos_runtime_api::CosmosRuntimeApi;
+	use cosmos_sdk_proto::{
+		cosmos::{
+			bank::v1beta1::MsgSend,
+			crypto::secp256k1,
+			tx::{
+				signing::v1beta1::SignMode,
+				v1beta1::{
+					mode_info::{Single, Sum},
+					AuthInfo, Fee, ModeInfo, SignDoc, SignerInfo, Tx, TxBody,
+				},
+			},
+		},
+		prost::Name,
+	};
+	use fp_self_contained::SelfContainedCall;
+	use frame_support::{dispatch::GetDispatchInfo, traits::Currency};
+	use pallet_cosmos::config_preludes::{AddressPrefix, ChainId};
+	use sp_core::{ecdsa, Pair};
+	use sp_runtime::{traits::BlakeTwo256, BuildStorage};
+
+	fn funder() -> AccountId {
+		CosmosSigner(ecdsa::Pair::from_string("//Bob", None).unwrap().public())
+	}
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut storage =
+			frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(funder(), 1_000_000 * ExistentialDeposit::get())],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(storage);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	fn acc_address(address: &H160) -> String {
+		let hrp = Hrp::parse(AddressPrefix::get()).unwrap();
+		bech32::encode::<Bech32>(hrp, address.as_bytes()).unwrap()
+	}
+
+	/// A `MsgSend` back to the funder, signed in direct mode with the first sequence.
+	fn first_tx(pair: &ecdsa::Pair, account_number: u64) -> Vec<u8> {
+		let address = CosmosSigner(pair.public()).to_cosmos_address().unwrap();
+		let msg = MsgSend {
+			from_address: acc_address(&address),
+			to_address: acc_address(&funder().to_cosmos_address().unwrap()),
+			amount: vec![cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+				denom: "acdt".into(),
+				amount: "1".into(),
+			}],
+		};
+		let body = TxBody {
+			messages: vec![Any { type_url: MsgSend::type_url(), value: msg.encode_to_vec() }],
+			..Default::default()
+		};
+		let public_key = secp256k1::PubKey { key: pair.public().0.to_vec() };
+		let auth_info = AuthInfo {
+			signer_infos: vec![SignerInfo {
+				public_key: Some(Any {
+					type_url: secp256k1::PubKey::type_url(),
+					value: public_key.encode_to_vec(),
+				}),
+				mode_info: Some(ModeInfo {
+					sum: Some(Sum::Single(Single { mode: SignMode::Direct as i32 })),
+				}),
+				sequence: 0,
+			}],
+			fee: Some(Fee { gas_limit: 200_000, ..Default::default() }),
+			..Default::default()
+		};
+		let sign_doc = SignDoc {
+			body_bytes: body.encode_to_vec(),
+			auth_info_bytes: auth_info.encode_to_vec(),
+			chain_id: ChainId::get().into(),
+			account_number,
+		};
+		let signature = pair.sign_prehashed(&sp_io::hashing::sha2_256(&sign_doc.encode_to_vec()));
+
+		Tx {
+			body: Some(body),
+			auth_info: Some(auth_info),
+			signatures: vec![signature.0[..64].to_vec()],
+		}
+		.encode_to_vec()
+	}
+
+	/// Queries the number of the account of `pair` and validates its first tx signed over it.
+	fn assert_first_tx_valid(pair: &ecdsa::Pair) {
+		let address = CosmosSigner(pair.public()).to_cosmos_address().unwrap();
+		let account = <Runtime as CosmosRuntimeApi<Block>>::account(acc_address(&address)).unwrap();
+		assert_eq!(account.sequence, 0);
+
+		let tx_bytes = first_tx(pair, account.account_number);
+		let len = tx_bytes.len();
+		let call = RuntimeCall::Cosmos(pallet_cosmos::Call::transact { tx_bytes });
+		let info = call.check_self_contained().unwrap().unwrap();
+		assert!(call
+			.validate_self_contained(&info, &call.get_dispatch_info(), len)
+			.unwrap()
+			.is_ok());
+	}
+
+	#[test]
+	fn balance_transfer_numbers_address_test() {
+		new_test_ext().execute_with(|| {
+			let pair = ecdsa::Pair::from_string("//Alice", None).unwrap();
+			let address = CosmosSigner(pair.public()).to_cosmos_address().unwrap();
+			assert_eq!(AccountNumbers::<Runtime>::account_number(&address), None);
+
+			assert!(Balances::transfer_allow_death(
+				RuntimeOrigin::signed(funder()),
+				MultiAddress::Address20(address.0),
+				1_000 * ExistentialDeposit::get(),
+			)
+			.is_ok());
+			assert!(AccountNumbers::<Runtime>::account_number(&address).is_some());
+
+			assert_first_tx_valid(&pair);
+		});
+	}
+
+	#[test]
+	fn contract_transfer_numbers_address_test() {
+		new_test_ext().execute_with(|| {
+			let pair = ecdsa::Pair::from_string("//Alice", None).unwrap();
+			let address = CosmosSigner(pair.public()).to_cosmos_address().unwrap();
+
+			// The recipient of a `BankMsg::Send` is resolved the same way.
+			let to =
+				<Runtime as pallet_cosmwasm::Config>::AccountToAddr::convert(acc_address(&address))
+					.unwrap();
+			assert!(AccountNumbers::<Runtime>::account_number(&address).is_some());
+			assert!(<Balances as Currency<AccountId>>::transfer(
+				&funder(),
+				&to,
+				1_000 * ExistentialDeposit::get(),
+				frame_support::traits::ExistenceRequirement::AllowDeath,
+			)
+			.is_ok());
+
+			assert_first_tx_valid(&pair);
+		});
+	}
+
+	#[test]
+	fn first_tx_migrates_imported_account_test() {
+		let pair = ecdsa::Pair::from_string("//Alice", None).unwrap();
+		let who = CosmosSigner(pair.public());
+		let address = who.to_cosmos_address().unwrap();
+		let code = include_bytes!("../../../frame/cosmos/contracts/cw20_base.wasm").to_vec();
+		let deposit = code.len() as u128 * CodeStorageByteDeposit::get() as u128;
+		let balance = deposit + 1_000 * ExistentialDeposit::get();
+
+		// Imported as by the genesis converter, with the coins and code deposit of the address
+		// held by its interim account.
+		let interim = HashedAddressMapping::<Runtime, BlakeTwo256>::interim_account_id(address);
+		let mut storage =
+			frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> { balances: vec![(interim.clone(), balance)] }
+			.assimilate_storage(&mut storage)
+			.unwrap();
+		pallet_assets::GenesisConfig::<Runtime> {
+			assets: vec![(1, funder(), true, 1)],
+			metadata: vec![],
+			accounts: vec![(1, interim.clone(), 100)],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+		pallet_cosmos_accounts::GenesisConfig::<Runtime> {
+			accounts: vec![],
+			account_numbers: vec![(address, 5)],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+		pallet_cosmwasm::GenesisConfig::<Runtime> {
+			contracts: vec![],
+			codes: vec![(1, interim.clone(), code.try_into().unwrap())],
+			instances: vec![],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
+		sp_io::TestExternalities::new(storage).execute_with(|| {
+			System::set_block_number(1);
+			assert_eq!(Balances::reserved_balance(&interim), deposit);
+
+			let tx_bytes = first_tx(&pair, 5);
+			let len = tx_bytes.len();
+			let call = RuntimeCall::Cosmos(pallet_cosmos::Call::transact { tx_bytes });
+			let info = call.check_self_contained().unwrap().unwrap();
+			assert!(call
+				.pre_dispatch_self_contained(&info, &call.get_dispatch_info(), len)
+				.unwrap()
+				.is_ok());
+
+			assert_eq!(Assets::balance(1, &who), 100);
+			assert_eq!(Balances::total_balance(&who), balance);
+			assert_eq!(Balances::reserved_balance(&who), deposit);
+			assert!(!System::account_exists(&interim));
+		});
+	}
+}
@@ -17,6 +17,7 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use alloc::string::{String, ToString};
+use cosmwasm_std::{DenomMetadata, DenomUnit};
 use frame_support::{ensure, traits::fungibles::metadata::Inspect};
 use sp_core::Get;
 use sp_runtime::{traits::Convert, BoundedVec};
@@ -43,7 +44,8 @@ where
 
 	fn destroyed(id: &T::AssetId) -> Result<(), ()> {
 		if let Some(denom) = pallet_cosmos::AssetDenomRouter::<T>::take(id) {
-			pallet_cosmos::DenomAssetRouter::<T>::remove(denom);
+			pallet_cosmos::DenomAssetRouter::<T>::remove(&denom);
+			pallet_cosmos::DenomMetadata::<T>::remove(denom);
 		}
 
 		Ok(())
@@ -80,3 +82,109 @@ where
 		}
 	}
 }
+
+pub struct DenomToMetadata<T>(core::marker::PhantomData<T>);
+impl<T> Convert<String, Option<DenomMetadata>> for DenomToMetadata<T>
+where
+	T: pallet_cosmos::Config,
+{
+	fn convert(denom: String) -> Option<DenomMetadata> {
+		let metadata = pallet_cosmos_x_bank::query::denom_metadata::<T>(&denom).ok()?;
+
+		Some(DenomMetadata {
+			description: metadata.description,
+			denom_units: metadata
+				.denom_units
+				.into_iter()
+				.map(|unit| DenomUnit {
+					denom: unit.denom,
+					exponent: unit.exponent,
+					aliases: unit.aliases,
+				})
+				.collect(),
+			base: metadata.base,
+			display: metadata.display,
+			name: metadata.name,
+			symbol: metadata.symbol,
+			uri: metadata.uri,
+			uri_hash: metadata.uri_hash,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Assets, Runtime, RuntimeOrigin};
+	use cosmos_sdk_proto::{
+		cosmos::bank::v1beta1::{QueryDenomMetadataRequest, QueryDenomMetadataResponse},
+		prost::Message,
+	};
+	use hp_account::CosmosSigner;
+	use pallet_cosmos_types::query::QueryRouter as _;
+	use sp_core::ecdsa;
+	use sp_runtime::BuildStorage;
+
+	#[test]
+	fn denom_metadata_follows_asset_metadata_test() {
+		let owner = CosmosSigner(ecdsa::Public::from_raw([1u8; 33]));
+		let mut storage =
+			frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+		pallet_assets::GenesisConfig::<Runtime> {
+			assets: vec![(1, owner, true, 1)],
+			metadata: vec![(1, b"Stake".to_vec(), b"stake".to_vec(), 0)],
+			accounts: vec![],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+		pallet_cosmos::GenesisConfig::<Runtime> {
+			assets: vec![(b"stake".to_vec(), 1)],
+			blocked_addrs: vec![],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
+		sp_io::TestExternalities::new(storage).execute_with(|| {
+			let query = || {
+				let handler = crate::query::QueryRouter::<Runtime>::route(
+					"/cosmos.bank.v1beta1.Query/DenomMetadata",
+				)
+				.unwrap();
+				let request = QueryDenomMetadataRequest { denom: "stake".into() }.encode_to_vec();
+				QueryDenomMetadataResponse::decode(&*handler.handle(&request).unwrap())
+					.unwrap()
+					.metadata
+					.unwrap()
+			};
+
+			let metadata = query();
+			assert_eq!(metadata.name, "Stake");
+			assert_eq!(metadata.display, "stake");
+
+			// Metadata changed in the assets pallet shows up in the derived metadata.
+			assert!(Assets::force_set_metadata(
+				RuntimeOrigin::root(),
+				1u128.into(),
+				b"Staking Token".to_vec(),
+				b"stake".to_vec(),
+				6,
+				false,
+			)
+			.is_ok());
+			let metadata = query();
+			assert_eq!(metadata.name, "Staking Token");
+			assert_eq!(metadata.denom_units.len(), 2);
+			assert_eq!(metadata.denom_units[1].exponent, 6);
+
+			// Metadata set for Cosmos clients takes precedence.
+			let mut custom =
+				pallet_cosmos_x_bank::query::denom_metadata::<Runtime>("stake").unwrap();
+			custom.description = "The staking token".into();
+			assert!(pallet_cosmos::Pallet::<Runtime>::set_denom_metadata(
+				RuntimeOrigin::root(),
+				custom,
+			)
+			.is_ok());
+			assert_eq!(query().description, "The staking token");
+		});
+	}
+}
@@ -0,0 +1,40 @@
+// This file is part of Horizion.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use alloc::string::String;
+use frame_support::traits::Contains;
+
+pub struct SendEnabled<T>(core::marker::PhantomData<T>);
+impl<T> Contains<String> for SendEnabled<T>
+where
+	T: pallet_cosmos::Config,
+{
+	fn contains(denom: &String) -> bool {
+		pallet_cosmos::Pallet::<T>::is_send_enabled(denom)
+	}
+}
+
+pub struct BlockedAddrs<T>(core::marker::PhantomData<T>);
+impl<T> Contains<T::AccountId> for BlockedAddrs<T>
+where
+	T: pallet_cosmos::Config,
+{
+	fn contains(who: &T::AccountId) -> bool {
+		pallet_cosmos::Pallet::<T>::is_blocked_addr(who)
+	}
+}
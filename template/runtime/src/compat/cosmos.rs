@@ -37,6 +37,18 @@ where
 		if let Some(x) = pallet_cosmos_accounts::Connections::<T>::get(address) {
 			return x;
 		}
+		Self::interim_account_id(address)
+	}
+}
+
+impl<T, H> HashedAddressMapping<T, H>
+where
+	T: pallet_cosmos_accounts::Config,
+	T::AccountId: From<CosmosSigner>,
+	H: Hasher<Out = H256>,
+{
+	/// The account that holds the assets of `address` until it is connected to a key.
+	pub fn interim_account_id(address: H160) -> T::AccountId {
 		let mut data = [0u8; 25];
 		data[0..5].copy_from_slice(b"cosm:");
 		data[5..25].copy_from_slice(&address[..]);
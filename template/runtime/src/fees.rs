@@ -0,0 +1,261 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{accounts::AccountToAddr, AccountId};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use frame_support::traits::{fungibles::Balanced, Currency, FindAuthor, Get, Imbalance};
+use pallet_cosmos::{CreditOf, NegativeImbalanceOf, OnChargeCosmosFee};
+use pallet_cosmos_types::events::CosmosEvent;
+use sp_runtime::{traits::Convert, Percent, SaturatedConversion};
+
+/// The account of the author of the current block, as found by `Author` in its digest.
+fn block_author<T, Author>() -> Option<T::AccountId>
+where
+	T: frame_system::Config,
+	Author: FindAuthor<T::AccountId>,
+{
+	let digest = frame_system::Pallet::<T>::digest();
+	Author::find_author(digest.logs.iter().filter_map(|d| d.as_pre_runtime()))
+}
+
+/// The events of `amount` of `denom` paid out of the fee collector to `to`, or burned.
+fn payout_events<T>(to: Option<&AccountId>, denom: &str, amount: u128) -> Vec<CosmosEvent>
+where
+	T: pallet_cosmos::Config,
+{
+	let to = to.map(|who| AccountToAddr::<T>::convert(who.clone()));
+	pallet_cosmos::Pallet::<T>::fee_payout_events(to.as_deref(), denom, amount)
+}
+
+/// Splits the fees between the block author found by `Author` and the treasury, giving the
+/// treasury `TreasuryShare` of each fee. Without a known author, the treasury receives all of it.
+pub struct DealWithFees<T, Author, TreasuryAccount, TreasuryShare>(
+	PhantomData<(T, Author, TreasuryAccount, TreasuryShare)>,
+);
+
+impl<T, Author, TreasuryAccount, TreasuryShare> OnChargeCosmosFee<T>
+	for DealWithFees<T, Author, TreasuryAccount, TreasuryShare>
+where
+	T: pallet_cosmos::Config<AccountId = AccountId>,
+	Author: FindAuthor<T::AccountId>,
+	TreasuryAccount: Get<T::AccountId>,
+	TreasuryShare: Get<Percent>,
+{
+	fn on_native_fee(fee: NegativeImbalanceOf<T>) -> Vec<CosmosEvent> {
+		let denom = T::NativeDenom::get();
+		let treasury = TreasuryShare::get() * fee.peek();
+		let (to_treasury, to_author) = fee.split(treasury);
+
+		let mut events = Vec::new();
+		let to_treasury = match block_author::<T, Author>() {
+			Some(author) => {
+				events.extend(payout_events::<T>(
+					Some(&author),
+					denom,
+					to_author.peek().saturated_into(),
+				));
+				T::NativeAsset::resolve_creating(&author, to_author);
+				to_treasury
+			},
+			None => to_treasury.merge(to_author),
+		};
+		let treasury_account = TreasuryAccount::get();
+		events.extend(payout_events::<T>(
+			Some(&treasury_account),
+			denom,
+			to_treasury.peek().saturated_into(),
+		));
+		T::NativeAsset::resolve_creating(&treasury_account, to_treasury);
+
+		events
+	}
+
+	fn on_asset_fee(fee: CreditOf<T>) -> Vec<CosmosEvent> {
+		let denom = T::AssetToDenom::convert(fee.asset());
+		let treasury = TreasuryShare::get() * fee.peek();
+		let (to_treasury, to_author) = fee.split(treasury);
+
+		let mut events = Vec::new();
+		let to_treasury = match block_author::<T, Author>() {
+			Some(author) => {
+				let amount = to_author.peek().into();
+				match T::Assets::resolve(&author, to_author) {
+					Ok(()) => {
+						events.extend(payout_events::<T>(Some(&author), &denom, amount));
+						to_treasury
+					},
+					Err(to_author) =>
+						to_treasury.merge(to_author).unwrap_or_else(|(credit, _)| credit),
+				}
+			},
+			None => to_treasury.merge(to_author).unwrap_or_else(|(credit, _)| credit),
+		};
+		// Whatever the treasury cannot take, e.g. below the minimum balance, is burned.
+		let treasury_account = TreasuryAccount::get();
+		let amount = to_treasury.peek().into();
+		let to = T::Assets::resolve(&treasury_account, to_treasury)
+			.is_ok()
+			.then_some(&treasury_account);
+		events.extend(payout_events::<T>(to, &denom, amount));
+
+		events
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		opaque::SessionKeys, AccountId, Assets, Balances, ExistentialDeposit, Runtime, System,
+		TreasuryAccount, TreasuryFeeShare,
+	};
+	use alloc::format;
+	use hp_account::CosmosSigner;
+	use parity_scale_codec::Encode;
+	use sp_consensus_aura::{Slot, AURA_ENGINE_ID};
+	use sp_core::{ecdsa, ed25519, sr25519, Pair};
+	use sp_runtime::{BuildStorage, ConsensusEngineId, DigestItem};
+
+	fn author() -> AccountId {
+		CosmosSigner(ecdsa::Public::from_raw([1u8; 33]))
+	}
+
+	struct TestAuthor;
+	impl FindAuthor<AccountId> for TestAuthor {
+		fn find_author<'a, I>(_digests: I) -> Option<AccountId>
+		where
+			I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+		{
+			Some(author())
+		}
+	}
+
+	/// The type, address and amount of a coin event.
+	fn coin_event(event: &CosmosEvent) -> (&str, &str, &str) {
+		(
+			core::str::from_utf8(&event.r#type).unwrap(),
+			core::str::from_utf8(&event.attributes[0].value).unwrap(),
+			core::str::from_utf8(&event.attributes[1].value).unwrap(),
+		)
+	}
+
+	#[test]
+	fn deal_with_fees_test() {
+		let storage = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+		sp_io::TestExternalities::new(storage).execute_with(|| {
+			let fee = 1_000 * ExistentialDeposit::get();
+			let to_treasury = TreasuryFeeShare::get() * fee;
+
+			let events =
+				DealWithFees::<Runtime, TestAuthor, TreasuryAccount, TreasuryFeeShare>::on_native_fee(
+					Balances::issue(fee),
+				);
+			assert_eq!(Balances::free_balance(author()), fee - to_treasury);
+			assert_eq!(Balances::free_balance(TreasuryAccount::get()), to_treasury);
+
+			// Both shares leave the fee collector for their receivers.
+			let fee_collector = pallet_cosmos::Pallet::<Runtime>::fee_collector();
+			let author_address = AccountToAddr::<Runtime>::convert(author());
+			let treasury_address = AccountToAddr::<Runtime>::convert(TreasuryAccount::get());
+			let to_author = format!("{}acdt", fee - to_treasury);
+			let to_treasury_amount = format!("{}acdt", to_treasury);
+			assert_eq!(
+				events.iter().map(coin_event).collect::<Vec<_>>(),
+				vec![
+					("coin_spent", fee_collector.as_str(), to_author.as_str()),
+					("coin_received", author_address.as_str(), to_author.as_str()),
+					("coin_spent", fee_collector.as_str(), to_treasury_amount.as_str()),
+					("coin_received", treasury_address.as_str(), to_treasury_amount.as_str()),
+				]
+			);
+
+			// Without an author, the treasury receives the whole fee.
+			DealWithFees::<Runtime, (), TreasuryAccount, TreasuryFeeShare>::on_native_fee(
+				Balances::issue(fee),
+			);
+			assert_eq!(Balances::free_balance(author()), fee - to_treasury);
+			assert_eq!(Balances::free_balance(TreasuryAccount::get()), to_treasury + fee);
+			assert_eq!(Balances::total_issuance(), 2 * fee);
+		});
+	}
+
+	#[test]
+	fn asset_fee_burn_events_test() {
+		let mut storage =
+			frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+		pallet_assets::GenesisConfig::<Runtime> {
+			assets: vec![(1, author(), true, 1_000_000)],
+			metadata: vec![(1, b"stake".to_vec(), b"stake".to_vec(), 0)],
+			accounts: vec![],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+		pallet_cosmos::GenesisConfig::<Runtime> {
+			assets: vec![(b"stake".to_vec(), 1)],
+			blocked_addrs: vec![],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
+		sp_io::TestExternalities::new(storage).execute_with(|| {
+			// The treasury can't take a fee below the minimum balance, so it's burned.
+			let events =
+				DealWithFees::<Runtime, (), TreasuryAccount, TreasuryFeeShare>::on_asset_fee(
+					Assets::issue(1, 1_000),
+				);
+			assert_eq!(Assets::balance(1, TreasuryAccount::get()), 0);
+
+			let fee_collector = pallet_cosmos::Pallet::<Runtime>::fee_collector();
+			assert_eq!(
+				events.iter().map(coin_event).collect::<Vec<_>>(),
+				vec![
+					("coin_spent", fee_collector.as_str(), "1000stake"),
+					("burn", fee_collector.as_str(), "1000stake"),
+				]
+			);
+		});
+	}
+
+	#[test]
+	fn runtime_fee_author_test() {
+		let validator = CosmosSigner(ecdsa::Pair::from_string("//Alice", None).unwrap().public());
+		let keys = SessionKeys {
+			aura: sr25519::Pair::from_string("//Alice", None).unwrap().public().into(),
+			grandpa: ed25519::Pair::from_string("//Alice", None).unwrap().public().into(),
+		};
+		let mut storage =
+			frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+		pallet_session::GenesisConfig::<Runtime> {
+			keys: vec![(validator.clone(), validator.clone(), keys)],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
+		sp_io::TestExternalities::new(storage).execute_with(|| {
+			System::deposit_log(DigestItem::PreRuntime(AURA_ENGINE_ID, Slot::from(7).encode()));
+			let fee = 1_000 * ExistentialDeposit::get();
+			let to_treasury = TreasuryFeeShare::get() * fee;
+
+			// The validator whose Aura key sealed the block is paid its share.
+			<Runtime as pallet_cosmos::Config>::OnChargeFee::on_native_fee(Balances::issue(fee));
+			assert_eq!(Balances::free_balance(&validator), fee - to_treasury);
+			assert_eq!(Balances::free_balance(TreasuryAccount::get()), to_treasury);
+		});
+	}
+}
@@ -31,15 +31,23 @@ extern crate alloc;
 mod accounts;
 mod ante;
 mod assets;
-mod compat;
+mod bank;
+pub mod compat;
+mod fees;
 mod msgs;
+mod query;
 
 use alloc::{boxed::Box, format, string::String, vec::Vec};
 use core::marker::PhantomData;
-use cosmos_runtime_api::{GasInfo, SimulateError, SimulateResponse, SimulateResult};
+use cosmos_runtime_api::{
+	AccountError, BalancesResponse, BaseAccount, BlockParams, Coin, ConsensusParams, GasInfo,
+	Metadata as DenomMetadata, PageRequest, PubKey, QueryError, SimulateError, SimulateResponse,
+	SimulateResult, SimulateWithGasRecordsResponse, SimulateWithGasRecordsResult, SupplyResponse,
+};
 use cosmos_sdk_proto::{cosmos::tx::v1beta1::Tx, prost::Message};
 use frame_support::{
 	construct_runtime, derive_impl,
+	dispatch::DispatchClass,
 	genesis_builder_helper::{build_config, create_default_config},
 	pallet_prelude::InvalidTransaction,
 	parameter_types,
@@ -55,15 +63,20 @@ use frame_support::{
 };
 use frame_system::EnsureRoot;
 use hp_account::CosmosSigner;
-use hp_crypto::EcdsaExt;
 use pallet_cosmos::{
 	config_preludes::{
-		AddressPrefix, ChainId, MaxDenomLimit, MaxMemoCharacters, MsgFilter, NativeAssetId,
-		NativeDenom, TxSigLimit, WeightToGas,
+		AddressPrefix, ChainId, MaxDenomLimit, MaxMemoCharacters, NativeAssetId, NativeDenom,
+		RefundUnusedGas, SigVerifyCostSecp256k1, TxLongevity, TxSigLimit, TxSizeCostPerByte,
+		WeightToGas,
 	},
 	AddressMapping,
 };
-use pallet_cosmos_types::{address::acc_address_from_bech32, any_match, context::Context};
+use pallet_cosmos_types::{
+	address::acc_address_from_bech32,
+	any_match,
+	context::Context,
+	gas::{Gas, TracingGasMeter},
+};
 use pallet_cosmos_x_auth_signing::{
 	sign_mode_handler::SignModeHandler, sign_verifiable_tx::SigVerifiableTx,
 };
@@ -78,11 +91,11 @@ use sp_core::{crypto::KeyTypeId, ecdsa::Public, OpaqueMetadata, H160};
 use sp_runtime::{
 	codec, create_runtime_str, generic, impl_opaque_keys,
 	traits::{
-		AccountIdLookup, BlakeTwo256, Block as BlockT, Convert, DispatchInfoOf, Dispatchable,
-		IdentifyAccount, NumberFor, One, PostDispatchInfoOf, Verify,
+		AccountIdConversion, BlakeTwo256, Block as BlockT, Convert, ConvertInto, DispatchInfoOf,
+		IdentifyAccount, NumberFor, One, OpaqueKeys, PostDispatchInfoOf, Verify,
 	},
 	transaction_validity::{TransactionSource, TransactionValidity, TransactionValidityError},
-	ApplyExtrinsicResult, ExtrinsicInclusionMode, Perbill,
+	ApplyExtrinsicResult, ExtrinsicInclusionMode, Perbill, Percent,
 };
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
@@ -190,7 +203,7 @@ impl frame_system::Config for Runtime {
 	/// The identifier used to distinguish between accounts.
 	type AccountId = AccountId;
 	/// The lookup mechanism to get account ID from whatever is passed in dispatchers.
-	type Lookup = AccountIdLookup<AccountId, ()>;
+	type Lookup = accounts::AccountLookup<Runtime>;
 	/// The block type.
 	type Block = Block;
 	/// The ubiquitous event type.
@@ -208,7 +221,7 @@ impl frame_system::Config for Runtime {
 	/// The data to be stored in an account.
 	type AccountData = pallet_balances::AccountData<Balance>;
 	/// What to do if a new account is created.
-	type OnNewAccount = ();
+	type OnNewAccount = CosmosAccounts;
 	/// What to do if an account is fully reaped from the system.
 	type OnKilledAccount = ();
 	/// Weight information for the extrinsics of this pallet.
@@ -254,6 +267,25 @@ impl pallet_aura::Config for Runtime {
 	type AllowMultipleBlocksPerSlot = ConstBool<false>;
 }
 
+parameter_types! {
+	/// Sessions last an hour. The validator set stays as set at genesis, so sessions only mark
+	/// when changes to session keys take effect.
+	pub const SessionPeriod: BlockNumber = (60 * 60 * 1000 / MILLISECS_PER_BLOCK) as BlockNumber;
+	pub const SessionOffset: BlockNumber = 0;
+}
+
+impl pallet_session::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ValidatorId = AccountId;
+	type ValidatorIdOf = ConvertInto;
+	type ShouldEndSession = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+	type NextSessionRotation = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+	type SessionManager = ();
+	type SessionHandler = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = opaque::SessionKeys;
+	type WeightInfo = pallet_session::weights::SubstrateWeight<Runtime>;
+}
+
 impl pallet_grandpa::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 
@@ -333,6 +365,13 @@ impl pallet_timestamp::Config for Runtime {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const TreasuryPalletId: PalletId = PalletId(*b"hrzn/trs");
+	pub TreasuryAccount: AccountId = TreasuryPalletId::get().into_account_truncating();
+	pub const TreasuryFeeShare: Percent = Percent::from_percent(20);
+	pub MaxBlockGas: Option<Gas> = Some(NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT.ref_time());
+}
+
 impl pallet_cosmos::Config for Runtime {
 	/// Mapping an address to an account id.
 	type AddressMapping = compat::cosmos::HashedAddressMapping<Self, BlakeTwo256>;
@@ -358,7 +397,7 @@ impl pallet_cosmos::Config for Runtime {
 	/// The chain ID.
 	type ChainId = ChainId;
 	/// The message filter.
-	type MsgFilter = MsgFilter;
+	type MsgFilter = msgs::MsgFilter;
 	/// Converts Weight to Gas and Gas to Weight.
 	type WeightToGas = WeightToGas;
 	/// The maximum number of transaction signatures allowed.
@@ -379,6 +418,44 @@ impl pallet_cosmos::Config for Runtime {
 	type Context = Context;
 
 	type NativeAssetId = NativeAssetId;
+	/// No chain-level minimum gas prices; nodes may set their own with `--min-gas-prices`.
+	type MinGasPrices = ();
+	/// The number of blocks a Cosmos transaction stays valid in the pool.
+	type TxLongevity = TxLongevity;
+	/// Whether the fee for unused gas is refunded to the fee payer after execution.
+	type RefundUnusedGas = RefundUnusedGas;
+	/// Splits fees between the block author, the validator whose Aura key sealed the block, and
+	/// the treasury.
+	type OnChargeFee = fees::DealWithFees<
+		Runtime,
+		pallet_session::FindAccountFromAuthorIndex<Runtime, Aura>,
+		TreasuryAccount,
+		TreasuryFeeShare,
+	>;
+	/// Fee allowances that let a granter pay the fees of a grantee.
+	type FeeGrant = CosmosFeegrant;
+	/// Account numbers assigned by the Cosmos accounts pallet.
+	type AccountNumbers = accounts::AccountNumbers<Runtime>;
+	/// Public keys stored by the Cosmos accounts pallet.
+	type AccountPubKeys = accounts::AccountPubKeys<Runtime>;
+	/// The gas charged for each byte of a transaction.
+	type TxSizeCostPerByte = TxSizeCostPerByte;
+	/// The gas charged for verifying a secp256k1 signature.
+	type SigVerifyCostSecp256k1 = SigVerifyCostSecp256k1;
+	/// Cosmos transactions may use as much gas as normal extrinsics have weight.
+	type MaxBlockGas = MaxBlockGas;
+	/// Denomination metadata and other bank parameters are managed by root.
+	type AdminOrigin = EnsureRoot<AccountId>;
+}
+
+impl pallet_cosmos_x_authz::Config for Runtime {
+	/// Time used to check grant expirations.
+	type UnixTime = Timestamp;
+}
+
+impl pallet_cosmos_x_feegrant::Config for Runtime {
+	/// Time used to check allowance expirations and periods.
+	type UnixTime = Timestamp;
 }
 
 impl pallet_cosmos_accounts::Config for Runtime {
@@ -414,6 +491,9 @@ impl pallet_cosmwasm::Config for Runtime {
 	type MaxMessageSize = ConstU32<{ 64 * 1024 }>;
 	type AccountToAddr = accounts::AccountToAddr<Runtime>;
 	type AssetToDenom = assets::AssetToDenom<Runtime>;
+	type DenomToMetadata = assets::DenomToMetadata<Runtime>;
+	type SendEnabled = bank::SendEnabled<Runtime>;
+	type BlockedAddrs = bank::BlockedAddrs<Runtime>;
 	type Balance = Balance;
 	type AssetId = AssetId;
 	type Assets = Assets;
@@ -462,9 +542,12 @@ construct_runtime!(
 		System: frame_system,
 		Balances: pallet_balances,
 		Assets: pallet_assets,
+		Session: pallet_session,
 		Aura: pallet_aura,
 		Cosmos: pallet_cosmos,
 		CosmosAccounts: pallet_cosmos_accounts,
+		CosmosAuthz: pallet_cosmos_x_authz,
+		CosmosFeegrant: pallet_cosmos_x_feegrant,
 		Cosmwasm: pallet_cosmwasm,
 		Grandpa: pallet_grandpa,
 		Sudo: pallet_sudo,
@@ -506,8 +589,12 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
+/// Migrations to apply on runtime upgrade.
+pub type Migrations = (pallet_cosmos_accounts::migrations::v1::MigrateToV1<Runtime>,);
+
 impl fp_self_contained::SelfContainedCall for RuntimeCall {
 	type SignedInfo = AccountId;
 
@@ -532,7 +619,7 @@ impl fp_self_contained::SelfContainedCall for RuntimeCall {
 
 	fn validate_self_contained(
 		&self,
-		info: &Self::SignedInfo,
+		_info: &Self::SignedInfo,
 		dispatch_info: &DispatchInfoOf<RuntimeCall>,
 		len: usize,
 	) -> Option<TransactionValidity> {
@@ -544,7 +631,13 @@ impl fp_self_contained::SelfContainedCall for RuntimeCall {
 					}
 				}
 
-				call.validate_self_contained(&info.to_cosmos_address().unwrap(), dispatch_info, len)
+				// The signed info of a fee payer that was not connected yet is its interim
+				// account, which has no address to derive.
+				let origin = match call.check_self_contained()? {
+					Ok(origin) => origin,
+					Err(e) => return Some(Err(e)),
+				};
+				call.validate_self_contained(&origin, dispatch_info, len)
 			},
 			_ => None,
 		}
@@ -572,13 +665,10 @@ impl fp_self_contained::SelfContainedCall for RuntimeCall {
 
 	fn apply_self_contained(
 		self,
-		info: Self::SignedInfo,
+		_info: Self::SignedInfo,
 	) -> Option<sp_runtime::DispatchResultWithInfo<PostDispatchInfoOf<Self>>> {
 		match self {
-			call @ RuntimeCall::Cosmos(pallet_cosmos::Call::transact { .. }) =>
-				Some(call.dispatch(RuntimeOrigin::from(
-					pallet_cosmos::RawOrigin::CosmosTransaction(info.to_cosmos_address().unwrap()),
-				))),
+			RuntimeCall::Cosmos(call) => call.apply_self_contained(),
 			_ => None,
 		}
 	}
@@ -586,10 +676,31 @@ impl fp_self_contained::SelfContainedCall for RuntimeCall {
 
 impl Runtime {
 	fn migrate_cosm_account(tx_bytes: &[u8]) -> Result<(), TransactionValidityError> {
-		use cosmos_sdk_proto::cosmos::crypto::secp256k1;
+		use cosmos_sdk_proto::cosmos::crypto::{multisig::LegacyAminoPubKey, secp256k1};
+		use frame_support::traits::{fungibles, ReservableCurrency};
 		use fungible::{Inspect, Mutate};
 		use pallet_cosmos_x_auth_signing::sign_verifiable_tx::traits::SigVerifiableTx;
 
+		// Moves what `from` can spend of its native balance to `to`.
+		let transfer_native =
+			|from: &AccountId, to: &AccountId| -> Result<(), InvalidTransaction> {
+				let balance = pallet_balances::Pallet::<Runtime>::reducible_balance(
+					from,
+					Preservation::Expendable,
+					Fortitude::Polite,
+				);
+				if balance > 0 {
+					<pallet_balances::Pallet<Runtime> as Mutate<AccountId>>::transfer(
+						from,
+						to,
+						balance,
+						Preservation::Expendable,
+					)
+					.map_err(|_| InvalidTransaction::Call)?;
+				}
+				Ok(())
+			};
+
 		let tx = Tx::decode(&mut &*tx_bytes).map_err(|_| InvalidTransaction::Call)?;
 		let signers = <Runtime as pallet_cosmos::Config>::SigVerifiableTx::get_signers(&tx)
 			.map_err(|_| InvalidTransaction::Call)?;
@@ -605,10 +716,9 @@ impl Runtime {
 				if address_raw.len() != 20 {
 					return Err(InvalidTransaction::BadSigner.into());
 				}
+				let address = H160::from_slice(&address_raw);
 				let interim_account =
-					<Runtime as pallet_cosmos::Config>::AddressMapping::into_account_id(
-						H160::from_slice(&address_raw),
-					);
+					<Runtime as pallet_cosmos::Config>::AddressMapping::into_account_id(address);
 
 				let public_key = signer_info.public_key.as_ref().ok_or(InvalidTransaction::Call)?;
 				let who = any_match!(
@@ -619,25 +729,46 @@ impl Runtime {
 							pk.copy_from_slice(&public_key.key);
 
 							Ok(CosmosSigner(Public(pk)))
+						},
+						// Multisig accounts have no key of their own and stay on the mapped account.
+						LegacyAminoPubKey => {
+							pallet_cosmos_accounts::Pallet::<Runtime>::ensure_account_number(&address);
+							continue
 						}
 					},
 					Err(InvalidTransaction::Call)
 				)?;
 
-				let balance = pallet_balances::Pallet::<Runtime>::reducible_balance(
-					&interim_account,
-					Preservation::Expendable,
-					Fortitude::Polite,
-				);
-				<pallet_balances::Pallet<Runtime> as Mutate<AccountId>>::transfer(
-					&interim_account,
-					&who,
-					balance,
-					Preservation::Expendable,
-				)
-				.map_err(|_| InvalidTransaction::Call)?;
+				// Code deposits are reserved on the creator of the codes and move along with them.
+				let deposit =
+					pallet_cosmwasm::Pallet::<Runtime>::hand_over_codes(&interim_account, &who)
+						.map_err(|_| InvalidTransaction::Call)?;
+				<Balances as ReservableCurrency<AccountId>>::unreserve(&interim_account, deposit);
+				transfer_native(&interim_account, &who)?;
+				<Balances as ReservableCurrency<AccountId>>::reserve(&who, deposit)
+					.map_err(|_| InvalidTransaction::Call)?;
 
-				// TODO: Add asset transfer for migration
+				for asset_id in <Assets as fungibles::InspectEnumerable<AccountId>>::asset_ids() {
+					let balance = <Assets as fungibles::Inspect<AccountId>>::reducible_balance(
+						asset_id,
+						&interim_account,
+						Preservation::Expendable,
+						Fortitude::Polite,
+					);
+					if balance > 0 {
+						<Assets as fungibles::Mutate<AccountId>>::transfer(
+							asset_id,
+							&interim_account,
+							&who,
+							balance,
+							Preservation::Expendable,
+						)
+						.map_err(|_| InvalidTransaction::Call)?;
+					}
+				}
+				// Accounts of assets that are not sufficient keep the interim account alive, so
+				// the existential deposit is left until they are gone.
+				transfer_native(&interim_account, &who)?;
 
 				pallet_cosmos_accounts::Pallet::<Runtime>::connect_account(&who)
 					.map_err(|_| InvalidTransaction::Call)?;
@@ -646,6 +777,58 @@ impl Runtime {
 
 		Ok(())
 	}
+
+	fn simulate_tx(tx_bytes: Vec<u8>) -> SimulateWithGasRecordsResult {
+		use pallet_cosmos_types::{context::traits::Context as _, handler::AnteDecorator};
+
+		let tx = Tx::decode(&mut &*tx_bytes).map_err(|_| SimulateError::InvalidTx)?;
+
+		// Drop the events of the block so far, leaving only those of the simulation. The state
+		// changes of a runtime API call are discarded anyway.
+		System::reset_events();
+
+		// Simulations run without a gas limit, so the gas used is what the tx needs. Unlike in
+		// blocks, the gas meter also records what the gas is consumed for.
+		let mut ctx = Context::<TracingGasMeter>::new(Gas::MAX);
+		<ante::AnteHandler<Runtime> as AnteDecorator<_>>::ante_handle(&mut ctx, &tx, true)
+			.map_err(|e| {
+				SimulateError::InternalError(
+					format!("Failed to run ante handlers. error: {:?}", e).into(),
+				)
+			})?;
+
+		pallet_cosmos::Pallet::<Runtime>::execute_transaction(tx, &mut ctx).map_err(|e| {
+			SimulateError::InternalError(
+				format!("Failed to simulate cosmos tx. error: {:?}", e).into(),
+			)
+		})?;
+
+		let mut ante_events = Vec::new();
+		for record in System::read_events_no_consensus() {
+			match record.event {
+				RuntimeEvent::Cosmos(pallet_cosmos::Event::AnteHandled(events)) => {
+					ante_events.extend(events);
+				},
+				RuntimeEvent::Cosmos(pallet_cosmos::Event::Executed {
+					gas_wanted,
+					gas_used,
+					events,
+				}) => {
+					ante_events.extend(events);
+					return Ok(SimulateWithGasRecordsResponse {
+						response: SimulateResponse {
+							gas_info: GasInfo { gas_wanted, gas_used },
+							events: ante_events,
+						},
+						gas_records: ctx.gas_meter.records().to_vec(),
+					});
+				},
+				_ => {},
+			}
+		}
+
+		Err(SimulateError::InternalError("Cosmos events does not exist".into()))
+	}
 }
 
 impl_runtime_apis! {
@@ -657,21 +840,85 @@ impl_runtime_apis! {
 		}
 
 		fn simulate(tx_bytes: Vec<u8>) -> SimulateResult {
-			let tx = Tx::decode(&mut &*tx_bytes).map_err(|_| SimulateError::InvalidTx)?;
+			Runtime::simulate_tx(tx_bytes).map(|response| response.response)
+		}
 
-			// TODO: Run ante handlers
+		fn simulate_with_gas_records(tx_bytes: Vec<u8>) -> SimulateWithGasRecordsResult {
+			Runtime::simulate_tx(tx_bytes)
+		}
 
-			pallet_cosmos::Pallet::<Runtime>::apply_validated_transaction(tx)
-				.map_err(|e| SimulateError::InternalError(format!("Failed to simulate cosmos tx. error: {:?}", e).into()))?;
+		fn consensus_params() -> ConsensusParams {
+			let max_bytes = BlockLength::get().max.get(DispatchClass::Normal);
+			let max_gas = MaxBlockGas::get().map_or(-1, |max_gas| max_gas.min(i64::MAX as u64) as i64);
 
-			System::read_events_no_consensus()
-				.find_map(|record| {
-					if let RuntimeEvent::Cosmos(pallet_cosmos::Event::Executed { gas_wanted, gas_used, events }) = record.event {
-						Some(SimulateResponse{gas_info: GasInfo { gas_wanted, gas_used }, events})
-					} else {
-						None
-					}
-				}).ok_or(SimulateError::InternalError("Cosmos events does not exist".into()))
+			ConsensusParams { block: BlockParams { max_bytes: max_bytes.into(), max_gas } }
+		}
+
+		fn chain_id() -> String {
+			ChainId::get().into()
+		}
+
+		fn account_number(address: H160) -> Option<u64> {
+			CosmosAccounts::account_number(&address)
+		}
+
+		fn account(address: String) -> Result<BaseAccount, AccountError> {
+			let account = pallet_cosmos_x_auth::query::account::<Runtime>(&address).map_err(|e| {
+				match e {
+					QueryError::InvalidAddress => AccountError::InvalidAddress,
+					_ => AccountError::NotFound,
+				}
+			})?;
+
+			Ok(BaseAccount {
+				address: account.address,
+				pub_key: account
+					.pub_key
+					.map(|pub_key| PubKey { type_url: pub_key.type_url, value: pub_key.value }),
+				account_number: account.account_number,
+				sequence: account.sequence,
+			})
+		}
+
+		fn balance(address: String, denom: String) -> Result<Coin, QueryError> {
+			pallet_cosmos_x_bank::query::balance::<Runtime>(&address, &denom)
+		}
+
+		fn all_balances(
+			address: String,
+			pagination: Option<PageRequest>,
+		) -> Result<BalancesResponse, QueryError> {
+			pallet_cosmos_x_bank::query::all_balances::<Runtime>(&address, pagination)
+				.map(|(balances, pagination)| BalancesResponse { balances, pagination })
+		}
+
+		fn spendable_balances(
+			address: String,
+			pagination: Option<PageRequest>,
+		) -> Result<BalancesResponse, QueryError> {
+			pallet_cosmos_x_bank::query::spendable_balances::<Runtime>(&address, pagination)
+				.map(|(balances, pagination)| BalancesResponse { balances, pagination })
+		}
+
+		fn total_supply(pagination: Option<PageRequest>) -> Result<SupplyResponse, QueryError> {
+			pallet_cosmos_x_bank::query::total_supply::<Runtime>(pagination)
+				.map(|(supply, pagination)| SupplyResponse { supply, pagination })
+		}
+
+		fn supply_of(denom: String) -> Coin {
+			pallet_cosmos_x_bank::query::supply_of::<Runtime>(&denom)
+		}
+
+		fn denom_metadata(denom: String) -> Result<DenomMetadata, QueryError> {
+			pallet_cosmos_x_bank::query::denom_metadata::<Runtime>(&denom)
+		}
+
+		fn abci_query(path: String, data: Vec<u8>) -> Result<Vec<u8>, QueryError> {
+			use pallet_cosmos_types::query::QueryRouter as _;
+
+			query::QueryRouter::<Runtime>::route(&path)
+				.ok_or(QueryError::UnknownRequest)?
+				.handle(&data)
 		}
 	}
 
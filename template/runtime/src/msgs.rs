@@ -19,32 +19,51 @@
 use alloc::boxed::Box;
 use core::marker::PhantomData;
 use cosmos_sdk_proto::{
-	cosmos::bank::v1beta1::MsgSend,
+	cosmos::{
+		authz::v1beta1::{MsgExec, MsgGrant, MsgRevoke},
+		bank::v1beta1::{MsgMultiSend, MsgSend},
+		feegrant::v1beta1::{MsgGrantAllowance, MsgRevokeAllowance},
+	},
 	cosmwasm::wasm::v1::{
 		MsgExecuteContract, MsgInstantiateContract2, MsgMigrateContract, MsgStoreCode,
 		MsgUpdateAdmin,
 	},
 	Any,
 };
+use frame_support::traits::Contains;
 use hp_crypto::EcdsaExt;
 use pallet_cosmos_types::{any_match, context, msgservice::MsgHandler};
-use pallet_cosmos_x_bank::msgs::MsgSendHandler;
+use pallet_cosmos_x_authz::msgs::{MsgExecHandler, MsgGrantHandler, MsgRevokeHandler};
+use pallet_cosmos_x_bank::msgs::{MsgMultiSendHandler, MsgSendHandler};
+use pallet_cosmos_x_feegrant::msgs::{MsgGrantAllowanceHandler, MsgRevokeAllowanceHandler};
 use pallet_cosmos_x_wasm::msgs::{
 	MsgExecuteContractHandler, MsgInstantiateContract2Handler, MsgMigrateContractHandler,
 	MsgStoreCodeHandler, MsgUpdateAdminHandler,
 };
 
 pub struct MsgServiceRouter<T>(PhantomData<T>);
-impl<T, Context> pallet_cosmos_types::msgservice::MsgServiceRouter<Context> for MsgServiceRouter<T>
+impl<T> pallet_cosmos_types::msgservice::MsgServiceRouter for MsgServiceRouter<T>
 where
-	T: frame_system::Config + pallet_cosmos::Config + pallet_cosmwasm::Config,
+	T: frame_system::Config
+		+ pallet_cosmos::Config
+		+ pallet_cosmos_x_authz::Config
+		+ pallet_cosmos_x_feegrant::Config
+		+ pallet_cosmwasm::Config,
 	T::AccountId: EcdsaExt,
-	Context: context::traits::Context,
 {
-	fn route(msg: &Any) -> Option<Box<dyn MsgHandler<Context>>> {
+	fn route<Context>(msg: &Any) -> Option<Box<dyn MsgHandler<Context>>>
+	where
+		Context: context::traits::Context,
+	{
 		any_match!(
 			msg, {
+				MsgGrant => Some(Box::<MsgGrantHandler<T>>::default()),
+				MsgExec => Some(Box::<MsgExecHandler<T>>::default()),
+				MsgRevoke => Some(Box::<MsgRevokeHandler<T>>::default()),
 				MsgSend => Some(Box::<MsgSendHandler<T>>::default()),
+				MsgMultiSend => Some(Box::<MsgMultiSendHandler<T>>::default()),
+				MsgGrantAllowance => Some(Box::<MsgGrantAllowanceHandler<T>>::default()),
+				MsgRevokeAllowance => Some(Box::<MsgRevokeAllowanceHandler<T>>::default()),
 				MsgStoreCode => Some(Box::<MsgStoreCodeHandler<T>>::default()),
 				MsgInstantiateContract2 => Some(Box::<MsgInstantiateContract2Handler<T>>::default()),
 				MsgExecuteContract => Some(Box::<MsgExecuteContractHandler<T>>::default()),
@@ -55,3 +74,26 @@ where
 		)
 	}
 }
+
+pub struct MsgFilter;
+impl Contains<Any> for MsgFilter {
+	fn contains(msg: &Any) -> bool {
+		any_match!(
+			msg, {
+				MsgGrant => true,
+				MsgExec => true,
+				MsgRevoke => true,
+				MsgSend => true,
+				MsgMultiSend => true,
+				MsgGrantAllowance => true,
+				MsgRevokeAllowance => true,
+				MsgStoreCode => true,
+				MsgInstantiateContract2 => true,
+				MsgExecuteContract => true,
+				MsgMigrateContract => true,
+				MsgUpdateAdmin => true,
+			},
+			false
+		)
+	}
+}
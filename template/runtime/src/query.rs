@@ -0,0 +1,116 @@
+// This file is part of Horizion.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use alloc::boxed::Box;
+use core::marker::PhantomData;
+use pallet_cosmos_types::query::QueryHandler;
+use pallet_cosmos_x_auth::query::QueryAccountHandler;
+use pallet_cosmos_x_bank::query::{
+	QueryAllBalancesHandler, QueryBalanceHandler, QueryDenomMetadataHandler,
+	QuerySpendableBalancesHandler, QuerySupplyOfHandler, QueryTotalSupplyHandler,
+};
+use pallet_cosmos_x_wasm::query::QuerySmartContractStateHandler;
+
+pub struct QueryRouter<T>(PhantomData<T>);
+impl<T> pallet_cosmos_types::query::QueryRouter for QueryRouter<T>
+where
+	T: pallet_cosmos::Config + pallet_cosmwasm::Config,
+{
+	fn route(path: &str) -> Option<Box<dyn QueryHandler>> {
+		match path {
+			"/cosmos.auth.v1beta1.Query/Account" => Some(Box::<QueryAccountHandler<T>>::default()),
+			"/cosmos.bank.v1beta1.Query/Balance" => Some(Box::<QueryBalanceHandler<T>>::default()),
+			"/cosmos.bank.v1beta1.Query/AllBalances" =>
+				Some(Box::<QueryAllBalancesHandler<T>>::default()),
+			"/cosmos.bank.v1beta1.Query/SpendableBalances" =>
+				Some(Box::<QuerySpendableBalancesHandler<T>>::default()),
+			"/cosmos.bank.v1beta1.Query/TotalSupply" =>
+				Some(Box::<QueryTotalSupplyHandler<T>>::default()),
+			"/cosmos.bank.v1beta1.Query/SupplyOf" =>
+				Some(Box::<QuerySupplyOfHandler<T>>::default()),
+			"/cosmos.bank.v1beta1.Query/DenomMetadata" =>
+				Some(Box::<QueryDenomMetadataHandler<T>>::default()),
+			"/cosmwasm.wasm.v1.Query/SmartContractState" =>
+				Some(Box::<QuerySmartContractStateHandler<T>>::default()),
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Block, Runtime, System};
+	use alloc::string::String;
+	use cosmos_runtime_api::{AccountError, CosmosRuntimeApi};
+	use cosmos_sdk_proto::{
+		cosmos::auth::v1beta1::{BaseAccount, QueryAccountRequest, QueryAccountResponse},
+		prost::Message,
+	};
+	use pallet_cosmos::AddressMapping;
+	use pallet_cosmos_types::query::{QueryError, QueryRouter as _};
+	use sp_core::H160;
+	use sp_runtime::BuildStorage;
+
+	/// Queries `address` through the gRPC query router.
+	fn query_account(address: &str) -> Result<BaseAccount, QueryError> {
+		let handler = QueryRouter::<Runtime>::route("/cosmos.auth.v1beta1.Query/Account").unwrap();
+		let request = QueryAccountRequest { address: address.into() }.encode_to_vec();
+		let response = QueryAccountResponse::decode(&*handler.handle(&request)?).unwrap();
+
+		Ok(BaseAccount::decode(&*response.account.unwrap().value).unwrap())
+	}
+
+	#[test]
+	fn account_queries_agree_test() {
+		let storage = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+		sp_io::TestExternalities::new(storage).execute_with(|| {
+			// cosmos1qd69nuwj95gta4akjgyxtj9ujmz4w8edmqysqw, numbered and with a sequence but no
+			// balance, and an address never seen.
+			let numbered = String::from("cosmos1qd69nuwj95gta4akjgyxtj9ujmz4w8edmqysqw");
+			let address =
+				H160::from_slice(&hex::decode("037459f1d22d10bed7b6920865c8bc96c5571f2d").unwrap());
+			let number = pallet_cosmos_accounts::Pallet::<Runtime>::ensure_account_number(&address);
+			System::inc_account_nonce(
+				<Runtime as pallet_cosmos::Config>::AddressMapping::into_account_id(address),
+			);
+			let unknown = String::from("cosmos1n5jgx64zw38ss76mzuu4uc7jeyqw2vfjk60fdz");
+
+			let account = <Runtime as CosmosRuntimeApi<Block>>::account(numbered.clone()).unwrap();
+			assert_eq!(account.account_number, number);
+			assert_eq!(account.sequence, 1);
+			let queried = query_account(&numbered).unwrap();
+			assert_eq!(
+				(queried.address, queried.account_number, queried.sequence),
+				(account.address, account.account_number, account.sequence)
+			);
+
+			assert_eq!(
+				<Runtime as CosmosRuntimeApi<Block>>::account(unknown.clone()),
+				Err(AccountError::NotFound)
+			);
+			assert_eq!(query_account(&unknown), Err(QueryError::NotFound));
+
+			assert_eq!(
+				<Runtime as CosmosRuntimeApi<Block>>::account("cosmos1invalid".into()),
+				Err(AccountError::InvalidAddress)
+			);
+			assert_eq!(query_account("cosmos1invalid"), Err(QueryError::InvalidAddress));
+		});
+	}
+}